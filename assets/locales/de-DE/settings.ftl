@@ -0,0 +1,243 @@
+# Settings menu strings, German
+
+## Menu
+
+menu-settings = Einstellungen
+menu-title = Spieleinstellungen
+title-video = Grafikeinstellungen
+title-audio = Audioeinstellungen
+title-gameplay = Spieleinstellungen
+title-controls = Steuerungseinstellungen
+title-interface = Oberflächeneinstellungen
+button-test = Testen
+
+tab-video = Grafik
+tab-audio = Audio
+tab-gameplay = Spiel
+tab-controls = Steuerung
+tab-interface = Oberfläche
+
+## Values
+
+value-on = An
+value-off = Aus
+value-number = { $value }
+value-size = { $width }x{ $height }
+value-none = Keine
+
+## Video
+
+setting-video-display-mode = Anzeigemodus
+setting-video-resolution = Auflösung
+setting-video-graphics-quality = Grafikqualität
+setting-video-vsync = VSync
+setting-video-fps-limit = FPS-Begrenzung
+setting-video-fps-limit-value = { $value } FPS
+setting-video-fps-limit-none = Unbegrenzt
+setting-video-ui-scale = UI-Skalierung
+
+display-mode-windowed = Fenster
+display-mode-fullscreen = Vollbild
+display-mode-borderless = Rahmenlos
+graphics-quality-low = Niedrig
+graphics-quality-medium = Mittel
+graphics-quality-high = Hoch
+graphics-quality-ultra = Ultra
+
+## Audio
+
+setting-audio-master-volume = Gesamtlautstärke
+setting-audio-music-volume = Musiklautstärke
+setting-audio-sfx-volume = Effektlautstärke
+setting-audio-voice-volume = Sprachlautstärke
+setting-audio-ambient-volume = Umgebungslautstärke
+setting-audio-mute-when-inactive = Im Hintergrund stummschalten
+
+## Gameplay
+
+setting-gameplay-difficulty = Schwierigkeit
+setting-gameplay-custom-difficulty-ai-resource-bonus = KI-Ressourcenbonus
+setting-gameplay-custom-difficulty-ai-reaction-delay = KI-Reaktionszeit
+setting-gameplay-custom-difficulty-player-damage-multiplier = Schaden des Spielers
+setting-gameplay-custom-difficulty-ai-damage-multiplier = KI-Schaden
+setting-gameplay-custom-difficulty-ai-ignores-fog = KI sieht durch den Nebel
+setting-gameplay-show-tutorials = Tutorials anzeigen
+setting-gameplay-subtitles = Untertitel
+setting-gameplay-closed-captions = Untertitel für Geräusche
+setting-gameplay-subtitle-style-size = Untertitelgröße
+setting-gameplay-subtitle-style-background-opacity = Untertitelhintergrund
+setting-gameplay-subtitle-style-speaker-names = Sprechernamen
+setting-gameplay-subtitle-style-speaker-colors = Sprecherfarben
+setting-gameplay-game-speed = Spielgeschwindigkeit
+setting-gameplay-game-speed-value = { $value }×
+setting-gameplay-autosave-interval-minutes = Automatisch speichern alle
+setting-gameplay-autosave-interval-minutes-value = { $value } Minuten
+setting-gameplay-autosave-interval-minutes-none = Nie
+setting-gameplay-autosave-slots = Autosave-Plätze
+setting-gameplay-alerts-under-attack = Warnung bei Angriffen
+setting-gameplay-alerts-idle-workers = Warnung bei untätigen Arbeitern
+setting-gameplay-alerts-research-complete = Meldung bei abgeschlossener Forschung
+setting-gameplay-health-bars = Lebensbalken
+setting-gameplay-waypoints = Wegpunkte
+
+difficulty-easy = Leicht
+difficulty-normal = Normal
+difficulty-hard = Schwer
+difficulty-custom = Benutzerdefiniert
+difficulty-custom-heading = Benutzerdefinierte Schwierigkeit
+difficulty-locked = Die Schwierigkeit kann während eines Spiels nicht geändert werden
+
+health-bar-visibility-always = Immer
+health-bar-visibility-selected = Ausgewählte Einheiten
+health-bar-visibility-damaged = Beschädigte Einheiten
+waypoint-display-selected = Ausgewählte Einheiten
+waypoint-display-queuing = Beim Einreihen
+waypoint-display-never = Nie
+
+## Controls
+
+setting-controls-mouse-sensitivity = Mausempfindlichkeit
+setting-controls-invert-y = Y-Achse umkehren
+setting-controls-mouse-acceleration = Mausbeschleunigung
+setting-controls-mouse-dead-zone = Maus-Totzone
+setting-controls-camera-edge-scroll = Randscrollen
+setting-controls-camera-edge-scroll-margin = Randscroll-Abstand
+setting-controls-camera-edge-scroll-margin-value =
+    { $value ->
+        [one] { $value } Pixel
+       *[other] { $value } Pixel
+    }
+setting-controls-camera-edge-scroll-speed = Randscroll-Geschwindigkeit
+setting-controls-camera-keyboard-pan-speed = Tastatur-Schwenkgeschwindigkeit
+setting-controls-camera-zoom-speed = Zoomgeschwindigkeit
+setting-controls-camera-min-zoom = Minimaler Zoom
+setting-controls-camera-max-zoom = Maximaler Zoom
+setting-controls-camera-middle-mouse-pan = Mit mittlerer Maustaste schwenken
+setting-controls-camera-lock-cursor = Mauszeiger im Fenster halten
+setting-controls-controller-left-stick-inner-dead-zone = Linker Stick, innere Totzone
+setting-controls-controller-left-stick-outer-dead-zone = Linker Stick, äußere Totzone
+setting-controls-controller-left-stick-response-curve = Linker Stick, Reaktionskurve
+setting-controls-controller-left-stick-custom-exponent = Linker Stick, Kurvenexponent
+setting-controls-controller-right-stick-inner-dead-zone = Rechter Stick, innere Totzone
+setting-controls-controller-right-stick-outer-dead-zone = Rechter Stick, äußere Totzone
+setting-controls-controller-right-stick-response-curve = Rechter Stick, Reaktionskurve
+setting-controls-controller-right-stick-custom-exponent = Rechter Stick, Kurvenexponent
+setting-controls-controller-left-trigger-threshold = Linker Trigger, Schwelle
+setting-controls-controller-right-trigger-threshold = Rechter Trigger, Schwelle
+setting-controls-controller-cursor-speed = Gamepad-Cursorgeschwindigkeit
+setting-controls-controller-rumble-strength = Vibrationsstärke
+setting-controls-keybinds-camera-pan-up = Kamera nach oben
+setting-controls-keybinds-camera-pan-down = Kamera nach unten
+setting-controls-keybinds-camera-pan-left = Kamera nach links
+setting-controls-keybinds-camera-pan-right = Kamera nach rechts
+
+acceleration-curve-off = Aus
+acceleration-curve-linear = Linear
+acceleration-curve-quadratic = Quadratisch
+response-curve-linear = Linear
+response-curve-quadratic = Quadratisch
+response-curve-custom = Benutzerdefiniert
+
+keymap-preset = Tastenbelegung
+keymap-preset-classic = Klassisch
+keymap-preset-grid = Raster
+keymap-preset-left-handed = Linkshänder
+keymap-import = Importieren
+keymap-export = Exportieren
+keymap-applied = Vorlage „{ $preset }“ übernommen
+keymap-preset-failed = Vorlage „{ $preset }“ konnte nicht geladen werden
+keymap-imported =
+    { $ignored ->
+        [0] Tastenbelegung „{ $name }“ geladen
+        [one] Tastenbelegung „{ $name }“ geladen, { $ignored } unbekannter Eintrag ignoriert
+       *[other] Tastenbelegung „{ $name }“ geladen, { $ignored } unbekannte Einträge ignoriert
+    }
+keymap-import-failed = Import fehlgeschlagen: { $error }
+keymap-exported = Tastenbelegung nach { $path } exportiert
+keymap-export-failed = Export fehlgeschlagen: { $error }
+
+## Interface
+
+setting-interface-language = Sprache
+setting-interface-ui-scale = UI-Skalierung
+setting-interface-colorblind-mode = Farbenblindmodus
+setting-interface-colorblind-strength = Stärke der Farbkorrektur
+setting-interface-text-scale = Textgröße
+setting-interface-high-contrast = Hoher Kontrast
+setting-interface-reduce-motion = Bewegung reduzieren
+setting-interface-dyslexia-font = Legasthenie-freundliche Schrift
+setting-interface-cursor-size = Mauszeigergröße
+setting-interface-hud-minimap-position = Position der Minikarte
+setting-interface-hud-minimap-size = Größe der Minikarte
+setting-interface-hud-minimap-rotation = Drehung der Minikarte
+setting-interface-hud-command-card-layout = Anordnung der Befehlsleiste
+setting-interface-hud-resource-bar-position = Position der Ressourcenleiste
+setting-interface-hud-chat-opacity = Deckkraft des Chats
+setting-interface-hud-chat-fade-time = Ausblendzeit des Chats
+setting-interface-hud-chat-fade-time-value = { $value } s
+setting-interface-hud-tooltip-delay = Verzögerung der Tooltips
+setting-interface-hud-tooltip-delay-value = { $value } s
+setting-interface-hud-tooltip-detail = Detailgrad der Tooltips
+setting-interface-hud-performance-overlay = FPS und Ping
+
+language-english = English
+language-german = Deutsch
+language-french = Français
+language-spanish = Español
+colorblind-mode-off = Aus
+colorblind-mode-protanopia = Protanopie
+colorblind-mode-deuteranopia = Deuteranopie
+colorblind-mode-tritanopia = Tritanopie
+colorblind-mode-achromatopsia = Achromatopsie
+cursor-size-normal = Normal
+cursor-size-large = Groß
+cursor-size-extra-large = Sehr groß
+screen-corner-bottom-left = Unten links
+screen-corner-bottom-right = Unten rechts
+screen-corner-top-left = Oben links
+screen-corner-top-right = Oben rechts
+minimap-rotation-north-up = Norden oben
+minimap-rotation-isometric = Isometrisch
+minimap-rotation-follow-camera = Kamera folgen
+command-card-layout-grid = Raster
+command-card-layout-row = Zeile
+resource-bar-position-top-left = Oben links
+resource-bar-position-top-center = Oben mittig
+resource-bar-position-top-right = Oben rechts
+tooltip-detail-basic = Einfach
+tooltip-detail-detailed = Ausführlich
+subtitle-size-small = Klein
+subtitle-size-medium = Mittel
+subtitle-size-large = Groß
+subtitle-size-extra-large = Sehr groß
+
+## Undo and redo
+
+history-undo = Rückgängig: { $change }
+history-redo = Wiederholen: { $change }
+history-edit = { $setting } { $from } → { $to }
+history-edits = { $setting } und { $more } weitere
+
+## Validation
+
+validation-not-a-number = Keine Zahl
+validation-below-minimum = { $value } liegt unter dem Minimum { $min }
+validation-above-maximum = { $value } liegt über dem Maximum { $max }
+validation-unsupported-resolution = { $width }x{ $height } wird nicht unterstützt
+validation-zoom-order = Muss mindestens dem minimalen Zoom { $min } entsprechen
+validation-dead-zone-order = Muss unter der äußeren Totzone { $outer } liegen
+
+## Screen readers
+
+control-decrease = { $setting } verringern
+control-increase = { $setting } erhöhen
+announce-change = { $setting } { $value }
+
+## Tutorials
+
+tutorial-dismiss = Verstanden
+tutorial-reset = Gesehene Hinweise zurücksetzen
+
+## HUD
+
+hud-heading = HUD-Anordnung
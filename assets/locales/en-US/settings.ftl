@@ -0,0 +1,247 @@
+# Settings menu strings, English
+#
+# Ids are documented in src/localization.rs. Every other language must define
+# every message in this file.
+
+## Menu
+
+menu-settings = Settings
+menu-title = Game Settings
+title-video = Video Settings
+title-audio = Audio Settings
+title-gameplay = Gameplay Settings
+title-controls = Controls Settings
+title-interface = Interface Settings
+button-test = Test
+
+tab-video = Video
+tab-audio = Audio
+tab-gameplay = Gameplay
+tab-controls = Controls
+tab-interface = Interface
+
+## Values
+
+value-on = On
+value-off = Off
+value-number = { $value }
+value-size = { $width }x{ $height }
+value-none = None
+
+## Video
+
+setting-video-display-mode = Display mode
+setting-video-resolution = Resolution
+setting-video-graphics-quality = Graphics quality
+setting-video-vsync = VSync
+setting-video-fps-limit = FPS limit
+setting-video-fps-limit-value = { $value } FPS
+setting-video-fps-limit-none = Unlimited
+setting-video-ui-scale = UI scale
+
+display-mode-windowed = Windowed
+display-mode-fullscreen = Fullscreen
+display-mode-borderless = Borderless
+graphics-quality-low = Low
+graphics-quality-medium = Medium
+graphics-quality-high = High
+graphics-quality-ultra = Ultra
+
+## Audio
+
+setting-audio-master-volume = Master volume
+setting-audio-music-volume = Music volume
+setting-audio-sfx-volume = Effects volume
+setting-audio-voice-volume = Voice volume
+setting-audio-ambient-volume = Ambient volume
+setting-audio-mute-when-inactive = Mute when inactive
+
+## Gameplay
+
+setting-gameplay-difficulty = Difficulty
+setting-gameplay-custom-difficulty-ai-resource-bonus = AI resource bonus
+setting-gameplay-custom-difficulty-ai-reaction-delay = AI reaction delay
+setting-gameplay-custom-difficulty-player-damage-multiplier = Player damage
+setting-gameplay-custom-difficulty-ai-damage-multiplier = AI damage
+setting-gameplay-custom-difficulty-ai-ignores-fog = AI sees through fog
+setting-gameplay-show-tutorials = Show tutorials
+setting-gameplay-subtitles = Subtitles
+setting-gameplay-closed-captions = Sound effect captions
+setting-gameplay-subtitle-style-size = Subtitle size
+setting-gameplay-subtitle-style-background-opacity = Subtitle background
+setting-gameplay-subtitle-style-speaker-names = Speaker names
+setting-gameplay-subtitle-style-speaker-colors = Speaker colours
+setting-gameplay-game-speed = Game speed
+setting-gameplay-game-speed-value = { $value }×
+setting-gameplay-autosave-interval-minutes = Autosave every
+setting-gameplay-autosave-interval-minutes-value = { $value } minutes
+setting-gameplay-autosave-interval-minutes-none = Never
+setting-gameplay-autosave-slots = Autosave slots
+setting-gameplay-alerts-under-attack = Under attack alerts
+setting-gameplay-alerts-idle-workers = Idle worker alerts
+setting-gameplay-alerts-research-complete = Research complete alerts
+setting-gameplay-health-bars = Health bars
+setting-gameplay-waypoints = Waypoints
+
+difficulty-easy = Easy
+difficulty-normal = Normal
+difficulty-hard = Hard
+difficulty-custom = Custom
+difficulty-custom-heading = Custom difficulty
+difficulty-locked = Difficulty can't be changed during a match
+
+health-bar-visibility-always = Always
+health-bar-visibility-selected = Selected units
+health-bar-visibility-damaged = Damaged units
+waypoint-display-selected = Selected units
+waypoint-display-queuing = While queuing
+waypoint-display-never = Never
+
+## Controls
+
+setting-controls-mouse-sensitivity = Mouse sensitivity
+setting-controls-invert-y = Invert Y
+setting-controls-mouse-acceleration = Mouse acceleration
+setting-controls-mouse-dead-zone = Mouse dead zone
+setting-controls-camera-edge-scroll = Edge scrolling
+setting-controls-camera-edge-scroll-margin = Edge scroll margin
+setting-controls-camera-edge-scroll-margin-value =
+    { $value ->
+        [one] { $value } pixel
+       *[other] { $value } pixels
+    }
+setting-controls-camera-edge-scroll-speed = Edge scroll speed
+setting-controls-camera-keyboard-pan-speed = Keyboard pan speed
+setting-controls-camera-zoom-speed = Zoom speed
+setting-controls-camera-min-zoom = Minimum zoom
+setting-controls-camera-max-zoom = Maximum zoom
+setting-controls-camera-middle-mouse-pan = Middle mouse drag pan
+setting-controls-camera-lock-cursor = Lock cursor to window
+setting-controls-controller-left-stick-inner-dead-zone = Left stick inner dead zone
+setting-controls-controller-left-stick-outer-dead-zone = Left stick outer dead zone
+setting-controls-controller-left-stick-response-curve = Left stick response curve
+setting-controls-controller-left-stick-custom-exponent = Left stick custom curve exponent
+setting-controls-controller-right-stick-inner-dead-zone = Right stick inner dead zone
+setting-controls-controller-right-stick-outer-dead-zone = Right stick outer dead zone
+setting-controls-controller-right-stick-response-curve = Right stick response curve
+setting-controls-controller-right-stick-custom-exponent = Right stick custom curve exponent
+setting-controls-controller-left-trigger-threshold = Left trigger threshold
+setting-controls-controller-right-trigger-threshold = Right trigger threshold
+setting-controls-controller-cursor-speed = Gamepad cursor speed
+setting-controls-controller-rumble-strength = Rumble strength
+setting-controls-keybinds-camera-pan-up = Pan camera up
+setting-controls-keybinds-camera-pan-down = Pan camera down
+setting-controls-keybinds-camera-pan-left = Pan camera left
+setting-controls-keybinds-camera-pan-right = Pan camera right
+
+acceleration-curve-off = Off
+acceleration-curve-linear = Linear
+acceleration-curve-quadratic = Quadratic
+response-curve-linear = Linear
+response-curve-quadratic = Quadratic
+response-curve-custom = Custom
+
+keymap-preset = Keymap preset
+keymap-preset-classic = Classic
+keymap-preset-grid = Grid
+keymap-preset-left-handed = Left-handed
+keymap-import = Import
+keymap-export = Export
+keymap-applied = Applied { $preset } preset
+keymap-preset-failed = Could not load { $preset } preset
+keymap-imported =
+    { $ignored ->
+        [0] Loaded keymap "{ $name }"
+        [one] Loaded keymap "{ $name }", ignored { $ignored } unknown entry
+       *[other] Loaded keymap "{ $name }", ignored { $ignored } unknown entries
+    }
+keymap-import-failed = Import failed: { $error }
+keymap-exported = Exported keymap to { $path }
+keymap-export-failed = Export failed: { $error }
+
+## Interface
+
+setting-interface-language = Language
+setting-interface-ui-scale = UI scale
+setting-interface-colorblind-mode = Colour-blind mode
+setting-interface-colorblind-strength = Colour-blind strength
+setting-interface-text-scale = Text size
+setting-interface-high-contrast = High contrast
+setting-interface-reduce-motion = Reduce motion
+setting-interface-dyslexia-font = Dyslexia-friendly font
+setting-interface-cursor-size = Cursor size
+setting-interface-hud-minimap-position = Minimap position
+setting-interface-hud-minimap-size = Minimap size
+setting-interface-hud-minimap-rotation = Minimap rotation
+setting-interface-hud-command-card-layout = Command card layout
+setting-interface-hud-resource-bar-position = Resource bar position
+setting-interface-hud-chat-opacity = Chat opacity
+setting-interface-hud-chat-fade-time = Chat fade time
+setting-interface-hud-chat-fade-time-value = { $value } s
+setting-interface-hud-tooltip-delay = Tooltip delay
+setting-interface-hud-tooltip-delay-value = { $value } s
+setting-interface-hud-tooltip-detail = Tooltip detail
+setting-interface-hud-performance-overlay = FPS and ping
+
+# Each language is named in its own language
+language-english = English
+language-german = Deutsch
+language-french = Français
+language-spanish = Español
+colorblind-mode-off = Off
+colorblind-mode-protanopia = Protanopia
+colorblind-mode-deuteranopia = Deuteranopia
+colorblind-mode-tritanopia = Tritanopia
+colorblind-mode-achromatopsia = Achromatopsia
+cursor-size-normal = Normal
+cursor-size-large = Large
+cursor-size-extra-large = Extra large
+screen-corner-bottom-left = Bottom left
+screen-corner-bottom-right = Bottom right
+screen-corner-top-left = Top left
+screen-corner-top-right = Top right
+minimap-rotation-north-up = North up
+minimap-rotation-isometric = Isometric
+minimap-rotation-follow-camera = Follow camera
+command-card-layout-grid = Grid
+command-card-layout-row = Row
+resource-bar-position-top-left = Top left
+resource-bar-position-top-center = Top centre
+resource-bar-position-top-right = Top right
+tooltip-detail-basic = Basic
+tooltip-detail-detailed = Detailed
+subtitle-size-small = Small
+subtitle-size-medium = Medium
+subtitle-size-large = Large
+subtitle-size-extra-large = Extra large
+
+## Undo and redo
+
+history-undo = Undo: { $change }
+history-redo = Redo: { $change }
+history-edit = { $setting } { $from } → { $to }
+history-edits = { $setting } and { $more } more
+
+## Validation
+
+validation-not-a-number = Not a number
+validation-below-minimum = { $value } is below the minimum { $min }
+validation-above-maximum = { $value } is above the maximum { $max }
+validation-unsupported-resolution = { $width }x{ $height } is not a supported resolution
+validation-zoom-order = Must be at least the minimum zoom { $min }
+validation-dead-zone-order = Must be below the outer dead zone { $outer }
+
+## Screen readers
+
+control-decrease = Decrease { $setting }
+control-increase = Increase { $setting }
+announce-change = { $setting } { $value }
+
+## Tutorials
+
+tutorial-dismiss = Got it
+tutorial-reset = Reset seen hints
+
+## HUD
+
+hud-heading = HUD layout
@@ -0,0 +1,243 @@
+# Settings menu strings, Spanish
+
+## Menu
+
+menu-settings = Opciones
+menu-title = Opciones del juego
+title-video = Opciones de vídeo
+title-audio = Opciones de audio
+title-gameplay = Opciones de juego
+title-controls = Opciones de controles
+title-interface = Opciones de interfaz
+button-test = Probar
+
+tab-video = Vídeo
+tab-audio = Audio
+tab-gameplay = Juego
+tab-controls = Controles
+tab-interface = Interfaz
+
+## Values
+
+value-on = Sí
+value-off = No
+value-number = { $value }
+value-size = { $width }x{ $height }
+value-none = Ninguno
+
+## Video
+
+setting-video-display-mode = Modo de pantalla
+setting-video-resolution = Resolución
+setting-video-graphics-quality = Calidad gráfica
+setting-video-vsync = Sincronización vertical
+setting-video-fps-limit = Límite de FPS
+setting-video-fps-limit-value = { $value } FPS
+setting-video-fps-limit-none = Sin límite
+setting-video-ui-scale = Escala de la interfaz
+
+display-mode-windowed = Ventana
+display-mode-fullscreen = Pantalla completa
+display-mode-borderless = Sin bordes
+graphics-quality-low = Baja
+graphics-quality-medium = Media
+graphics-quality-high = Alta
+graphics-quality-ultra = Ultra
+
+## Audio
+
+setting-audio-master-volume = Volumen general
+setting-audio-music-volume = Volumen de la música
+setting-audio-sfx-volume = Volumen de los efectos
+setting-audio-voice-volume = Volumen de las voces
+setting-audio-ambient-volume = Volumen ambiental
+setting-audio-mute-when-inactive = Silenciar en segundo plano
+
+## Gameplay
+
+setting-gameplay-difficulty = Dificultad
+setting-gameplay-custom-difficulty-ai-resource-bonus = Bonificación de recursos de la IA
+setting-gameplay-custom-difficulty-ai-reaction-delay = Retardo de reacción de la IA
+setting-gameplay-custom-difficulty-player-damage-multiplier = Daño del jugador
+setting-gameplay-custom-difficulty-ai-damage-multiplier = Daño de la IA
+setting-gameplay-custom-difficulty-ai-ignores-fog = La IA ve a través de la niebla
+setting-gameplay-show-tutorials = Mostrar tutoriales
+setting-gameplay-subtitles = Subtítulos
+setting-gameplay-closed-captions = Subtítulos de efectos de sonido
+setting-gameplay-subtitle-style-size = Tamaño de los subtítulos
+setting-gameplay-subtitle-style-background-opacity = Fondo de los subtítulos
+setting-gameplay-subtitle-style-speaker-names = Nombres de los hablantes
+setting-gameplay-subtitle-style-speaker-colors = Colores de los hablantes
+setting-gameplay-game-speed = Velocidad de juego
+setting-gameplay-game-speed-value = { $value }×
+setting-gameplay-autosave-interval-minutes = Autoguardado cada
+setting-gameplay-autosave-interval-minutes-value = { $value } minutos
+setting-gameplay-autosave-interval-minutes-none = Nunca
+setting-gameplay-autosave-slots = Ranuras de autoguardado
+setting-gameplay-alerts-under-attack = Alertas de ataque
+setting-gameplay-alerts-idle-workers = Alertas de trabajadores inactivos
+setting-gameplay-alerts-research-complete = Alertas de investigación completada
+setting-gameplay-health-bars = Barras de salud
+setting-gameplay-waypoints = Puntos de ruta
+
+difficulty-easy = Fácil
+difficulty-normal = Normal
+difficulty-hard = Difícil
+difficulty-custom = Personalizada
+difficulty-custom-heading = Dificultad personalizada
+difficulty-locked = La dificultad no se puede cambiar durante una partida
+
+health-bar-visibility-always = Siempre
+health-bar-visibility-selected = Unidades seleccionadas
+health-bar-visibility-damaged = Unidades dañadas
+waypoint-display-selected = Unidades seleccionadas
+waypoint-display-queuing = Al encolar órdenes
+waypoint-display-never = Nunca
+
+## Controls
+
+setting-controls-mouse-sensitivity = Sensibilidad del ratón
+setting-controls-invert-y = Invertir eje Y
+setting-controls-mouse-acceleration = Aceleración del ratón
+setting-controls-mouse-dead-zone = Zona muerta del ratón
+setting-controls-camera-edge-scroll = Desplazamiento por los bordes
+setting-controls-camera-edge-scroll-margin = Margen de desplazamiento
+setting-controls-camera-edge-scroll-margin-value =
+    { $value ->
+        [one] { $value } píxel
+       *[other] { $value } píxeles
+    }
+setting-controls-camera-edge-scroll-speed = Velocidad de desplazamiento por los bordes
+setting-controls-camera-keyboard-pan-speed = Velocidad de desplazamiento con teclado
+setting-controls-camera-zoom-speed = Velocidad del zoom
+setting-controls-camera-min-zoom = Zoom mínimo
+setting-controls-camera-max-zoom = Zoom máximo
+setting-controls-camera-middle-mouse-pan = Desplazar con el botón central
+setting-controls-camera-lock-cursor = Mantener el cursor en la ventana
+setting-controls-controller-left-stick-inner-dead-zone = Stick izquierdo, zona muerta interior
+setting-controls-controller-left-stick-outer-dead-zone = Stick izquierdo, zona muerta exterior
+setting-controls-controller-left-stick-response-curve = Stick izquierdo, curva de respuesta
+setting-controls-controller-left-stick-custom-exponent = Stick izquierdo, exponente de la curva
+setting-controls-controller-right-stick-inner-dead-zone = Stick derecho, zona muerta interior
+setting-controls-controller-right-stick-outer-dead-zone = Stick derecho, zona muerta exterior
+setting-controls-controller-right-stick-response-curve = Stick derecho, curva de respuesta
+setting-controls-controller-right-stick-custom-exponent = Stick derecho, exponente de la curva
+setting-controls-controller-left-trigger-threshold = Umbral del gatillo izquierdo
+setting-controls-controller-right-trigger-threshold = Umbral del gatillo derecho
+setting-controls-controller-cursor-speed = Velocidad del cursor con mando
+setting-controls-controller-rumble-strength = Intensidad de la vibración
+setting-controls-keybinds-camera-pan-up = Cámara hacia arriba
+setting-controls-keybinds-camera-pan-down = Cámara hacia abajo
+setting-controls-keybinds-camera-pan-left = Cámara a la izquierda
+setting-controls-keybinds-camera-pan-right = Cámara a la derecha
+
+acceleration-curve-off = Desactivada
+acceleration-curve-linear = Lineal
+acceleration-curve-quadratic = Cuadrática
+response-curve-linear = Lineal
+response-curve-quadratic = Cuadrática
+response-curve-custom = Personalizada
+
+keymap-preset = Distribución de teclas
+keymap-preset-classic = Clásica
+keymap-preset-grid = Cuadrícula
+keymap-preset-left-handed = Zurdos
+keymap-import = Importar
+keymap-export = Exportar
+keymap-applied = Distribución «{ $preset }» aplicada
+keymap-preset-failed = No se pudo cargar la distribución «{ $preset }»
+keymap-imported =
+    { $ignored ->
+        [0] Distribución «{ $name }» cargada
+        [one] Distribución «{ $name }» cargada, se ignoró { $ignored } entrada desconocida
+       *[other] Distribución «{ $name }» cargada, se ignoraron { $ignored } entradas desconocidas
+    }
+keymap-import-failed = Error al importar: { $error }
+keymap-exported = Distribución exportada a { $path }
+keymap-export-failed = Error al exportar: { $error }
+
+## Interface
+
+setting-interface-language = Idioma
+setting-interface-ui-scale = Escala de la interfaz
+setting-interface-colorblind-mode = Modo para daltónicos
+setting-interface-colorblind-strength = Intensidad de la corrección
+setting-interface-text-scale = Tamaño del texto
+setting-interface-high-contrast = Alto contraste
+setting-interface-reduce-motion = Reducir movimiento
+setting-interface-dyslexia-font = Fuente para dislexia
+setting-interface-cursor-size = Tamaño del cursor
+setting-interface-hud-minimap-position = Posición del minimapa
+setting-interface-hud-minimap-size = Tamaño del minimapa
+setting-interface-hud-minimap-rotation = Rotación del minimapa
+setting-interface-hud-command-card-layout = Disposición de los comandos
+setting-interface-hud-resource-bar-position = Posición de la barra de recursos
+setting-interface-hud-chat-opacity = Opacidad del chat
+setting-interface-hud-chat-fade-time = Tiempo de desvanecimiento del chat
+setting-interface-hud-chat-fade-time-value = { $value } s
+setting-interface-hud-tooltip-delay = Retardo de la información emergente
+setting-interface-hud-tooltip-delay-value = { $value } s
+setting-interface-hud-tooltip-detail = Detalle de la información emergente
+setting-interface-hud-performance-overlay = FPS y ping
+
+language-english = English
+language-german = Deutsch
+language-french = Français
+language-spanish = Español
+colorblind-mode-off = Desactivado
+colorblind-mode-protanopia = Protanopía
+colorblind-mode-deuteranopia = Deuteranopía
+colorblind-mode-tritanopia = Tritanopía
+colorblind-mode-achromatopsia = Acromatopsia
+cursor-size-normal = Normal
+cursor-size-large = Grande
+cursor-size-extra-large = Muy grande
+screen-corner-bottom-left = Abajo a la izquierda
+screen-corner-bottom-right = Abajo a la derecha
+screen-corner-top-left = Arriba a la izquierda
+screen-corner-top-right = Arriba a la derecha
+minimap-rotation-north-up = Norte arriba
+minimap-rotation-isometric = Isométrica
+minimap-rotation-follow-camera = Seguir la cámara
+command-card-layout-grid = Cuadrícula
+command-card-layout-row = Fila
+resource-bar-position-top-left = Arriba a la izquierda
+resource-bar-position-top-center = Arriba al centro
+resource-bar-position-top-right = Arriba a la derecha
+tooltip-detail-basic = Básico
+tooltip-detail-detailed = Detallado
+subtitle-size-small = Pequeño
+subtitle-size-medium = Mediano
+subtitle-size-large = Grande
+subtitle-size-extra-large = Muy grande
+
+## Undo and redo
+
+history-undo = Deshacer: { $change }
+history-redo = Rehacer: { $change }
+history-edit = { $setting } { $from } → { $to }
+history-edits = { $setting } y { $more } más
+
+## Validation
+
+validation-not-a-number = No es un número
+validation-below-minimum = { $value } es menor que el mínimo { $min }
+validation-above-maximum = { $value } es mayor que el máximo { $max }
+validation-unsupported-resolution = La resolución { $width }x{ $height } no es compatible
+validation-zoom-order = Debe ser al menos el zoom mínimo { $min }
+validation-dead-zone-order = Debe ser menor que la zona muerta exterior { $outer }
+
+## Screen readers
+
+control-decrease = Reducir { $setting }
+control-increase = Aumentar { $setting }
+announce-change = { $setting } { $value }
+
+## Tutorials
+
+tutorial-dismiss = Entendido
+tutorial-reset = Restablecer consejos vistos
+
+## HUD
+
+hud-heading = Disposición de la interfaz
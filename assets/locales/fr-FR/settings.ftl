@@ -0,0 +1,243 @@
+# Settings menu strings, French
+
+## Menu
+
+menu-settings = Paramètres
+menu-title = Paramètres du jeu
+title-video = Paramètres vidéo
+title-audio = Paramètres audio
+title-gameplay = Paramètres de jeu
+title-controls = Paramètres des commandes
+title-interface = Paramètres de l'interface
+button-test = Tester
+
+tab-video = Vidéo
+tab-audio = Audio
+tab-gameplay = Jeu
+tab-controls = Commandes
+tab-interface = Interface
+
+## Values
+
+value-on = Activé
+value-off = Désactivé
+value-number = { $value }
+value-size = { $width }x{ $height }
+value-none = Aucun
+
+## Video
+
+setting-video-display-mode = Mode d'affichage
+setting-video-resolution = Résolution
+setting-video-graphics-quality = Qualité graphique
+setting-video-vsync = Synchronisation verticale
+setting-video-fps-limit = Limite d'IPS
+setting-video-fps-limit-value = { $value } IPS
+setting-video-fps-limit-none = Illimitée
+setting-video-ui-scale = Échelle de l'interface
+
+display-mode-windowed = Fenêtré
+display-mode-fullscreen = Plein écran
+display-mode-borderless = Sans bordure
+graphics-quality-low = Basse
+graphics-quality-medium = Moyenne
+graphics-quality-high = Haute
+graphics-quality-ultra = Ultra
+
+## Audio
+
+setting-audio-master-volume = Volume général
+setting-audio-music-volume = Volume de la musique
+setting-audio-sfx-volume = Volume des effets
+setting-audio-voice-volume = Volume des voix
+setting-audio-ambient-volume = Volume d'ambiance
+setting-audio-mute-when-inactive = Couper le son en arrière-plan
+
+## Gameplay
+
+setting-gameplay-difficulty = Difficulté
+setting-gameplay-custom-difficulty-ai-resource-bonus = Bonus de ressources de l'IA
+setting-gameplay-custom-difficulty-ai-reaction-delay = Délai de réaction de l'IA
+setting-gameplay-custom-difficulty-player-damage-multiplier = Dégâts du joueur
+setting-gameplay-custom-difficulty-ai-damage-multiplier = Dégâts de l'IA
+setting-gameplay-custom-difficulty-ai-ignores-fog = L'IA voit à travers le brouillard
+setting-gameplay-show-tutorials = Afficher les tutoriels
+setting-gameplay-subtitles = Sous-titres
+setting-gameplay-closed-captions = Sous-titres des effets sonores
+setting-gameplay-subtitle-style-size = Taille des sous-titres
+setting-gameplay-subtitle-style-background-opacity = Fond des sous-titres
+setting-gameplay-subtitle-style-speaker-names = Noms des interlocuteurs
+setting-gameplay-subtitle-style-speaker-colors = Couleurs des interlocuteurs
+setting-gameplay-game-speed = Vitesse de jeu
+setting-gameplay-game-speed-value = { $value }×
+setting-gameplay-autosave-interval-minutes = Sauvegarde automatique toutes les
+setting-gameplay-autosave-interval-minutes-value = { $value } minutes
+setting-gameplay-autosave-interval-minutes-none = Jamais
+setting-gameplay-autosave-slots = Emplacements de sauvegarde automatique
+setting-gameplay-alerts-under-attack = Alertes d'attaque
+setting-gameplay-alerts-idle-workers = Alertes d'ouvriers inactifs
+setting-gameplay-alerts-research-complete = Alertes de recherche terminée
+setting-gameplay-health-bars = Barres de vie
+setting-gameplay-waypoints = Points de passage
+
+difficulty-easy = Facile
+difficulty-normal = Normale
+difficulty-hard = Difficile
+difficulty-custom = Personnalisée
+difficulty-custom-heading = Difficulté personnalisée
+difficulty-locked = La difficulté ne peut pas être modifiée pendant une partie
+
+health-bar-visibility-always = Toujours
+health-bar-visibility-selected = Unités sélectionnées
+health-bar-visibility-damaged = Unités endommagées
+waypoint-display-selected = Unités sélectionnées
+waypoint-display-queuing = Pendant la mise en file
+waypoint-display-never = Jamais
+
+## Controls
+
+setting-controls-mouse-sensitivity = Sensibilité de la souris
+setting-controls-invert-y = Inverser l'axe Y
+setting-controls-mouse-acceleration = Accélération de la souris
+setting-controls-mouse-dead-zone = Zone morte de la souris
+setting-controls-camera-edge-scroll = Défilement par les bords
+setting-controls-camera-edge-scroll-margin = Marge de défilement
+setting-controls-camera-edge-scroll-margin-value =
+    { $value ->
+        [one] { $value } pixel
+       *[other] { $value } pixels
+    }
+setting-controls-camera-edge-scroll-speed = Vitesse de défilement par les bords
+setting-controls-camera-keyboard-pan-speed = Vitesse de défilement au clavier
+setting-controls-camera-zoom-speed = Vitesse du zoom
+setting-controls-camera-min-zoom = Zoom minimal
+setting-controls-camera-max-zoom = Zoom maximal
+setting-controls-camera-middle-mouse-pan = Déplacer avec le bouton du milieu
+setting-controls-camera-lock-cursor = Garder le curseur dans la fenêtre
+setting-controls-controller-left-stick-inner-dead-zone = Stick gauche, zone morte intérieure
+setting-controls-controller-left-stick-outer-dead-zone = Stick gauche, zone morte extérieure
+setting-controls-controller-left-stick-response-curve = Stick gauche, courbe de réponse
+setting-controls-controller-left-stick-custom-exponent = Stick gauche, exposant de la courbe
+setting-controls-controller-right-stick-inner-dead-zone = Stick droit, zone morte intérieure
+setting-controls-controller-right-stick-outer-dead-zone = Stick droit, zone morte extérieure
+setting-controls-controller-right-stick-response-curve = Stick droit, courbe de réponse
+setting-controls-controller-right-stick-custom-exponent = Stick droit, exposant de la courbe
+setting-controls-controller-left-trigger-threshold = Seuil de la gâchette gauche
+setting-controls-controller-right-trigger-threshold = Seuil de la gâchette droite
+setting-controls-controller-cursor-speed = Vitesse du curseur à la manette
+setting-controls-controller-rumble-strength = Intensité des vibrations
+setting-controls-keybinds-camera-pan-up = Caméra vers le haut
+setting-controls-keybinds-camera-pan-down = Caméra vers le bas
+setting-controls-keybinds-camera-pan-left = Caméra vers la gauche
+setting-controls-keybinds-camera-pan-right = Caméra vers la droite
+
+acceleration-curve-off = Désactivée
+acceleration-curve-linear = Linéaire
+acceleration-curve-quadratic = Quadratique
+response-curve-linear = Linéaire
+response-curve-quadratic = Quadratique
+response-curve-custom = Personnalisée
+
+keymap-preset = Disposition des touches
+keymap-preset-classic = Classique
+keymap-preset-grid = Grille
+keymap-preset-left-handed = Gaucher
+keymap-import = Importer
+keymap-export = Exporter
+keymap-applied = Disposition « { $preset } » appliquée
+keymap-preset-failed = Impossible de charger la disposition « { $preset } »
+keymap-imported =
+    { $ignored ->
+        [0] Disposition « { $name } » chargée
+        [one] Disposition « { $name } » chargée, { $ignored } entrée inconnue ignorée
+       *[other] Disposition « { $name } » chargée, { $ignored } entrées inconnues ignorées
+    }
+keymap-import-failed = Échec de l'import : { $error }
+keymap-exported = Disposition exportée vers { $path }
+keymap-export-failed = Échec de l'export : { $error }
+
+## Interface
+
+setting-interface-language = Langue
+setting-interface-ui-scale = Échelle de l'interface
+setting-interface-colorblind-mode = Mode daltonien
+setting-interface-colorblind-strength = Intensité de la correction
+setting-interface-text-scale = Taille du texte
+setting-interface-high-contrast = Contraste élevé
+setting-interface-reduce-motion = Réduire les animations
+setting-interface-dyslexia-font = Police adaptée à la dyslexie
+setting-interface-cursor-size = Taille du curseur
+setting-interface-hud-minimap-position = Position de la mini-carte
+setting-interface-hud-minimap-size = Taille de la mini-carte
+setting-interface-hud-minimap-rotation = Rotation de la mini-carte
+setting-interface-hud-command-card-layout = Disposition des commandes
+setting-interface-hud-resource-bar-position = Position de la barre de ressources
+setting-interface-hud-chat-opacity = Opacité du chat
+setting-interface-hud-chat-fade-time = Durée d'affichage du chat
+setting-interface-hud-chat-fade-time-value = { $value } s
+setting-interface-hud-tooltip-delay = Délai des info-bulles
+setting-interface-hud-tooltip-delay-value = { $value } s
+setting-interface-hud-tooltip-detail = Détail des info-bulles
+setting-interface-hud-performance-overlay = IPS et ping
+
+language-english = English
+language-german = Deutsch
+language-french = Français
+language-spanish = Español
+colorblind-mode-off = Désactivé
+colorblind-mode-protanopia = Protanopie
+colorblind-mode-deuteranopia = Deutéranopie
+colorblind-mode-tritanopia = Tritanopie
+colorblind-mode-achromatopsia = Achromatopsie
+cursor-size-normal = Normale
+cursor-size-large = Grande
+cursor-size-extra-large = Très grande
+screen-corner-bottom-left = En bas à gauche
+screen-corner-bottom-right = En bas à droite
+screen-corner-top-left = En haut à gauche
+screen-corner-top-right = En haut à droite
+minimap-rotation-north-up = Nord en haut
+minimap-rotation-isometric = Isométrique
+minimap-rotation-follow-camera = Suivre la caméra
+command-card-layout-grid = Grille
+command-card-layout-row = Ligne
+resource-bar-position-top-left = En haut à gauche
+resource-bar-position-top-center = En haut au centre
+resource-bar-position-top-right = En haut à droite
+tooltip-detail-basic = Simple
+tooltip-detail-detailed = Détaillé
+subtitle-size-small = Petite
+subtitle-size-medium = Moyenne
+subtitle-size-large = Grande
+subtitle-size-extra-large = Très grande
+
+## Undo and redo
+
+history-undo = Annuler : { $change }
+history-redo = Rétablir : { $change }
+history-edit = { $setting } { $from } → { $to }
+history-edits = { $setting } et { $more } autres
+
+## Validation
+
+validation-not-a-number = Ce n'est pas un nombre
+validation-below-minimum = { $value } est inférieur au minimum { $min }
+validation-above-maximum = { $value } est supérieur au maximum { $max }
+validation-unsupported-resolution = La résolution { $width }x{ $height } n'est pas prise en charge
+validation-zoom-order = Doit être au moins égal au zoom minimal { $min }
+validation-dead-zone-order = Doit être inférieure à la zone morte extérieure { $outer }
+
+## Screen readers
+
+control-decrease = Diminuer { $setting }
+control-increase = Augmenter { $setting }
+announce-change = { $setting } { $value }
+
+## Tutorials
+
+tutorial-dismiss = Compris
+tutorial-reset = Réinitialiser les astuces vues
+
+## HUD
+
+hud-heading = Disposition de l'interface
@@ -1,102 +1,249 @@
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
-fn main() {
-    println!("cargo:rerun-if-changed=src/lib.rs");
-    // Add other relevant rerun-if-changed lines if specific files impact the build output.
+/// Symbols the dynamic loader looks up in the plugin library
+const EXPORTED_SYMBOLS: &[&str] = &[
+    "create_settings_plugin",
+    "destroy_settings_plugin",
+    "settings_plugin_abi_version",
+    "settings_plugin_bevy_version",
+    "settings_plugin_template_version",
+    "settings_plugin_rustc_version",
+    "settings_plugin_name",
+    "settings_plugin_version",
+];
 
-    // Get the output directory from Cargo (where build script outputs go)
-    let out_dir = env::var("OUT_DIR").unwrap();
+/// Extra symbols exported by development builds with the `hot-reload` feature
+const HOT_RELOAD_SYMBOLS: &[&str] = &[
+    "settings_plugin_build",
+    "settings_plugin_unload",
+    "settings_plugin_restore",
+    "settings_plugin_free_snapshot",
+];
 
-    // Determine the profile (debug or release)
-    let profile = env::var("PROFILE").unwrap(); // "debug" or "release"
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SF_PLUGINS_DIR");
 
-    // Construct the name of the DLL
     let crate_name = env::var("CARGO_PKG_NAME").unwrap(); // sf-settings-menu
-    // Convert crate name to library file name convention (e.g., sf_settings_menu.dll)
-    let dll_name = format!("{}.dll", crate_name.replace("-", "_"));
-
-    // Define the destination directory for plugins within the workspace target directory
-    // e.g., StrategyForge-Workspace/target/<profile>/plugins/
-    let workspace_root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-        .parent() // Up to StrategyForge-Workspace
-        .unwrap()
+    let lib_name = crate_name.replace('-', "_");
+
+    // Use the *target* platform, not the host, so cross builds get the right names
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let artifact_name = artifact_file_name(&lib_name, &target_os);
+
+    // OUT_DIR is target/[<triple>/]<profile>/build/<pkg>-<hash>/out, so the
+    // artifact directory is three levels up. This also follows CARGO_TARGET_DIR.
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let artifact_dir = out_dir
+        .ancestors()
+        .nth(3)
+        .expect("OUT_DIR is not inside a cargo target directory")
         .to_path_buf();
+    // The game loads plugins from target/<profile>/plugins; SF_PLUGINS_DIR
+    // overrides this when the crate is built outside the game's workspace
+    let plugins_dir = env::var_os("SF_PLUGINS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| artifact_dir.join("plugins"));
+
+    fs::create_dir_all(&plugins_dir).unwrap_or_else(|err| {
+        panic!("Failed to create plugins directory {:?}: {}", plugins_dir, err)
+    });
+
+    // The library doesn't exist yet while the build script runs, so the
+    // plugins directory gets a link to where it will be. deps/ is written on
+    // every build of the library, while cargo only copies it up into the
+    // profile directory when the library itself is what's being built.
+    let library = artifact_dir.join("deps").join(&artifact_name);
+    link_library(&library, &plugins_dir.join(&artifact_name));
+
+    println!("cargo:rustc-env=SF_PLUGIN_ARTIFACT={}", artifact_name);
+    // The library as last built, for the hot reload host and the tests
+    println!("cargo:rustc-env=SF_PLUGIN_LIBRARY={}", display(&library));
+
+    emit_export_args(&target_env);
+    emit_version_info();
+}
 
-    let target_plugins_dir = workspace_root
-        .join("target")
-        .join(&profile) // Append profile (debug/release)
-        .join("plugins");
+/// Point `link` at `library`, replacing whatever is there, e.g. a copy staged
+/// by hand
+fn link_library(library: &Path, link: &Path) {
+    if fs::read_link(link).is_ok_and(|target| target == library) {
+        return;
+    }
+    match fs::remove_file(link) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => panic!("Failed to replace {:?}: {}", link, err),
+    }
+
+    #[cfg(unix)]
+    let linked = std::os::unix::fs::symlink(library, link);
+    #[cfg(windows)]
+    let linked = std::os::windows::fs::symlink_file(library, link);
+    linked.unwrap_or_else(|err| {
+        let hint = if cfg!(windows) { " (creating symbolic links needs Developer Mode)" } else { "" };
+        panic!("Failed to link {:?} to {:?}: {}{}", link, library, err, hint)
+    });
+}
 
-    // Create the target plugins directory if it doesn't exist
-    if !target_plugins_dir.exists() {
-        fs::create_dir_all(&target_plugins_dir).expect("Failed to create plugins directory");
+/// Version baked in when one can't be determined; no host accepts it, so the
+/// build still succeeds but the library is refused instead of loaded blindly
+const UNKNOWN_VERSION: &str = "unknown";
+
+/// Bake the versions the host checks before calling into the plugin
+fn emit_version_info() {
+    let package = env::var("CARGO_PKG_NAME").unwrap();
+    let package_version = env::var("CARGO_PKG_VERSION").unwrap();
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let packages = find_lock_file(&manifest_dir, &package, &package_version);
+
+    for (dependency, variable) in [("bevy", "SF_BEVY_VERSION"), ("sf-plugin-template", "SF_PLUGIN_TEMPLATE_VERSION")] {
+        let version = packages
+            .as_deref()
+            .and_then(|packages| dependency_version(packages, &package, &package_version, dependency))
+            .unwrap_or_else(|| {
+                println!(
+                    "cargo:warning=Couldn't find the {} version {} {} was locked with, hosts will refuse this build",
+                    dependency, package, package_version,
+                );
+                UNKNOWN_VERSION.to_string()
+            });
+        println!("cargo:rustc-env={}={}", variable, version);
     }
 
-    // Calculate the paths where the DLL might be located
-    // We'll check multiple potential locations to ensure we find it
-    let out_dir_path = Path::new(&out_dir);
-    
-    // Option 1: Direct in target/<profile>
-    let path1 = out_dir_path
-        .ancestors()
-        .nth(3) // Up to target/<profile>
-        .unwrap()
-        .join(&dll_name);
-    
-    // Option 2: In target/<profile>/deps/
-    let path2 = out_dir_path
-        .ancestors()
-        .nth(3) // Up to target/<profile>
-        .unwrap()
-        .join("deps") // target/<profile>/deps/
-        .join(&dll_name);
-        
-    // Option 3: In the current crate's target/<profile>
-    let path3 = workspace_root
-        .join(&crate_name)
-        .join("target")
-        .join(&profile)
-        .join(&dll_name);
-    
-    // Use the first path that exists
-    let source_dll_path = if path1.exists() {
-        path1
-    } else if path2.exists() {
-        path2
-    } else if path3.exists() {
-        path3
-    } else {
-        // Try one last path - sometimes it might be directly in the deps directory
-        out_dir_path
-            .ancestors()
-            .nth(2) // Up to target/<profile>/build
-            .unwrap()
-            .parent() // Up to target/<profile>
-            .unwrap()
-            .join(&dll_name)
+    // Full `rustc -V` output, including the commit hash, since Rust has no stable ABI
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(&rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(|| {
+            println!("cargo:warning=Couldn't run {} -V, hosts will refuse this build", rustc);
+            UNKNOWN_VERSION.to_string()
+        });
+    println!("cargo:rustc-env=SF_RUSTC_VERSION={}", rustc_version);
+}
+
+/// A `[[package]]` entry of a Cargo.lock
+struct LockedPackage {
+    name: String,
+    version: String,
+    /// `"name"`, or `"name version"` when several versions are locked
+    dependencies: Vec<String>,
+}
+
+/// Packages of the closest Cargo.lock above the manifest that locks this
+/// package, i.e. the one of the workspace being built
+///
+/// Crates built as a git or vendored dependency of another workspace have none.
+fn find_lock_file(manifest_dir: &Path, package: &str, version: &str) -> Option<Vec<LockedPackage>> {
+    manifest_dir.ancestors().find_map(|dir| {
+        let path = dir.join("Cargo.lock");
+        let source = fs::read_to_string(&path).ok()?;
+        let packages = parse_lock_file(&source);
+        let locks_package = packages.iter().any(|locked| locked.name == package && locked.version == version);
+        if !locks_package {
+            return None;
+        }
+        println!("cargo:rerun-if-changed={}", display(&path));
+        Some(packages)
+    })
+}
+
+fn parse_lock_file(source: &str) -> Vec<LockedPackage> {
+    let quoted = |line: &str, key: &str| {
+        line.strip_prefix(key)?
+            .trim_start()
+            .strip_prefix("= \"")?
+            .strip_suffix('"')
+            .map(str::to_string)
     };
-    
-    println!("cargo:warning=Looking for DLL in: {:?}", source_dll_path);
-
-    let destination_path = target_plugins_dir.join(&dll_name);
-
-    // Copy the DLL to the target plugins directory
-    if source_dll_path.exists() {
-        fs::copy(&source_dll_path, &destination_path)
-            .expect(&format!("Failed to copy DLL from {:?} to {:?}", source_dll_path, destination_path));
-        println!("cargo:warning=Copied {} to {:?}", dll_name, target_plugins_dir);
-    } else {
-        println!("cargo:warning=DLL not found at {:?}. Plugin will not be copied.", source_dll_path);
-        println!("cargo:warning=Ensure that crate-type in Cargo.toml is [\"cdylib\"].");
-        println!("cargo:warning=Crate name: {}, Expected DLL name: {}", crate_name, dll_name);
-        println!("cargo:warning=Profile: {}, OUT_DIR: {}", profile, out_dir);
+
+    source
+        .split("[[package]]")
+        .skip(1)
+        .map(|entry| {
+            let mut package = LockedPackage {
+                name: String::new(),
+                version: String::new(),
+                dependencies: Vec::new(),
+            };
+            let mut in_dependencies = false;
+            for line in entry.lines().map(str::trim) {
+                if in_dependencies {
+                    if line == "]" {
+                        in_dependencies = false;
+                    } else {
+                        package.dependencies.push(line.trim_end_matches(',').trim_matches('"').to_string());
+                    }
+                } else if let Some(name) = quoted(line, "name") {
+                    package.name = name;
+                } else if let Some(version) = quoted(line, "version") {
+                    package.version = version;
+                } else if line == "dependencies = [" {
+                    in_dependencies = true;
+                }
+            }
+            package
+        })
+        .collect()
+}
+
+/// Version of `dependency` that `package` is locked to use
+fn dependency_version(packages: &[LockedPackage], package: &str, version: &str, dependency: &str) -> Option<String> {
+    let locked = packages
+        .iter()
+        .find(|locked| locked.name == package && locked.version == version)?;
+    let entry = locked
+        .dependencies
+        .iter()
+        .find(|entry| entry.split(' ').next() == Some(dependency))?;
+    // Without a version only one version of the dependency is locked
+    match entry.split(' ').nth(1) {
+        Some(version) => Some(version.to_string()),
+        None => packages
+            .iter()
+            .find(|locked| locked.name == dependency)
+            .map(|locked| locked.version.clone()),
     }
-    
-    // Specify the export function name(s) for the linker.
-    // For sf-settings-menu, the dynamic loader expects 'create_settings_plugin'.
-    println!("cargo:rustc-cdylib-link-arg=/EXPORT:create_settings_plugin");
-    // If 'create_plugin' is also a valid generic entry point for this plugin:
-    // println!("cargo:rustc-cdylib-link-arg=/EXPORT:create_plugin"); 
+}
+
+/// File name of the cdylib for the target platform
+fn artifact_file_name(lib_name: &str, target_os: &str) -> String {
+    match target_os {
+        "windows" => format!("{}.dll", lib_name),
+        "macos" | "ios" => format!("lib{}.dylib", lib_name),
+        _ => format!("lib{}.so", lib_name),
+    }
+}
+
+/// Make sure the loader entry points are exported with the target's linker
+fn emit_export_args(target_env: &str) {
+    if target_env == "msvc" {
+        let hot_reload = env::var_os("CARGO_FEATURE_HOT_RELOAD").is_some();
+        let symbols = EXPORTED_SYMBOLS
+            .iter()
+            .chain(HOT_RELOAD_SYMBOLS.iter().filter(|_| hot_reload));
+        for symbol in symbols {
+            println!("cargo:rustc-cdylib-link-arg=/EXPORT:{}", symbol);
+        }
+    }
+    // GNU ld, lld and Apple's ld already export every `#[no_mangle] pub extern`
+    // function from a cdylib through the symbol list rustc passes them, and
+    // adding our own export list on macOS would hide rustc's, so nothing is
+    // emitted for those linkers.
+}
+
+fn display(path: &Path) -> String {
+    path.to_str()
+        .unwrap_or_else(|| panic!("Path {:?} is not valid UTF-8", path))
+        .to_string()
 }
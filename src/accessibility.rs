@@ -0,0 +1,304 @@
+//! Screen reader support
+//!
+//! Settings widgets carry an [`AccessibleControl`] which is turned into AccessKit
+//! metadata for Bevy's accessibility integration:
+//!
+//! - every row is a group named after its setting
+//! - toggles are check boxes, enum selectors are combo boxes and steppers are
+//!   sliders with their value, range and step; their `-` and `+` buttons are
+//!   named "Decrease …" and "Increase …"
+//! - settings with a doc comment use it as their description
+//! - the tab bar is a tab list with the current tab selected
+//!
+//! Changes made while the menu is shown are read out through a polite live
+//! region, see [`spawn_settings_announcer`], and the focused [`Focusable`] is
+//! reported as the accessibility focus.
+//!
+//! `bevy_ui` names every button after its text when it is added, so the
+//! metadata is written in `Last`, after that has happened.
+
+use bevy::a11y::accesskit::{Live, NodeBuilder, Toggled};
+use bevy::a11y::Focus;
+use bevy::prelude::*;
+use sf_ui_common::accessibility::{AccessibilityNode, Role};
+use sf_ui_common::components::{FocusState, Focusable};
+
+use crate::changes::SettingChanged;
+use crate::localization::{self, keys, Localization, LocalizedText};
+use crate::overlay::settings_menu_shown;
+use crate::registry::{self, SettingsSections};
+use crate::settings::{Settings, SettingsState, SettingsTab};
+use crate::tabs::{SettingsTabButton, SettingsTabContent, SettingsTabs};
+use crate::widgets::{field_label, SettingRange};
+
+/// What a settings widget is to assistive technology
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlRole {
+    /// Row holding a setting's label and control
+    Row,
+    Toggle,
+    /// Enum selector
+    Selector,
+    Slider(SettingRange),
+    Decrease,
+    Increase,
+}
+
+/// Accessibility metadata of a widget bound to the setting at `path`
+#[derive(Component, Clone, Debug)]
+pub struct AccessibleControl {
+    pub path: String,
+    pub label: String,
+    pub role: ControlRole,
+}
+
+impl AccessibleControl {
+    pub fn new(path: &str, label: &str, role: ControlRole) -> Self {
+        Self {
+            path: path.to_string(),
+            label: label.to_string(),
+            role,
+        }
+    }
+}
+
+/// Live region announcing setting changes
+#[derive(Component)]
+pub struct SettingsAnnouncer;
+
+pub struct SettingsAccessibilityPlugin;
+
+impl Plugin for SettingsAccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, (
+            describe_controls,
+            describe_tabs,
+            announce_setting_changes.run_if(settings_menu_shown),
+            sync_accessibility_focus,
+        ));
+    }
+}
+
+/// Spawn the invisible live region that reads out setting changes
+pub fn spawn_settings_announcer(parent: &mut ChildBuilder) {
+    let mut node = NodeBuilder::new(Role::Status);
+    node.set_live(Live::Polite);
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Px(0.0),
+                height: Val::Px(0.0),
+                ..default()
+            },
+            ..default()
+        },
+        AccessibilityNode::from(node),
+        SettingsAnnouncer,
+    ));
+}
+
+/// Write the metadata of new controls, and of every control when settings change
+fn describe_controls(world: &mut World, controls: &mut QueryState<(Entity, Ref<AccessibleControl>)>) {
+    let sections = world.get_resource::<SettingsSections>().cloned().unwrap_or_default();
+    let settings_changed = world.is_resource_changed::<Settings>()
+        || world.is_resource_changed::<Localization>()
+        || sections.iter().any(|section| section.is_changed(world));
+
+    let nodes: Vec<(Entity, NodeBuilder)> = controls
+        .iter(world)
+        .filter(|(_, control)| settings_changed || control.is_added())
+        .map(|(entity, control)| (entity, control_node(world, &control)))
+        .collect();
+
+    for (entity, node) in nodes {
+        world.entity_mut(entity).insert(AccessibilityNode::from(node));
+    }
+}
+
+fn control_node(world: &World, control: &AccessibleControl) -> NodeBuilder {
+    let mut node = NodeBuilder::new(match control.role {
+        ControlRole::Row => Role::Group,
+        ControlRole::Toggle => Role::CheckBox,
+        ControlRole::Selector => Role::ComboBox,
+        ControlRole::Slider(_) => Role::Slider,
+        ControlRole::Decrease | ControlRole::Increase => Role::Button,
+    });
+
+    let label = localization::setting_label(world, &control.path, &control.label);
+    let step_name = |id: &str, fallback: &str| {
+        let text = LocalizedText::new(id).with_arg("setting", label.as_str()).with_fallback(format!("{fallback} {label}"));
+        world.get_resource::<Localization>().map_or_else(|| text.fallback.clone(), |localization| localization.text(&text))
+    };
+    match control.role {
+        ControlRole::Decrease => node.set_name(step_name(keys::CONTROL_DECREASE, "Decrease")),
+        ControlRole::Increase => node.set_name(step_name(keys::CONTROL_INCREASE, "Increase")),
+        _ => {
+            node.set_name(label.as_str());
+            if let Some(docs) = field_docs(world, &control.path) {
+                node.set_description(docs);
+            }
+        }
+    }
+
+    registry::read_setting(world, &control.path, |value| match control.role {
+        ControlRole::Toggle => {
+            if let Some(on) = value.downcast_ref::<bool>() {
+                node.set_toggled(if *on { Toggled::True } else { Toggled::False });
+            }
+        }
+        ControlRole::Selector => node.set_value(localization::format_setting(world, &control.path, value)),
+        ControlRole::Slider(range) => {
+            node.set_value(localization::format_setting(world, &control.path, value));
+            if let Some(number) = numeric_value(value) {
+                node.set_numeric_value(number);
+            }
+            node.set_min_numeric_value(range.min.into());
+            node.set_max_numeric_value(range.max.into());
+            node.set_numeric_value_step(range.step.into());
+        }
+        _ => {}
+    });
+    node
+}
+
+/// Doc comment of the field at `path`
+fn field_docs(world: &World, path: &str) -> Option<String> {
+    registry::read_field_info(world, path, |field| field.docs().map(|docs| docs.trim().to_string())).flatten()
+}
+
+fn numeric_value(value: &dyn Reflect) -> Option<f64> {
+    if let Some(value) = value.downcast_ref::<f32>() {
+        Some((*value).into())
+    } else if let Some(Some(value)) = value.downcast_ref::<Option<u32>>() {
+        Some((*value).into())
+    } else {
+        value.downcast_ref::<u32>().map(|value| (*value).into())
+    }
+}
+
+/// Expose tab buttons as tabs and tab content as the current tab's panel
+fn describe_tabs(
+    mut commands: Commands,
+    state: Res<SettingsState>,
+    tabs: Res<SettingsTabs>,
+    localization: Res<Localization>,
+    buttons: Query<(Entity, Ref<SettingsTabButton>)>,
+    panels: Query<(Entity, Ref<SettingsTabContent>)>,
+) {
+    let label = |tab: &SettingsTab| match tabs.get(tab) {
+        Some(info) if info.label != tab.label() => info.label.clone(),
+        _ => localization.text(&LocalizedText::new(localization::tab_id(tab)).with_fallback(tab.label())),
+    };
+    let relabel = state.is_changed() || localization.is_changed();
+
+    for (entity, button) in &buttons {
+        if !relabel && !button.is_added() {
+            continue;
+        }
+        let mut node = NodeBuilder::new(Role::Tab);
+        node.set_name(label(&button.0));
+        node.set_selected(button.0 == state.current_tab);
+        commands.entity(entity).insert(AccessibilityNode::from(node));
+    }
+
+    for (entity, panel) in &panels {
+        if !relabel && !panel.is_added() {
+            continue;
+        }
+        let mut node = NodeBuilder::new(Role::TabPanel);
+        node.set_name(label(&state.current_tab));
+        commands.entity(entity).insert(AccessibilityNode::from(node));
+    }
+}
+
+/// Read out the latest change, e.g. "Music volume 0.55"
+fn announce_setting_changes(
+    mut changes: EventReader<SettingChanged>,
+    localization: Res<Localization>,
+    controls: Query<&AccessibleControl>,
+    mut announcers: Query<&mut AccessibilityNode, With<SettingsAnnouncer>>,
+) {
+    let Some(change) = changes.read().last() else {
+        return;
+    };
+
+    let control = controls.iter().find(|control| control.path == change.path);
+    let label = control
+        .map(|control| control.label.clone())
+        .unwrap_or_else(|| field_label(change.path.rsplit('.').next().unwrap_or(&change.path)));
+    let range = controls.iter().find_map(|control| match control.role {
+        ControlRole::Slider(range) if control.path == change.path => Some(range),
+        _ => None,
+    });
+    let message = localization.text(
+        &LocalizedText::new(keys::ANNOUNCE_CHANGE)
+            .with_arg("setting", localization.setting_label(&change.path, &label))
+            .with_arg("value", localization.format_value(&change.path, change.new.as_ref(), range)),
+    );
+
+    for mut announcer in &mut announcers {
+        announcer.set_name(message.as_str());
+    }
+}
+
+/// Report the focused [`Focusable`] to assistive technology
+fn sync_accessibility_focus(
+    focus: Option<ResMut<Focus>>,
+    focusables: Query<(Entity, &Focusable), Changed<Focusable>>,
+) {
+    let Some(mut focus) = focus else {
+        return;
+    };
+    for (entity, focusable) in &focusables {
+        if focusable.state == FocusState::Focused {
+            focus.0 = Some(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changes::{SettingsChangesPlugin, SettingsCommitSet};
+    use crate::localization::Language;
+    use crate::settings::Difficulty;
+
+    /// What the announcer reads out after `edit` changes the settings
+    fn announce(edit: impl FnOnce(&mut Settings), language: Language) -> String {
+        let mut localization = Localization::shipped();
+        localization.set_language(language);
+
+        let mut app = App::new();
+        app.add_plugins(SettingsChangesPlugin)
+            .register_type::<Settings>()
+            .init_resource::<Settings>()
+            .insert_resource(localization)
+            .add_systems(PostUpdate, announce_setting_changes.after(SettingsCommitSet));
+        let announcer = app
+            .world_mut()
+            .spawn((AccessibilityNode::from(NodeBuilder::new(Role::Status)), SettingsAnnouncer))
+            .id();
+        app.update();
+        edit(&mut app.world_mut().resource_mut::<Settings>());
+        app.update();
+
+        let node = app.world().get::<AccessibilityNode>(announcer).unwrap();
+        node.name().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn announces_values_like_the_menu() {
+        let difficulty = announce(|settings| settings.gameplay.difficulty = Difficulty::Hard, Language::French);
+        assert!(difficulty.contains("Difficile"), "{difficulty}");
+
+        let localization = Localization::shipped();
+        let resolution = announce(|settings| settings.video.resolution = (2560, 1440), Language::English);
+        let expected = localization.format_value("video.resolution", &(2560_u32, 1440_u32), None);
+        assert!(resolution.ends_with(&expected), "{resolution}");
+
+        let interval = announce(|settings| settings.gameplay.autosave.interval_minutes = None, Language::English);
+        let never = localization.format_value("gameplay.autosave.interval_minutes", &None::<u32>, None);
+        assert!(interval.ends_with(&never), "{interval}");
+    }
+}
@@ -0,0 +1,178 @@
+//! Minimal host that loads, unloads and reloads the settings plugin library
+//!
+//! Each cycle copies the built library to a fresh file (so the dynamic loader
+//! maps a new image rather than reusing the old one), builds it into a new app,
+//! restores the snapshot from the previous cycle, changes a setting and tears
+//! the plugin down again. It exits with a non-zero code if state is lost or plugin entities
+//! survive an unload:
+//!
+//! ```sh
+//! cargo run --features hot-reload --bin hot-reload-host
+//! ```
+
+use std::ffi::{c_char, CStr};
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use libloading::{Library, Symbol};
+
+use sf_settings_menu::persistence::SettingsPath;
+use sf_settings_menu::{Settings, SettingsMenuEntity, SettingsState, SettingsTab, SETTINGS_PLUGIN_ABI_VERSION};
+
+const CYCLES: usize = 3;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => {
+            println!("Reloaded the settings plugin {} times without losing state", CYCLES - 1);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    // Set by build.rs to the library built alongside this binary
+    let library_path = Path::new(env!("SF_PLUGIN_LIBRARY"));
+    let work_dir = std::env::temp_dir().join(format!("sf-settings-hot-reload-{}", std::process::id()));
+    fs::create_dir_all(&work_dir).map_err(|err| format!("failed to create {:?}: {}", work_dir, err))?;
+
+    let result = run_cycles(library_path, &work_dir);
+    let _ = fs::remove_dir_all(&work_dir);
+    result
+}
+
+fn run_cycles(library_path: &Path, work_dir: &Path) -> Result<(), String> {
+    let mut snapshot: Option<String> = None;
+
+    for cycle in 0..CYCLES {
+        let copy = work_dir.join(format!("cycle-{}-{}", cycle, env!("SF_PLUGIN_ARTIFACT")));
+        fs::copy(library_path, &copy).map_err(|err| format!("failed to copy {:?}: {}", library_path, err))?;
+
+        let library = unsafe { Library::new(&copy) }.map_err(|err| format!("failed to load {:?}: {}", copy, err))?;
+        let mut app = host_app(work_dir);
+
+        unsafe {
+            let abi_version: Symbol<extern "C" fn() -> u32> = symbol(&library, b"settings_plugin_abi_version")?;
+            if abi_version() != SETTINGS_PLUGIN_ABI_VERSION {
+                return Err(format!("plugin ABI {} does not match host ABI {}", abi_version(), SETTINGS_PLUGIN_ABI_VERSION));
+            }
+
+            let build: Symbol<unsafe extern "C" fn(*mut App)> = symbol(&library, b"settings_plugin_build")?;
+            build(&mut app);
+        }
+        app.finish();
+        app.cleanup();
+        // Run startup first so the snapshot wins over the settings file it loads
+        app.update();
+
+        if let Some(snapshot) = snapshot.take() {
+            let snapshot = std::ffi::CString::new(snapshot).map_err(|err| err.to_string())?;
+            let restored = unsafe {
+                let restore: Symbol<unsafe extern "C" fn(*mut World, *const c_char) -> bool> =
+                    symbol(&library, b"settings_plugin_restore")?;
+                restore(app.world_mut(), snapshot.as_ptr())
+            };
+            if !restored {
+                return Err(format!("cycle {}: restoring the snapshot failed", cycle));
+            }
+        }
+
+        for _ in 0..2 {
+            app.update();
+        }
+
+        if cycle > 0 {
+            check_restored(app.world(), cycle)?;
+        }
+
+        // Change something the next load has to pick up
+        app.world_mut().resource_mut::<Settings>().controls.camera.edge_scroll_speed = edge_scroll_speed(cycle);
+        let mut state = app.world_mut().resource_mut::<SettingsState>();
+        state.current_tab = SettingsTab::Audio;
+        state.is_visible = true;
+
+        if tagged_entities(app.world_mut()) == 0 {
+            return Err(format!("cycle {}: the plugin spawned no tagged entities", cycle));
+        }
+
+        snapshot = Some(unsafe { unload(&library, app.world_mut())? });
+
+        let remaining = tagged_entities(app.world_mut());
+        if remaining != 0 {
+            return Err(format!("cycle {}: {} plugin entities survived the unload", cycle, remaining));
+        }
+
+        // Systems and type registrations point into the library, so the app
+        // must go first. The image itself stays mapped: Bevy's global label
+        // interner keeps entries pointing into it, and closing it would crash
+        // the next build. Each reload maps a fresh copy, so this only leaks
+        // the old code.
+        drop(app);
+        std::mem::forget(library);
+        println!("Cycle {}: loaded, restored and tore down {:?}", cycle, copy);
+    }
+    Ok(())
+}
+
+/// Headless app with just what the plugin's systems need
+fn host_app(work_dir: &Path) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin, HierarchyPlugin))
+        // Tab content loads fonts, the handles just never resolve without a renderer
+        .init_asset::<Font>()
+        // Inserted before the plugin is built so it doesn't touch the real settings file
+        .insert_resource(SettingsPath(work_dir.join("settings.ron")));
+    app
+}
+
+unsafe fn unload(library: &Library, world: &mut World) -> Result<String, String> {
+    let unload: Symbol<unsafe extern "C" fn(*mut World) -> *mut c_char> = symbol(library, b"settings_plugin_unload")?;
+    let free: Symbol<unsafe extern "C" fn(*mut c_char)> = symbol(library, b"settings_plugin_free_snapshot")?;
+
+    let raw = unload(world);
+    if raw.is_null() {
+        return Err("snapshotting the settings failed".to_string());
+    }
+    // Copy the snapshot out so it outlives the library that allocated it
+    let snapshot = CStr::from_ptr(raw).to_string_lossy().into_owned();
+    free(raw);
+    Ok(snapshot)
+}
+
+fn check_restored(world: &World, cycle: usize) -> Result<(), String> {
+    let expected = edge_scroll_speed(cycle - 1);
+    let actual = world.resource::<Settings>().controls.camera.edge_scroll_speed;
+    if actual != expected {
+        return Err(format!("cycle {}: edge scroll speed is {}, expected {}", cycle, actual, expected));
+    }
+
+    let state = world.resource::<SettingsState>();
+    if state.current_tab != SettingsTab::Audio || !state.is_visible {
+        return Err(format!("cycle {}: settings state was not restored", cycle));
+    }
+    Ok(())
+}
+
+fn edge_scroll_speed(cycle: usize) -> f32 {
+    1000.0 + cycle as f32
+}
+
+fn tagged_entities(world: &mut World) -> usize {
+    world
+        .query_filtered::<(), With<SettingsMenuEntity>>()
+        .iter(world)
+        .count()
+}
+
+unsafe fn symbol<'lib, T>(library: &'lib Library, name: &[u8]) -> Result<Symbol<'lib, T>, String> {
+    library
+        .get(name)
+        .map_err(|err| format!("missing export {}: {}", String::from_utf8_lossy(name), err))
+}
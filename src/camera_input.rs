@@ -0,0 +1,258 @@
+//! Camera input processing
+//!
+//! Turns raw mouse, keyboard and cursor input into processed camera deltas with
+//! the player's [`ControlsSettings`] applied, so camera plugins only have to
+//! read [`CameraInput`] instead of re-implementing sensitivity, inversion,
+//! acceleration, dead zones and edge scrolling themselves.
+
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+
+use crate::settings::{AccelerationCurve, CameraSettings, ControlsSettings, KeybindSettings, Settings, SettingsState};
+
+/// Pointer speed (pixels per second) at which acceleration curves reach `1 + factor`
+const ACCELERATION_REFERENCE_SPEED: f32 = 1000.0;
+
+/// Scroll distance of one wheel line, used to normalise pixel based scrolling
+const PIXELS_PER_SCROLL_LINE: f32 = 100.0;
+
+/// Processed camera deltas for the current frame
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct CameraInput {
+    /// Mouse motion while the pan button is held, with `invert_y` applied
+    pub pan: Vec2,
+    /// Mouse motion while the rotate button is held, with `invert_y` applied
+    pub rotate: Vec2,
+    /// Zoom change for this frame, positive zooms in
+    pub zoom: f32,
+    /// Screen-space pan velocity from edge scrolling and the pan keys, in units per second
+    ///
+    /// `+y` is up, matching the `camera_pan_up` key.
+    pub scroll_velocity: Vec2,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+}
+
+impl CameraInput {
+    /// Clamp a camera zoom level to the player's zoom limits
+    ///
+    /// Doesn't panic when the limits are out of order, the maximum wins then.
+    pub fn clamp_zoom(&self, zoom: f32) -> f32 {
+        zoom.max(self.min_zoom).min(self.max_zoom)
+    }
+}
+
+/// Mouse buttons that route motion to panning or rotating
+///
+/// Host games can overwrite this resource to match their camera scheme.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct CameraInputButtons {
+    pub pan: Option<MouseButton>,
+    pub rotate: Option<MouseButton>,
+}
+
+impl Default for CameraInputButtons {
+    fn default() -> Self {
+        Self {
+            pan: Some(MouseButton::Middle),
+            rotate: Some(MouseButton::Right),
+        }
+    }
+}
+
+/// Set containing the camera input processing, order camera systems after it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CameraInputSet;
+
+pub struct CameraInputPlugin;
+
+impl Plugin for CameraInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraInput>()
+            .init_resource::<CameraInputButtons>()
+            .configure_sets(PreUpdate, CameraInputSet.after(InputSystem))
+            .add_systems(PreUpdate, process_camera_input.in_set(CameraInputSet))
+            .add_systems(Update, apply_cursor_lock);
+    }
+}
+
+/// Collect this frame's mouse events and publish the processed deltas
+fn process_camera_input(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    buttons: Res<CameraInputButtons>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut camera_input: ResMut<CameraInput>,
+) {
+    let controls = &settings.controls;
+    let camera = &controls.camera;
+
+    let raw_motion: Vec2 = motion_events.read().map(|event| event.delta).sum();
+    let motion = process_mouse_delta(raw_motion, time.delta_seconds(), controls);
+
+    let is_held = |button: Option<MouseButton>| button.is_some_and(|button| mouse_buttons.pressed(button));
+
+    let raw_scroll: f32 = wheel_events
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_SCROLL_LINE,
+        })
+        .sum();
+
+    let edge_direction = windows
+        .get_single()
+        .ok()
+        .filter(|_| camera.edge_scroll)
+        .map(|window| edge_scroll_direction(window, camera.edge_scroll_margin))
+        .unwrap_or(Vec2::ZERO);
+    let key_direction = pan_key_direction(&keys, &controls.keybinds);
+
+    *camera_input = CameraInput {
+        pan: if camera.middle_mouse_pan && is_held(buttons.pan) { apply_invert_y(motion, controls) } else { Vec2::ZERO },
+        rotate: if is_held(buttons.rotate) { apply_invert_y(motion, controls) } else { Vec2::ZERO },
+        zoom: raw_scroll * camera.zoom_speed,
+        scroll_velocity: edge_direction * camera.edge_scroll_speed
+            + key_direction * camera.keyboard_pan_speed,
+        min_zoom: camera.min_zoom,
+        max_zoom: camera.max_zoom,
+    };
+}
+
+/// Direction to scroll when the cursor sits within `margin` pixels of a window edge
+pub fn edge_scroll_direction(window: &Window, margin: f32) -> Vec2 {
+    let Some(cursor) = window.cursor_position() else {
+        return Vec2::ZERO;
+    };
+
+    let mut direction = Vec2::ZERO;
+    if cursor.x <= margin {
+        direction.x -= 1.0;
+    } else if cursor.x >= window.width() - margin {
+        direction.x += 1.0;
+    }
+    // Window coordinates grow downwards
+    if cursor.y <= margin {
+        direction.y += 1.0;
+    } else if cursor.y >= window.height() - margin {
+        direction.y -= 1.0;
+    }
+    direction.normalize_or_zero()
+}
+
+/// Direction requested by the camera pan keys
+pub fn pan_key_direction(keys: &ButtonInput<KeyCode>, keybinds: &KeybindSettings) -> Vec2 {
+    let mut direction = Vec2::ZERO;
+    if keys.pressed(keybinds.camera_pan_up) {
+        direction.y += 1.0;
+    }
+    if keys.pressed(keybinds.camera_pan_down) {
+        direction.y -= 1.0;
+    }
+    if keys.pressed(keybinds.camera_pan_left) {
+        direction.x -= 1.0;
+    }
+    if keys.pressed(keybinds.camera_pan_right) {
+        direction.x += 1.0;
+    }
+    direction.normalize_or_zero()
+}
+
+/// Confine the cursor to the primary window while `lock_cursor` is enabled
+fn apply_cursor_lock(
+    settings: Res<Settings>,
+    state: Res<SettingsState>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() && !state.is_changed() {
+        return;
+    }
+
+    // The settings overlay needs a free cursor
+    let grab_mode = if state.is_visible {
+        CursorGrabMode::None
+    } else {
+        cursor_grab_mode(&settings.controls.camera)
+    };
+    for mut window in &mut windows {
+        if window.cursor.grab_mode != grab_mode {
+            window.cursor.grab_mode = grab_mode;
+        }
+    }
+}
+
+fn cursor_grab_mode(camera: &CameraSettings) -> CursorGrabMode {
+    if camera.lock_cursor {
+        CursorGrabMode::Confined
+    } else {
+        CursorGrabMode::None
+    }
+}
+
+/// Apply dead zone, acceleration and sensitivity to a raw mouse delta
+///
+/// `delta_seconds` is the frame time the delta was accumulated over and is used to
+/// turn the delta into a pointer speed for the acceleration curve. Without a
+/// frame time, e.g. on the first frame, no acceleration is applied.
+pub fn process_mouse_delta(raw: Vec2, delta_seconds: f32, controls: &ControlsSettings) -> Vec2 {
+    let length = raw.length();
+    if length <= controls.mouse_dead_zone {
+        return Vec2::ZERO;
+    }
+
+    // Rescale so motion ramps up from zero at the edge of the dead zone
+    let delta = raw * ((length - controls.mouse_dead_zone) / length);
+
+    let acceleration = if delta_seconds > 0.0 {
+        let speed = delta.length() / delta_seconds;
+        controls.mouse_acceleration.multiplier(speed / ACCELERATION_REFERENCE_SPEED)
+    } else {
+        1.0
+    };
+
+    delta * acceleration * controls.mouse_sensitivity
+}
+
+/// Flip the vertical axis when the player asked for inverted look
+pub fn apply_invert_y(delta: Vec2, controls: &ControlsSettings) -> Vec2 {
+    if controls.invert_y {
+        Vec2::new(delta.x, -delta.y)
+    } else {
+        delta
+    }
+}
+
+impl AccelerationCurve {
+    /// Multiplier for a pointer moving at `normalized_speed` times the reference speed
+    pub fn multiplier(&self, normalized_speed: f32) -> f32 {
+        match *self {
+            AccelerationCurve::Off => 1.0,
+            AccelerationCurve::Linear { factor } => 1.0 + factor * normalized_speed,
+            AccelerationCurve::Quadratic { factor } => 1.0 + factor * normalized_speed * normalized_speed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_frame_motion_is_not_accelerated() {
+        let controls = ControlsSettings {
+            mouse_acceleration: AccelerationCurve::Quadratic { factor: 2.0 },
+            mouse_dead_zone: 0.0,
+            mouse_sensitivity: 1.0,
+            ..default()
+        };
+
+        assert_eq!(process_mouse_delta(Vec2::new(3.0, 4.0), 0.0, &controls), Vec2::new(3.0, 4.0));
+        assert!(process_mouse_delta(Vec2::new(3.0, 4.0), 0.001, &controls).length() > 5.0);
+    }
+}
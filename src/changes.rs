@@ -0,0 +1,352 @@
+//! Change events for individual settings
+//!
+//! Edits made during a frame, from the menu, keymap imports or game code, are
+//! committed together in [`SettingsCommitSet`] (in `PostUpdate`). Each commit
+//! compares the settings against the previous commit and sends:
+//!
+//! - one [`SettingChanged`] per changed field, e.g. `"audio.music_volume"` or
+//!   `"minimap.zoom"` for a plugin section
+//! - one [`SettingsSectionChanged<T>`] per changed section, e.g.
+//!   [`VideoSettingsChanged`], carrying the whole section before and after
+//!
+//! ```ignore
+//! fn apply_volume(mut changes: EventReader<AudioSettingsChanged>) {
+//!     for change in changes.read() {
+//!         set_master_volume(change.current.master_volume);
+//!     }
+//! }
+//! ```
+//!
+//! The values loaded at startup are the first commit and don't send events.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy::reflect::{ReflectFromReflect, ReflectRef, TypeRegistry};
+
+use crate::persistence::CORE_SETTINGS_KEY;
+use crate::registry::SettingsSections;
+use crate::settings::{
+    AudioSettings, ControlsSettings, GameplaySettings, InterfaceSettings, Settings, VideoSettings,
+};
+
+/// A single settings field changed
+#[derive(Event, Debug)]
+pub struct SettingChanged {
+    /// Reflection path of the field, as used by the settings widgets
+    pub path: String,
+    /// Field of [`Settings`] (`"video"`, `"audio"`, ...) or plugin section id the field belongs to
+    pub section: String,
+    /// Value at the previous commit
+    ///
+    /// Both values have the field's own type when it is registered with
+    /// [`ReflectFromReflect`], see [`clone_concrete`].
+    pub old: Box<dyn Reflect>,
+    pub new: Box<dyn Reflect>,
+}
+
+impl SettingChanged {
+    /// The new value as `T`, if the field has that type
+    pub fn new_value<T: Reflect>(&self) -> Option<&T> {
+        self.new.downcast_ref::<T>()
+    }
+
+    /// The previous value as `T`, if the field has that type
+    pub fn old_value<T: Reflect>(&self) -> Option<&T> {
+        self.old.downcast_ref::<T>()
+    }
+}
+
+/// A settings section changed, sent once per commit after its [`SettingChanged`] events
+#[derive(Event, Clone)]
+pub struct SettingsSectionChanged<T> {
+    pub previous: T,
+    pub current: T,
+    /// Paths of the changed fields
+    pub paths: Vec<String>,
+}
+
+pub type VideoSettingsChanged = SettingsSectionChanged<VideoSettings>;
+pub type AudioSettingsChanged = SettingsSectionChanged<AudioSettings>;
+pub type GameplaySettingsChanged = SettingsSectionChanged<GameplaySettings>;
+pub type ControlsSettingsChanged = SettingsSectionChanged<ControlsSettings>;
+pub type InterfaceSettingsChanged = SettingsSectionChanged<InterfaceSettings>;
+
+/// Set committing settings edits, order systems after it to see this frame's events
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SettingsCommitSet;
+
+/// Settings as of the last commit, keyed like the settings file
+#[derive(Resource, Default)]
+struct CommittedSettings {
+    values: HashMap<String, Box<dyn Reflect>>,
+}
+
+pub struct SettingsChangesPlugin;
+
+impl Plugin for SettingsChangesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommittedSettings>()
+            .add_event::<SettingChanged>()
+            .add_event::<VideoSettingsChanged>()
+            .add_event::<AudioSettingsChanged>()
+            .add_event::<GameplaySettingsChanged>()
+            .add_event::<ControlsSettingsChanged>()
+            .add_event::<InterfaceSettingsChanged>()
+            // Tuples don't register this themselves, and the resolution is one
+            .register_type::<(u32, u32)>()
+            .register_type_data::<(u32, u32), ReflectFromReflect>()
+            .add_systems(PostUpdate, commit_settings.in_set(SettingsCommitSet));
+    }
+}
+
+/// Send change events for everything edited since the last commit
+fn commit_settings(world: &mut World) {
+    if world.is_resource_changed::<Settings>() {
+        let current = world.resource::<Settings>().clone_value();
+        if let Some(previous) = replace_committed(world, CORE_SETTINGS_KEY, current.clone_value()) {
+            send_settings_changes(world, previous.as_ref(), current.as_ref());
+        }
+    }
+
+    let sections = world.get_resource::<SettingsSections>().cloned().unwrap_or_default();
+    for section in sections.iter() {
+        // Hidden sections hold bookkeeping, not settings the player edits
+        if section.hidden || !section.is_changed(world) {
+            continue;
+        }
+        let Some(current) = section.reflect(world).map(|value| value.clone_value()) else {
+            continue;
+        };
+        let Some(previous) = replace_committed(world, section.id, current.clone_value()) else {
+            continue;
+        };
+
+        let mut paths = Vec::new();
+        diff_fields(section.id, previous.as_ref(), current.as_ref(), &mut paths);
+        if paths.is_empty() {
+            continue;
+        }
+        send_field_changes(world, section.id, section.id, &paths, previous.as_ref(), current.as_ref());
+        section.send_changed(world, previous.as_ref(), current.as_ref(), paths);
+    }
+}
+
+/// Store `current` as the committed value of `key`, returning the previous commit
+fn replace_committed(world: &mut World, key: &str, current: Box<dyn Reflect>) -> Option<Box<dyn Reflect>> {
+    world
+        .resource_mut::<CommittedSettings>()
+        .values
+        .insert(key.to_string(), current)
+}
+
+fn send_settings_changes(world: &mut World, previous: &dyn Reflect, current: &dyn Reflect) {
+    let mut paths = Vec::new();
+    diff_fields("", previous, current, &mut paths);
+    if paths.is_empty() {
+        return;
+    }
+
+    // Field events for every section first, then the typed section events
+    for section in ["video", "audio", "gameplay", "controls", "interface"] {
+        let section_paths: Vec<String> = paths
+            .iter()
+            .filter(|path| path_section(path) == section)
+            .cloned()
+            .collect();
+        send_field_changes(world, section, "", &section_paths, previous, current);
+    }
+
+    send_section_changed::<VideoSettings>(world, "video", &paths, previous, current);
+    send_section_changed::<AudioSettings>(world, "audio", &paths, previous, current);
+    send_section_changed::<GameplaySettings>(world, "gameplay", &paths, previous, current);
+    send_section_changed::<ControlsSettings>(world, "controls", &paths, previous, current);
+    send_section_changed::<InterfaceSettings>(world, "interface", &paths, previous, current);
+}
+
+/// Send a [`SettingChanged`] for every path
+///
+/// `root` is the path prefix of the values themselves: empty for [`Settings`],
+/// the section id for plugin sections.
+fn send_field_changes(
+    world: &mut World,
+    section: &str,
+    root: &str,
+    paths: &[String],
+    previous: &dyn Reflect,
+    current: &dyn Reflect,
+) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for path in paths {
+        let field_path = if root.is_empty() {
+            path.as_str()
+        } else {
+            path.strip_prefix(root)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .unwrap_or("")
+        };
+        let (Some(old), Some(new)) = (field(previous, field_path), field(current, field_path)) else {
+            continue;
+        };
+
+        world.send_event(SettingChanged {
+            path: path.clone(),
+            section: section.to_string(),
+            old: clone_concrete(&registry, old),
+            new: clone_concrete(&registry, new),
+        });
+    }
+}
+
+/// Send the typed event for the built-in section stored in the `section` field of [`Settings`]
+fn send_section_changed<T: FromReflect>(
+    world: &mut World,
+    section: &str,
+    paths: &[String],
+    previous: &dyn Reflect,
+    current: &dyn Reflect,
+) {
+    let paths: Vec<String> = paths
+        .iter()
+        .filter(|path| path_section(path) == section)
+        .cloned()
+        .collect();
+    if paths.is_empty() {
+        return;
+    }
+
+    let previous = field(previous, section).and_then(T::from_reflect);
+    let current = field(current, section).and_then(T::from_reflect);
+    if let (Some(previous), Some(current)) = (previous, current) {
+        world.send_event(SettingsSectionChanged { previous, current, paths });
+    }
+}
+
+/// Append the paths of every leaf field that differs between `old` and `new`
+///
+/// Structs are compared field by field; any other value (numbers, enums,
+/// tuples, lists) is a single setting.
+pub fn diff_fields(prefix: &str, old: &dyn Reflect, new: &dyn Reflect, paths: &mut Vec<String>) {
+    if let (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) = (old.reflect_ref(), new.reflect_ref()) {
+        for index in 0..old_struct.field_len() {
+            let Some(name) = old_struct.name_at(index) else {
+                continue;
+            };
+            let (Some(old_field), Some(new_field)) = (old_struct.field_at(index), new_struct.field(name)) else {
+                continue;
+            };
+            let path = if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{prefix}.{name}")
+            };
+            diff_fields(&path, old_field, new_field, paths);
+        }
+        return;
+    }
+
+    if old.reflect_partial_eq(new) != Some(true) {
+        paths.push(prefix.to_string());
+    }
+}
+
+/// Copy of `value` with the type it represents, so it can be downcast
+///
+/// [`Reflect::clone_value`] turns enums, tuples and options into dynamic
+/// values. Types without [`ReflectFromReflect`] in `registry` keep such a copy.
+pub fn clone_concrete(registry: &TypeRegistry, value: &dyn Reflect) -> Box<dyn Reflect> {
+    value
+        .get_represented_type_info()
+        .and_then(|info| registry.get_type_data::<ReflectFromReflect>(info.type_id()))
+        .and_then(|from_reflect| from_reflect.from_reflect(value))
+        .unwrap_or_else(|| value.clone_value())
+}
+
+fn field<'a>(root: &'a dyn Reflect, path: &str) -> Option<&'a dyn Reflect> {
+    if path.is_empty() {
+        Some(root)
+    } else {
+        root.reflect_path(path).ok()
+    }
+}
+
+/// First segment of a setting path
+fn path_section(path: &str) -> &str {
+    path.split_once('.').map_or(path, |(head, _)| head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{Difficulty, DisplayMode, ScreenCorner, Settings};
+
+    fn changed_paths(old: &Settings, new: &Settings) -> Vec<String> {
+        let mut paths = Vec::new();
+        diff_fields("", old, new, &mut paths);
+        paths
+    }
+
+    #[test]
+    fn unchanged_settings_have_no_paths() {
+        assert!(changed_paths(&Settings::default(), &Settings::default()).is_empty());
+    }
+
+    #[test]
+    fn finds_nested_leaf_fields_in_order() {
+        let old = Settings::default();
+        let mut new = old.clone();
+        new.video.display_mode = DisplayMode::Borderless;
+        new.audio.master_volume = 0.25;
+        new.interface.hud.minimap_position = ScreenCorner::TopRight;
+
+        assert_eq!(
+            changed_paths(&old, &new),
+            ["video.display_mode", "audio.master_volume", "interface.hud.minimap_position"],
+        );
+    }
+
+    #[test]
+    fn tuples_and_options_are_single_settings() {
+        let old = Settings::default();
+        let mut new = old.clone();
+        new.video.resolution.0 += 1;
+        new.gameplay.autosave.interval_minutes = None;
+
+        assert_eq!(changed_paths(&old, &new), ["video.resolution", "gameplay.autosave.interval_minutes"]);
+    }
+
+    #[test]
+    fn prefix_starts_every_path() {
+        let old = Settings::default();
+        let mut new = old.clone();
+        new.audio.music_volume = 0.1;
+
+        let mut paths = Vec::new();
+        diff_fields("audio", &old.audio, &new.audio, &mut paths);
+        assert_eq!(paths, ["audio.music_volume"]);
+    }
+
+    #[test]
+    fn changed_values_keep_their_types() {
+        let mut app = App::new();
+        app.add_plugins(SettingsChangesPlugin)
+            .register_type::<Settings>()
+            .init_resource::<Settings>();
+        app.update();
+
+        let mut settings = app.world_mut().resource_mut::<Settings>();
+        settings.video.resolution = (2560, 1440);
+        settings.video.fps_limit = Some(144);
+        settings.gameplay.difficulty = Difficulty::Hard;
+        app.update();
+
+        let events = app.world().resource::<Events<SettingChanged>>();
+        let changes: Vec<&SettingChanged> = events.iter_current_update_events().collect();
+        let change = |path: &str| *changes.iter().find(|change| change.path == path).unwrap();
+        assert_eq!(change("video.resolution").new_value::<(u32, u32)>(), Some(&(2560, 1440)));
+        assert_eq!(change("video.fps_limit").new_value::<Option<u32>>(), Some(&Some(144)));
+        assert!(matches!(change("gameplay.difficulty").new_value::<Difficulty>(), Some(Difficulty::Hard)));
+        assert!(matches!(change("gameplay.difficulty").old_value::<Difficulty>(), Some(Difficulty::Normal)));
+    }
+}
@@ -0,0 +1,470 @@
+//! Colour-blind support
+//!
+//! [`InterfaceSettings::colorblind_mode`] picks the deficiency to compensate
+//! for and [`InterfaceSettings::colorblind_strength`] how strongly. Two things
+//! follow from it:
+//!
+//! - a screen-space filter on the top-most camera that shifts the colours the
+//!   player can't tell apart into ones they can (daltonization). It runs after
+//!   the UI pass, so menus and HUD are corrected too.
+//! - [`TeamPalette::team_color`], which swaps team colours for a palette that
+//!   stays distinct under the selected mode
+//!
+//! Designers can set [`ColorblindPreview`] to see the screen as a player with a
+//! deficiency does. The preview is applied after the correction, so it also
+//! shows how well the correction works.
+
+use bevy::asset::load_internal_asset;
+use bevy::color::palettes::css;
+use bevy::color::{Mix, Oklaba};
+use bevy::core_pipeline::core_2d::graph::{Core2d, Node2d};
+use bevy::core_pipeline::core_3d::graph::{Core3d, Node3d};
+use bevy::core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy::ecs::query::QueryItem;
+use bevy::prelude::*;
+use bevy::render::extract_component::{
+    ComponentUniforms, DynamicUniformIndex, ExtractComponentPlugin, UniformComponentPlugin,
+};
+use bevy::render::render_graph::{
+    NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+};
+use bevy::render::render_resource::binding_types::{sampler, texture_2d, uniform_buffer};
+use bevy::render::render_resource::{
+    BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
+    ColorWrites, FragmentState, MultisampleState, Operations, PipelineCache, PrimitiveState,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Sampler, SamplerBindingType,
+    SamplerDescriptor, ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines,
+    TextureFormat, TextureSampleType,
+};
+use bevy::render::renderer::{RenderContext, RenderDevice};
+use bevy::render::texture::BevyDefault;
+use bevy::render::view::{ExtractedView, ViewTarget};
+use bevy::render::{Render, RenderApp, RenderSet};
+use bevy::ui::graph::NodeUi;
+
+use crate::settings::{InterfaceSettings, Settings};
+
+const COLORBLIND_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(0x5f3c_9a1e_7d24_4b8e_a6c1_2e90_d4b7_13f5);
+
+/// Colour vision deficiency to compensate for
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    /// No red cones, reds look dark and close to greens
+    Protanopia,
+    /// No green cones, the most common red-green deficiency
+    Deuteranopia,
+    /// No blue cones, blues and greens and yellows and violets are confused
+    Tritanopia,
+    /// No colour vision, only brightness
+    Achromatopsia,
+}
+
+impl ColorblindMode {
+    /// How a player with this deficiency sees linear RGB, blended with normal
+    /// vision by `severity` (0-1)
+    ///
+    /// Matrices from Machado, Oliveira and Fernandes (2009) at full severity.
+    pub fn simulation_matrix(self, severity: f32) -> Mat3 {
+        let full = match self {
+            ColorblindMode::Off => return Mat3::IDENTITY,
+            ColorblindMode::Protanopia => rows([
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ]),
+            ColorblindMode::Deuteranopia => rows([
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ]),
+            ColorblindMode::Tritanopia => rows([
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ]),
+            ColorblindMode::Achromatopsia => rows([[0.2126, 0.7152, 0.0722]; 3]),
+        };
+        blend(full, severity)
+    }
+
+    /// Linear RGB correction that moves the colour information this deficiency
+    /// loses into channels it still sees, scaled by `strength` (0-1)
+    pub fn correction_matrix(self, strength: f32) -> Mat3 {
+        let error_shift = match self {
+            // Nothing to shift into without colour vision, the team palette carries it
+            ColorblindMode::Off | ColorblindMode::Achromatopsia => return Mat3::IDENTITY,
+            ColorblindMode::Protanopia | ColorblindMode::Deuteranopia => rows([
+                [0.0, 0.0, 0.0],
+                [0.7, 1.0, 0.0],
+                [0.7, 0.0, 1.0],
+            ]),
+            ColorblindMode::Tritanopia => rows([
+                [1.0, 0.0, 0.7],
+                [0.0, 1.0, 0.7],
+                [0.0, 0.0, 0.0],
+            ]),
+        };
+        let lost = Mat3::IDENTITY - self.simulation_matrix(1.0);
+        Mat3::IDENTITY + error_shift * lost * strength
+    }
+
+    /// Team colours that stay distinct for this deficiency, none without one
+    fn safe_palette(self) -> &'static [Color] {
+        match self {
+            ColorblindMode::Off => &[],
+            ColorblindMode::Protanopia | ColorblindMode::Deuteranopia => RED_GREEN_SAFE_COLORS,
+            ColorblindMode::Tritanopia => BLUE_YELLOW_SAFE_COLORS,
+            ColorblindMode::Achromatopsia => LUMINANCE_COLORS,
+        }
+    }
+}
+
+/// Default team colours
+pub const DEFAULT_TEAM_COLORS: &[Color] = &[
+    Color::srgb(0.85, 0.2, 0.2),
+    Color::srgb(0.2, 0.4, 0.9),
+    Color::srgb(0.2, 0.7, 0.3),
+    Color::srgb(0.95, 0.8, 0.2),
+    Color::srgb(0.6, 0.3, 0.8),
+    Color::srgb(0.95, 0.55, 0.15),
+    Color::srgb(0.2, 0.8, 0.85),
+    Color::srgb(0.95, 0.45, 0.7),
+];
+
+/// Okabe-Ito palette
+const RED_GREEN_SAFE_COLORS: &[Color] = &[
+    Color::srgb(0.835, 0.369, 0.0),
+    Color::srgb(0.0, 0.447, 0.698),
+    Color::srgb(0.0, 0.620, 0.451),
+    Color::srgb(0.941, 0.894, 0.259),
+    Color::srgb(0.800, 0.475, 0.655),
+    Color::srgb(0.902, 0.624, 0.0),
+    Color::srgb(0.337, 0.706, 0.914),
+    Color::srgb(0.94, 0.94, 0.94),
+];
+
+/// Paul Tol's vibrant palette
+const BLUE_YELLOW_SAFE_COLORS: &[Color] = &[
+    Color::srgb(0.8, 0.2, 0.067),
+    Color::srgb(0.0, 0.467, 0.733),
+    Color::srgb(0.0, 0.6, 0.533),
+    Color::srgb(0.933, 0.467, 0.2),
+    Color::srgb(0.933, 0.2, 0.467),
+    Color::srgb(0.2, 0.733, 0.933),
+    Color::srgb(0.733, 0.733, 0.733),
+    Color::srgb(0.1, 0.1, 0.1),
+];
+
+/// Alternating light and dark greys, far apart in brightness
+const LUMINANCE_COLORS: &[Color] = &[
+    Color::srgb(0.95, 0.95, 0.95),
+    Color::srgb(0.1, 0.1, 0.1),
+    Color::srgb(0.75, 0.75, 0.75),
+    Color::srgb(0.3, 0.3, 0.3),
+    Color::srgb(0.55, 0.55, 0.55),
+    Color::srgb(0.2, 0.2, 0.2),
+    Color::srgb(0.85, 0.85, 0.85),
+    Color::srgb(0.45, 0.45, 0.45),
+];
+
+/// Team colours, remapped for the player's colour-blind mode
+///
+/// Every team colour the game shows, on units, the minimap or the HUD, must be
+/// read through [`TeamPalette::team_color`]; colours taken from anywhere else,
+/// such as `sf_ui_common::colors`, don't follow the player's mode. Games with
+/// their own team colours replace [`TeamPalette::colors`] at startup.
+///
+/// The palette holds plain colours and doesn't depend on the settings types,
+/// so UI crates can read it without the settings menu; [`ColorblindPlugin`]
+/// keeps the remap in step with [`InterfaceSettings`].
+#[derive(Resource, Clone, Debug)]
+pub struct TeamPalette {
+    pub colors: Vec<Color>,
+    /// Palette of the selected mode, empty when it is off
+    safe_colors: &'static [Color],
+    /// How far [`TeamPalette::colors`] move towards `safe_colors`, 0 to 1
+    strength: f32,
+}
+
+impl Default for TeamPalette {
+    fn default() -> Self {
+        Self {
+            colors: DEFAULT_TEAM_COLORS.to_vec(),
+            safe_colors: &[],
+            strength: 0.0,
+        }
+    }
+}
+
+impl TeamPalette {
+    /// Colour of team `index`, blended towards the safe palette of the selected
+    /// mode by the colour-blind strength
+    pub fn team_color(&self, index: usize) -> Color {
+        let original = match self.colors.len() {
+            0 => Color::from(css::WHITE),
+            len => self.colors[index % len],
+        };
+        if self.safe_colors.is_empty() {
+            return original;
+        }
+
+        let safe = self.safe_colors[index % self.safe_colors.len()];
+        Oklaba::from(original).mix(&Oklaba::from(safe), self.strength).into()
+    }
+}
+
+/// Shows the screen as a player with `mode` sees it, for checking UI and art
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ColorblindPreview {
+    pub mode: ColorblindMode,
+    /// 0 is normal vision, 1 is the full deficiency
+    pub severity: f32,
+}
+
+impl Default for ColorblindPreview {
+    fn default() -> Self {
+        Self {
+            mode: ColorblindMode::Off,
+            severity: 1.0,
+        }
+    }
+}
+
+pub use filter::ColorblindFilter;
+
+mod filter {
+    // The `ShaderType` derive emits a trait check function that is never called
+    #![allow(dead_code)]
+
+    use bevy::prelude::*;
+    use bevy::render::extract_component::ExtractComponent;
+    use bevy::render::render_resource::ShaderType;
+
+    /// Linear RGB transform applied to a camera's output by the colour filter pass
+    #[derive(Component, Clone, Copy, Debug, ExtractComponent, ShaderType)]
+    pub struct ColorblindFilter {
+        pub matrix: Mat3,
+    }
+}
+
+#[derive(RenderLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct ColorblindFilterLabel;
+
+pub struct ColorblindPlugin;
+
+impl Plugin for ColorblindPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TeamPalette>()
+            .init_resource::<ColorblindPreview>()
+            .register_type::<ColorblindMode>()
+            .add_systems(PostUpdate, (sync_team_palette, sync_colorblind_filter));
+
+        // Headless apps have no renderer to filter
+        if app.get_sub_app(RenderApp).is_none() {
+            return;
+        }
+        load_internal_asset!(app, COLORBLIND_SHADER_HANDLE, "colorblind.wgsl", Shader::from_wgsl);
+        app.add_plugins((
+            ExtractComponentPlugin::<ColorblindFilter>::default(),
+            UniformComponentPlugin::<ColorblindFilter>::default(),
+        ));
+
+        app.sub_app_mut(RenderApp)
+            .init_resource::<SpecializedRenderPipelines<ColorblindPipeline>>()
+            .add_systems(Render, prepare_colorblind_pipelines.in_set(RenderSet::Prepare))
+            .add_render_graph_node::<ViewNodeRunner<ColorblindFilterNode>>(Core3d, ColorblindFilterLabel)
+            .add_render_graph_edges(Core3d, (NodeUi::UiPass, ColorblindFilterLabel, Node3d::Upscaling))
+            .add_render_graph_node::<ViewNodeRunner<ColorblindFilterNode>>(Core2d, ColorblindFilterLabel)
+            .add_render_graph_edges(Core2d, (NodeUi::UiPass, ColorblindFilterLabel, Node2d::Upscaling));
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<ColorblindPipeline>();
+    }
+}
+
+/// Combined correction and preview transform, identity when neither is active
+pub fn filter_matrix(interface: &InterfaceSettings, preview: &ColorblindPreview) -> Mat3 {
+    let correction = interface.colorblind_mode.correction_matrix(interface.colorblind_strength.clamp(0.0, 1.0));
+    preview.mode.simulation_matrix(preview.severity.clamp(0.0, 1.0)) * correction
+}
+
+/// Remap team colours for the colour-blind settings
+fn sync_team_palette(settings: Res<Settings>, mut palette: ResMut<TeamPalette>) {
+    if !settings.is_changed() {
+        return;
+    }
+    palette.safe_colors = settings.interface.colorblind_mode.safe_palette();
+    palette.strength = settings.interface.colorblind_strength.clamp(0.0, 1.0);
+}
+
+/// Put the filter on the top-most active camera
+///
+/// Cameras drawing to the same target share its texture, so filtering more
+/// than one would apply the correction several times.
+fn sync_colorblind_filter(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    preview: Res<ColorblindPreview>,
+    cameras: Query<(Entity, &Camera)>,
+    filtered: Query<Entity, With<ColorblindFilter>>,
+    mut current: Local<Option<(Entity, Mat3)>>,
+) {
+    let matrix = filter_matrix(&settings.interface, &preview);
+    let top = cameras
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .max_by_key(|(_, camera)| camera.order)
+        .map(|(entity, _)| entity);
+    let target = top.filter(|_| matrix != Mat3::IDENTITY).map(|entity| (entity, matrix));
+    if *current == target {
+        return;
+    }
+
+    for entity in &filtered {
+        if target.map(|(target, _)| target) != Some(entity) {
+            commands.entity(entity).remove::<ColorblindFilter>();
+        }
+    }
+    if let Some((entity, matrix)) = target {
+        commands.entity(entity).insert(ColorblindFilter { matrix });
+    }
+    *current = target;
+}
+
+#[derive(Resource)]
+struct ColorblindPipeline {
+    layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for ColorblindPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "colorblind_filter_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<ColorblindFilter>(true),
+                ),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+        ColorblindPipeline { layout, sampler }
+    }
+}
+
+impl SpecializedRenderPipeline for ColorblindPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("colorblind_filter".into()),
+            layout: vec![self.layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: COLORBLIND_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct ViewColorblindPipeline(CachedRenderPipelineId);
+
+fn prepare_colorblind_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ColorblindPipeline>>,
+    pipeline: Res<ColorblindPipeline>,
+    views: Query<(Entity, &ExtractedView), With<ColorblindFilter>>,
+) {
+    for (entity, view) in &views {
+        let format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        let id = pipelines.specialize(&pipeline_cache, &pipeline, format);
+        commands.entity(entity).insert(ViewColorblindPipeline(id));
+    }
+}
+
+#[derive(Default)]
+struct ColorblindFilterNode;
+
+impl ViewNode for ColorblindFilterNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewColorblindPipeline,
+        &'static DynamicUniformIndex<ColorblindFilter>,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (view_target, view_pipeline, uniform_index): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline = world.resource::<ColorblindPipeline>();
+        let Some(render_pipeline) = world.resource::<PipelineCache>().get_render_pipeline(view_pipeline.0) else {
+            return Ok(());
+        };
+        let Some(uniforms) = world.resource::<ComponentUniforms<ColorblindFilter>>().uniforms().binding() else {
+            return Ok(());
+        };
+
+        let post_process = view_target.post_process_write();
+        let bind_group = render_context.render_device().create_bind_group(
+            "colorblind_filter_bind_group",
+            &pipeline.layout,
+            &BindGroupEntries::sequential((post_process.source, &pipeline.sampler, uniforms)),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("colorblind_filter_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_render_pipeline(render_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+        Ok(())
+    }
+}
+
+/// Matrix from rows, glam stores columns
+fn rows(rows: [[f32; 3]; 3]) -> Mat3 {
+    Mat3::from_cols_array_2d(&rows).transpose()
+}
+
+fn blend(matrix: Mat3, amount: f32) -> Mat3 {
+    Mat3::IDENTITY * (1.0 - amount) + matrix * amount
+}
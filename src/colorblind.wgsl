@@ -0,0 +1,18 @@
+// Colour-blind correction and preview, a single linear RGB transform
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct ColorblindFilter {
+    matrix: mat3x3<f32>,
+}
+
+@group(0) @binding(0) var screen_texture: texture_2d<f32>;
+@group(0) @binding(1) var screen_sampler: sampler;
+@group(0) @binding(2) var<uniform> colorblind: ColorblindFilter;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(screen_texture, screen_sampler, in.uv);
+    // The correction can push channels below zero, anything above one is
+    // valid HDR and left alone
+    return vec4<f32>(max(colorblind.matrix * color.rgb, vec3<f32>(0.0)), color.a);
+}
@@ -1,8 +1,36 @@
+use std::ffi::{c_char, CString};
+use std::sync::OnceLock;
+
+#[cfg(feature = "hot-reload")]
+use std::ffi::CStr;
+
+// Not the prelude: its `Plugin` trait would make `name()` below ambiguous
+#[cfg(feature = "hot-reload")]
+use bevy::{app::App, ecs::world::World, log::error};
+
+#[cfg(feature = "hot-reload")]
+use crate::hot_reload;
 use crate::SettingsMenuPlugin;
 use crate::SfPluginHandle;
+use sf_plugin_template::GamePlugin;
 
-// Export the plugin creation function for dynamic loading
-// Using a unique name to avoid symbol conflicts
+/// Version of the exported plugin interface
+///
+/// Bump whenever the signature or semantics of an exported function change.
+/// Hosts must check it (and the Bevy, sf-plugin-template and rustc versions)
+/// before calling `create_settings_plugin`, since Rust types crossing the
+/// boundary have no stable layout.
+pub const SETTINGS_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Bevy version the plugin was built against
+pub const BEVY_VERSION: &str = env!("SF_BEVY_VERSION");
+/// sf-plugin-template version the plugin was built against
+pub const PLUGIN_TEMPLATE_VERSION: &str = env!("SF_PLUGIN_TEMPLATE_VERSION");
+/// `rustc -V` of the compiler that built the plugin
+pub const RUSTC_VERSION: &str = env!("SF_RUSTC_VERSION");
+
+/// Create the settings menu with the default [`crate::SettingsMenuConfig`],
+/// so menu integration is enabled and the plugin adds its menu item
 #[no_mangle]
 pub extern "C" fn create_settings_plugin() -> *mut SfPluginHandle {
     // Create a new plugin handle with our settings menu plugin
@@ -13,3 +41,149 @@ pub extern "C" fn create_settings_plugin() -> *mut SfPluginHandle {
     // Convert to raw pointer and return
     Box::into_raw(handle)
 }
+
+/// Free a handle returned by `create_settings_plugin`
+///
+/// The handle must be freed here rather than by the host so it is released by
+/// the allocator that created it.
+///
+/// # Safety
+///
+/// `handle` must come from `create_settings_plugin` in this library and must not
+/// be used afterwards. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn destroy_settings_plugin(handle: *mut SfPluginHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn settings_plugin_abi_version() -> u32 {
+    SETTINGS_PLUGIN_ABI_VERSION
+}
+
+#[no_mangle]
+pub extern "C" fn settings_plugin_bevy_version() -> *const c_char {
+    static VALUE: OnceLock<CString> = OnceLock::new();
+    c_string(&VALUE, BEVY_VERSION)
+}
+
+#[no_mangle]
+pub extern "C" fn settings_plugin_template_version() -> *const c_char {
+    static VALUE: OnceLock<CString> = OnceLock::new();
+    c_string(&VALUE, PLUGIN_TEMPLATE_VERSION)
+}
+
+#[no_mangle]
+pub extern "C" fn settings_plugin_rustc_version() -> *const c_char {
+    static VALUE: OnceLock<CString> = OnceLock::new();
+    c_string(&VALUE, RUSTC_VERSION)
+}
+
+/// `GamePlugin::name` of the plugin the constructor returns
+#[no_mangle]
+pub extern "C" fn settings_plugin_name() -> *const c_char {
+    static VALUE: OnceLock<CString> = OnceLock::new();
+    c_string(&VALUE, SettingsMenuPlugin::default().name())
+}
+
+/// `GamePlugin::version` of the plugin the constructor returns
+#[no_mangle]
+pub extern "C" fn settings_plugin_version() -> *const c_char {
+    static VALUE: OnceLock<CString> = OnceLock::new();
+    c_string(&VALUE, SettingsMenuPlugin::default().version())
+}
+
+/// NUL-terminated copy of `value` that lives for as long as the library is loaded
+fn c_string(cell: &'static OnceLock<CString>, value: &str) -> *const c_char {
+    cell.get_or_init(|| CString::new(value).unwrap_or_default())
+        .as_ptr()
+}
+
+/// Add the settings plugin to a host app rebuilt for a hot reload
+///
+/// # Safety
+///
+/// `app` must be a valid, exclusive pointer to an `App` built with the same Bevy
+/// version and compiler as this library.
+#[cfg(feature = "hot-reload")]
+#[no_mangle]
+pub unsafe extern "C" fn settings_plugin_build(app: *mut App) {
+    if let Some(app) = app.as_mut() {
+        app.add_plugins(SettingsMenuPlugin::default());
+    }
+}
+
+/// Snapshot the settings and despawn the plugin's entities before unloading
+///
+/// Returns a RON snapshot for `settings_plugin_restore`, or null on failure.
+/// The string must be released with `settings_plugin_free_snapshot` before the
+/// library is unloaded.
+///
+/// # Safety
+///
+/// `world` must be a valid, exclusive pointer to the world the plugin was built into.
+#[cfg(feature = "hot-reload")]
+#[no_mangle]
+pub unsafe extern "C" fn settings_plugin_unload(world: *mut World) -> *mut c_char {
+    let Some(world) = world.as_mut() else {
+        return std::ptr::null_mut();
+    };
+
+    match hot_reload::prepare_unload(world).map(CString::new) {
+        Ok(Ok(snapshot)) => snapshot.into_raw(),
+        Ok(Err(err)) => {
+            error!("Settings snapshot is not a valid C string: {}", err);
+            std::ptr::null_mut()
+        }
+        Err(err) => {
+            error!("Failed to snapshot settings before unload: {}", err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Restore a snapshot taken by `settings_plugin_unload`, possibly in an earlier
+/// load of the library
+///
+/// # Safety
+///
+/// `world` must be a valid, exclusive pointer to a world the plugin was built
+/// into and `snapshot` a valid NUL-terminated string.
+#[cfg(feature = "hot-reload")]
+#[no_mangle]
+pub unsafe extern "C" fn settings_plugin_restore(world: *mut World, snapshot: *const c_char) -> bool {
+    let Some(world) = world.as_mut() else {
+        return false;
+    };
+    if snapshot.is_null() {
+        return false;
+    }
+
+    let Ok(snapshot) = CStr::from_ptr(snapshot).to_str() else {
+        error!("Settings snapshot is not valid UTF-8");
+        return false;
+    };
+    match hot_reload::restore(world, snapshot) {
+        Ok(()) => true,
+        Err(err) => {
+            error!("Failed to restore settings after reload: {}", err);
+            false
+        }
+    }
+}
+
+/// Free a snapshot returned by `settings_plugin_unload`
+///
+/// # Safety
+///
+/// `snapshot` must come from `settings_plugin_unload` in this load of the
+/// library and must not be used afterwards. Null is ignored.
+#[cfg(feature = "hot-reload")]
+#[no_mangle]
+pub unsafe extern "C" fn settings_plugin_free_snapshot(snapshot: *mut c_char) {
+    if !snapshot.is_null() {
+        drop(CString::from_raw(snapshot));
+    }
+}
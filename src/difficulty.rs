@@ -0,0 +1,149 @@
+//! Difficulty modifiers
+//!
+//! Each [`Difficulty`] resolves to a set of [`DifficultyModifiers`] the
+//! simulation reads from the resource of the same name. The modifiers of Easy,
+//! Normal and Hard come from a [`DifficultyTable`] asset, by default
+//! [`DEFAULT_DIFFICULTY_PATH`]; Custom uses
+//! [`GameplaySettings::custom_difficulty`](crate::settings::GameplaySettings::custom_difficulty),
+//! which players edit in the Gameplay tab.
+//!
+//! Set [`MatchInProgress`] while a match runs. The resource then keeps the
+//! modifiers the match started with and the difficulty settings are locked in
+//! the menu:
+//!
+//! ```ignore
+//! fn start_match(mut in_progress: ResMut<MatchInProgress>) {
+//!     in_progress.0 = true;
+//! }
+//!
+//! fn ai_income(modifiers: Res<DifficultyModifiers>, base: f32) -> f32 {
+//!     base * (1.0 + modifiers.ai_resource_bonus)
+//! }
+//! ```
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::ron_asset::{RonAsset, RonAssetPath, RonAssetPlugin};
+use crate::settings::{Difficulty, Settings};
+use crate::widgets::{LockedSettings, SettingRange};
+
+/// Difficulty table the game ships in its assets
+pub const DEFAULT_DIFFICULTY_PATH: &str = "gameplay/settings.difficulty.ron";
+
+/// Table used until the game's table has loaded, or when it has none
+const EMBEDDED_DIFFICULTY: &str = include_str!("../assets/gameplay/settings.difficulty.ron");
+
+/// Settings locked while a match is in progress
+const LOCKED_PATHS: [&str; 2] = ["gameplay.difficulty", "gameplay.custom_difficulty"];
+
+/// How a difficulty changes the game
+#[derive(Resource, Reflect, Deserialize, Clone, PartialEq, Debug)]
+pub struct DifficultyModifiers {
+    /// Extra resources AI players gather, e.g. 0.5 for 50% more
+    #[reflect(@SettingRange::new(-0.5, 1.0, 0.05))]
+    pub ai_resource_bonus: f32,
+    /// Seconds before AI players react to what they see
+    #[reflect(@SettingRange::new(0.0, 2.0, 0.1))]
+    pub ai_reaction_delay: f32,
+    /// Multiplier for damage dealt by the player's units
+    #[reflect(@SettingRange::new(0.25, 2.0, 0.05))]
+    pub player_damage_multiplier: f32,
+    /// Multiplier for damage dealt by AI units
+    #[reflect(@SettingRange::new(0.25, 2.0, 0.05))]
+    pub ai_damage_multiplier: f32,
+    /// AI players see through the fog of war
+    pub ai_ignores_fog: bool,
+}
+
+impl Default for DifficultyModifiers {
+    fn default() -> Self {
+        Self {
+            ai_resource_bonus: 0.0,
+            ai_reaction_delay: 0.5,
+            player_damage_multiplier: 1.0,
+            ai_damage_multiplier: 1.0,
+            ai_ignores_fog: false,
+        }
+    }
+}
+
+/// Modifiers of the predefined difficulties
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, PartialEq, Debug)]
+pub struct DifficultyTable {
+    pub easy: DifficultyModifiers,
+    pub normal: DifficultyModifiers,
+    pub hard: DifficultyModifiers,
+}
+
+impl DifficultyTable {
+    /// Modifiers for `difficulty`, with `custom` standing in for Custom
+    pub fn modifiers<'a>(&'a self, difficulty: &Difficulty, custom: &'a DifficultyModifiers) -> &'a DifficultyModifiers {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+            Difficulty::Custom => custom,
+        }
+    }
+}
+
+impl Default for DifficultyTable {
+    fn default() -> Self {
+        ron::from_str(EMBEDDED_DIFFICULTY).expect("embedded difficulty table is valid")
+    }
+}
+
+/// Whether a match is running; the host sets it
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MatchInProgress(pub bool);
+
+impl RonAsset for DifficultyTable {
+    const EXTENSION: &'static str = "difficulty.ron";
+    const DEFAULT_PATH: &'static str = DEFAULT_DIFFICULTY_PATH;
+    const DESCRIPTION: &'static str = "difficulty table";
+}
+
+/// Difficulty table to load, [`DEFAULT_DIFFICULTY_PATH`] unless the host sets another
+pub type DifficultyTablePath = RonAssetPath<DifficultyTable>;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<DifficultyTable>::default())
+            .init_resource::<DifficultyModifiers>()
+            .init_resource::<MatchInProgress>()
+            .init_resource::<LockedSettings>()
+            .register_type::<DifficultyModifiers>()
+            .add_systems(Update, (lock_difficulty_settings, publish_difficulty_modifiers));
+    }
+}
+
+fn lock_difficulty_settings(in_progress: Res<MatchInProgress>, mut locks: ResMut<LockedSettings>) {
+    if !in_progress.is_changed() {
+        return;
+    }
+    for path in LOCKED_PATHS {
+        if in_progress.0 {
+            locks.lock(path);
+        } else {
+            locks.unlock(path);
+        }
+    }
+}
+
+/// Resolve the selected difficulty into [`DifficultyModifiers`], leaving them
+/// as they are while a match is in progress
+fn publish_difficulty_modifiers(
+    settings: Res<Settings>,
+    table: Res<DifficultyTable>,
+    in_progress: Res<MatchInProgress>,
+    mut modifiers: ResMut<DifficultyModifiers>,
+) {
+    if in_progress.0 || !(settings.is_changed() || table.is_changed() || in_progress.is_changed()) {
+        return;
+    }
+    let gameplay = &settings.gameplay;
+    modifiers.set_if_neq(table.modifiers(&gameplay.difficulty, &gameplay.custom_difficulty).clone());
+}
@@ -0,0 +1,450 @@
+//! Accessibility display options
+//!
+//! [`UiAccessibility`] mirrors the display options of [`InterfaceSettings`] for
+//! the settings menu and any other UI that wants to follow them:
+//!
+//! - `text_scale` multiplies font sizes, independently of the UI scale
+//! - `high_contrast` swaps the menu colours for [`HIGH_CONTRAST`]
+//! - `reduce_motion` turns off the overlay's slide-in; games should scale screen
+//!   shake and similar effects by [`UiAccessibility::motion_scale`]
+//! - `dyslexia_font` holds the font from [`DYSLEXIA_FONT_PATH`] while it is
+//!   selected, the game ships the file in its assets
+//! - `cursor_size` above normal replaces the system cursor with a scaled
+//!   [`CURSOR_IMAGE_PATH`], since Bevy can't resize the system cursor; games
+//!   hide the cursor through [`GameCursor`] then, since the system one is
+//!   already hidden
+//!
+//! The menu follows the options live: every text and background below the
+//! standalone panel or the embedded menu remembers how it was spawned and is
+//! restyled from that whenever the options change.
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use bevy::window::PrimaryWindow;
+use sf_ui_common::colors::{button, focus};
+
+use crate::overlay::{EmbeddedSettingsMenu, SettingsPanel};
+use crate::settings::{InterfaceSettings, Settings};
+use crate::theme::SettingsTheme;
+use crate::validation::SettingErrorText;
+use crate::SettingsMenuEntity;
+
+/// Dyslexia-friendly font, loaded from the game's assets
+pub const DYSLEXIA_FONT_PATH: &str = "fonts/OpenDyslexic-Regular.otf";
+
+/// Pointer image drawn for the larger cursor sizes, with its hotspot at the top left
+pub const CURSOR_IMAGE_PATH: &str = "cursors/pointer.png";
+
+/// Size of [`CURSOR_IMAGE_PATH`] at [`CursorSize::Normal`], in logical pixels
+const CURSOR_IMAGE_SIZE: f32 = 32.0;
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CursorSize {
+    /// The system cursor
+    #[default]
+    Normal,
+    Large,
+    ExtraLarge,
+}
+
+impl CursorSize {
+    pub fn scale(self) -> f32 {
+        match self {
+            CursorSize::Normal => 1.0,
+            CursorSize::Large => 1.5,
+            CursorSize::ExtraLarge => 2.0,
+        }
+    }
+}
+
+/// Colours used while [`UiAccessibility::high_contrast`] is set
+#[derive(Clone, Copy, Debug)]
+pub struct ContrastPalette {
+    pub text: Color,
+    pub error: Color,
+    pub background: Color,
+    pub button: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+    pub focused: Color,
+}
+
+/// White text on black, with buttons that stay well apart from each other
+pub const HIGH_CONTRAST: ContrastPalette = ContrastPalette {
+    text: Color::WHITE,
+    error: Color::srgb(1.0, 0.85, 0.0),
+    background: Color::BLACK,
+    button: Color::srgb(0.12, 0.12, 0.12),
+    hovered: Color::srgb(0.3, 0.3, 0.3),
+    pressed: Color::srgb(0.0, 0.35, 0.8),
+    focused: Color::srgb(0.55, 0.35, 0.0),
+};
+
+/// Display options for UI plugins to follow, kept in sync with [`InterfaceSettings`]
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct UiAccessibility {
+    pub text_scale: f32,
+    pub high_contrast: bool,
+    pub reduce_motion: bool,
+    /// Set while the dyslexia-friendly font is selected
+    pub dyslexia_font: Option<Handle<Font>>,
+    pub cursor_size: CursorSize,
+}
+
+impl Default for UiAccessibility {
+    fn default() -> Self {
+        Self {
+            text_scale: 1.0,
+            high_contrast: false,
+            reduce_motion: false,
+            dyslexia_font: None,
+            cursor_size: CursorSize::Normal,
+        }
+    }
+}
+
+impl UiAccessibility {
+    pub fn from_settings(interface: &InterfaceSettings, asset_server: Option<&AssetServer>) -> Self {
+        Self {
+            text_scale: interface.text_scale,
+            high_contrast: interface.high_contrast,
+            reduce_motion: interface.reduce_motion,
+            dyslexia_font: asset_server
+                .filter(|_| interface.dyslexia_font)
+                .map(|asset_server| asset_server.load(DYSLEXIA_FONT_PATH)),
+            cursor_size: interface.cursor_size,
+        }
+    }
+
+    pub fn font_size(&self, size: f32) -> f32 {
+        size * self.text_scale
+    }
+
+    /// `font`, or the dyslexia-friendly font while it is selected
+    pub fn font(&self, font: &Handle<Font>) -> Handle<Font> {
+        self.dyslexia_font.clone().unwrap_or_else(|| font.clone())
+    }
+
+    /// 0 with reduced motion, 1 otherwise; multiply screen shake and the like by it
+    pub fn motion_scale(&self) -> f32 {
+        if self.reduce_motion {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    pub fn text_color(&self, color: Color) -> Color {
+        if self.high_contrast {
+            HIGH_CONTRAST.text
+        } else {
+            color
+        }
+    }
+
+    /// Background for a node styled with `color`, mapping the shared button and
+    /// focus colours to their high contrast counterparts
+    pub fn background(&self, color: Color) -> Color {
+        self.contrast_background(color, [button::NORMAL, button::HOVERED, button::PRESSED, focus::HIGHLIGHT])
+    }
+
+    /// [`UiAccessibility::background`] for a node styled by `theme`
+    pub fn themed_background(&self, color: Color, theme: &SettingsTheme) -> Color {
+        let colors = &theme.colors;
+        if color == colors.tab.0 {
+            return self.background(button::NORMAL);
+        }
+        if color == colors.tab_selected.0 {
+            return self.background(button::PRESSED);
+        }
+        self.contrast_background(
+            color,
+            [colors.button.0, colors.button_hovered.0, colors.button_pressed.0, colors.focused.0],
+        )
+    }
+
+    /// `color`, or its high contrast counterpart given the normal, hovered,
+    /// pressed and focused button colours
+    fn contrast_background(&self, color: Color, [normal, hovered, pressed, focused]: [Color; 4]) -> Color {
+        if !self.high_contrast || color.alpha() == 0.0 {
+            return color;
+        }
+        if color == normal {
+            HIGH_CONTRAST.button
+        } else if color == hovered {
+            HIGH_CONTRAST.hovered
+        } else if color == pressed {
+            HIGH_CONTRAST.pressed
+        } else if color == focused {
+            HIGH_CONTRAST.focused
+        } else {
+            HIGH_CONTRAST.background
+        }
+    }
+}
+
+/// Text styles of a menu text as spawned or last themed
+#[derive(Component)]
+pub(crate) struct MenuTextBase {
+    sections: Vec<TextStyle>,
+}
+
+impl MenuTextBase {
+    /// Take a new font size and colour from the theme
+    pub(crate) fn set_themed(&mut self, font_size: f32, color: Color) {
+        for style in &mut self.sections {
+            style.font_size = font_size;
+            style.color = color;
+        }
+    }
+}
+
+/// Background of a menu node as last set by the menu, and as shown
+#[derive(Component)]
+struct MenuBackground {
+    base: Color,
+    shown: Color,
+}
+
+/// Menu node whose background high contrast leaves alone, for colours that
+/// only mean something while they stay apart, such as the HUD preview's
+#[derive(Component)]
+pub struct KeepsBackground;
+
+/// Roots of the standalone panel and of the menu embedded in the host menu
+type MenuRoot = Or<(With<SettingsPanel>, With<EmbeddedSettingsMenu>)>;
+
+/// Larger cursor drawn in place of the system one
+#[derive(Component)]
+pub struct SoftwareCursor;
+
+/// Whether the game wants a mouse cursor shown
+///
+/// While a larger cursor size is selected the system cursor stays hidden and
+/// the software cursor is drawn instead, only while `visible` is set. `visible`
+/// takes the window's cursor visibility when the software cursor takes over,
+/// and a window cursor the game shows again sets it; the window gets it back
+/// once the software cursor is no longer used.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GameCursor {
+    pub visible: bool,
+    /// Set while the software cursor owns the window's cursor visibility
+    managed: bool,
+}
+
+impl Default for GameCursor {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            managed: false,
+        }
+    }
+}
+
+/// Systems applying the display options to the menu, in `PostUpdate` before UI layout
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UiAccessibilitySet;
+
+pub struct UiAccessibilityPlugin;
+
+impl Plugin for UiAccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiAccessibility>()
+            .init_resource::<GameCursor>()
+            .register_type::<CursorSize>()
+            .add_systems(
+                PostUpdate,
+                (
+                    sync_ui_accessibility,
+                    track_menu_nodes,
+                    (restyle_menu_text, restyle_menu_backgrounds, update_software_cursor),
+                )
+                    .chain()
+                    .in_set(UiAccessibilitySet)
+                    .before(bevy::ui::UiSystem::Layout),
+            );
+    }
+}
+
+fn sync_ui_accessibility(
+    settings: Res<Settings>,
+    asset_server: Option<Res<AssetServer>>,
+    mut options: ResMut<UiAccessibility>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    options.set_if_neq(UiAccessibility::from_settings(&settings.interface, asset_server.as_deref()));
+}
+
+/// Remember how new nodes of the settings menu were styled
+fn track_menu_nodes(
+    mut commands: Commands,
+    nodes: Query<Entity, Added<Node>>,
+    parents: Query<&Parent>,
+    roots: Query<(), MenuRoot>,
+    texts: Query<&Text>,
+    backgrounds: Query<&BackgroundColor, Without<KeepsBackground>>,
+) {
+    for entity in &nodes {
+        let in_menu = roots.contains(entity) || parents.iter_ancestors(entity).any(|ancestor| roots.contains(ancestor));
+        if !in_menu {
+            continue;
+        }
+
+        if let Ok(text) = texts.get(entity) {
+            commands.entity(entity).insert(MenuTextBase {
+                sections: text.sections.iter().map(|section| section.style.clone()).collect(),
+            });
+        }
+        if let Ok(background) = backgrounds.get(entity) {
+            commands.entity(entity).insert(MenuBackground {
+                base: background.0,
+                shown: background.0,
+            });
+        }
+    }
+}
+
+fn restyle_menu_text(
+    options: Res<UiAccessibility>,
+    mut texts: Query<(Ref<MenuTextBase>, &mut Text, Has<SettingErrorText>)>,
+) {
+    for (base, mut text, is_error) in &mut texts {
+        if !options.is_changed() && !base.is_changed() {
+            continue;
+        }
+        for (section, style) in text.sections.iter_mut().zip(&base.sections) {
+            section.style = TextStyle {
+                font: options.font(&style.font),
+                font_size: options.font_size(style.font_size),
+                color: if is_error && options.high_contrast {
+                    HIGH_CONTRAST.error
+                } else {
+                    options.text_color(style.color)
+                },
+            };
+        }
+    }
+}
+
+/// Map menu backgrounds, including the ones set by hover and focus systems since
+fn restyle_menu_backgrounds(
+    options: Res<UiAccessibility>,
+    theme: Option<Res<SettingsTheme>>,
+    mut backgrounds: Query<(Mut<MenuBackground>, Mut<BackgroundColor>)>,
+) {
+    for (mut menu, mut background) in &mut backgrounds {
+        if !options.is_changed() && !menu.is_added() && !background.is_changed() {
+            continue;
+        }
+        // Anything other than what was shown last was set by the menu itself
+        if background.0 != menu.shown {
+            menu.base = background.0;
+        }
+        let shown = match &theme {
+            Some(theme) => options.themed_background(menu.base, theme),
+            None => options.background(menu.base),
+        };
+        menu.shown = shown;
+        background.set_if_neq(BackgroundColor(shown));
+    }
+}
+
+/// Draw a scaled cursor in place of the system one for the larger cursor
+/// sizes, while the game shows a cursor
+fn update_software_cursor(
+    mut commands: Commands,
+    options: Res<UiAccessibility>,
+    mut game_cursor: ResMut<GameCursor>,
+    asset_server: Option<Res<AssetServer>>,
+    ui_scale: Option<Res<UiScale>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut cursors: Query<(Entity, &mut Style, &mut Visibility), With<SoftwareCursor>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+
+    if options.cursor_size == CursorSize::Normal {
+        for (entity, ..) in &cursors {
+            commands.entity(entity).despawn_recursive();
+        }
+        if game_cursor.managed {
+            game_cursor.managed = false;
+            window.cursor.visible = game_cursor.visible;
+        }
+        return;
+    }
+
+    // Only the game shows the system cursor while we manage it
+    if !game_cursor.managed || window.cursor.visible {
+        game_cursor.visible = window.cursor.visible;
+        game_cursor.managed = true;
+        window.cursor.visible = false;
+    }
+    if !game_cursor.visible {
+        for (entity, ..) in &cursors {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let scale = ui_scale.map_or(1.0, |ui_scale| ui_scale.0);
+    let size = Val::Px(CURSOR_IMAGE_SIZE * options.cursor_size.scale() / scale);
+    let position = window.cursor_position().map(|position| position / scale);
+
+    let Ok((_, mut style, mut visibility)) = cursors.get_single_mut() else {
+        let Some(asset_server) = asset_server else {
+            return;
+        };
+        commands.spawn((
+            ImageBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: size,
+                    height: size,
+                    ..default()
+                },
+                image: UiImage::new(asset_server.load(CURSOR_IMAGE_PATH)),
+                focus_policy: FocusPolicy::Pass,
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            Name::new("SoftwareCursor"),
+            SoftwareCursor,
+            SettingsMenuEntity,
+        ));
+        return;
+    };
+
+    let Some(position) = position else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Inherited);
+    let (left, top) = (Val::Px(position.x), Val::Px(position.y));
+    if style.left != left || style.top != top || style.width != size {
+        style.left = left;
+        style.top = top;
+        style.width = size;
+        style.height = size;
+    }
+}
+
+/// Hand the cursor visibility back to the window if the software cursor
+/// manages it, before the plugin is unloaded
+pub fn restore_system_cursor(world: &mut World) {
+    let Some(mut game_cursor) = world.get_resource_mut::<GameCursor>() else {
+        return;
+    };
+    if !std::mem::take(&mut game_cursor.managed) {
+        return;
+    }
+    let visible = game_cursor.visible;
+    let mut windows = world.query_filtered::<&mut Window, With<PrimaryWindow>>();
+    if let Ok(mut window) = windows.get_single_mut(world) {
+        window.cursor.visible = visible;
+    }
+}
@@ -0,0 +1,353 @@
+//! Fonts of the settings menu
+//!
+//! Every settings text carries a [`FontRole`] and gets its font from
+//! [`SettingsFonts`], which is built from a font manifest asset, by default
+//! [`DEFAULT_FONT_MANIFEST_PATH`]:
+//!
+//! ```ron
+//! (
+//!     regular: "fonts/FiraSans-Regular.ttf",
+//!     bold: "fonts/FiraSans-Bold.ttf",
+//!     mono: "fonts/FiraMono-Medium.ttf",
+//!     // Scripts the fonts above cover
+//!     scripts: [Latin, Greek, Cyrillic],
+//!     // Tried in order for text in any other script
+//!     fallbacks: [
+//!         (scripts: [Han, Kana], regular: "fonts/NotoSansCJKjp-Regular.otf", bold: Some("fonts/NotoSansCJKjp-Bold.otf")),
+//!     ],
+//! )
+//! ```
+//!
+//! A text written in a script the main fonts don't cover uses the first
+//! fallback listing that script, and a font that fails to load is skipped in
+//! favour of the next one, ending with Bevy's built-in font. Fallbacks are
+//! loaded the first time a text needs them, so games only ship the fonts for
+//! the scripts they show. Without a manifest in the game's assets the one
+//! embedded in the plugin is used. Changes to the manifest are picked up while the game runs.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use bevy::asset::AssetLoadFailedEvent;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::display::{UiAccessibility, UiAccessibilitySet};
+use crate::localization::LocalizationSet;
+use crate::ron_asset::{RonAsset, RonAssetPath, RonAssetPlugin, RonAssetSet};
+
+/// Font manifest the game ships in its assets
+pub const DEFAULT_FONT_MANIFEST_PATH: &str = "fonts/settings.fonts.ron";
+
+/// Manifest used until the game's manifest has loaded, or when it has none
+const EMBEDDED_MANIFEST: &str = include_str!("../assets/fonts/settings.fonts.ron");
+
+/// Which of the configured fonts a text uses
+#[derive(Component, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum FontRole {
+    #[default]
+    Regular,
+    /// Titles, headings and buttons
+    Bold,
+    Mono,
+}
+
+/// Writing systems the font manifest can assign fonts to
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Hangul,
+    /// Hiragana and katakana
+    Kana,
+    /// Chinese characters, also used by Japanese
+    Han,
+}
+
+impl Script {
+    /// Script of `c`, `None` for digits, punctuation and other shared characters
+    pub fn of(c: char) -> Option<Script> {
+        match c {
+            'A'..='Z' | 'a'..='z' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => {
+                // × and ÷ sit in the middle of Latin-1
+                (c != '\u{00D7}' && c != '\u{00F7}').then_some(Script::Latin)
+            }
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Script::Greek),
+            '\u{0400}'..='\u{052F}' => Some(Script::Cyrillic),
+            '\u{0590}'..='\u{05FF}' => Some(Script::Hebrew),
+            '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' => Some(Script::Arabic),
+            '\u{0900}'..='\u{097F}' => Some(Script::Devanagari),
+            '\u{0E00}'..='\u{0E7F}' => Some(Script::Thai),
+            '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => Some(Script::Hangul),
+            '\u{3040}'..='\u{30FF}' => Some(Script::Kana),
+            '\u{3000}'..='\u{303F}' | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+                Some(Script::Han)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Font files for the settings menu, see the [module docs](self)
+///
+/// The resource holds the manifest in use; the asset is what manifest files load into.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, PartialEq, Debug)]
+pub struct FontManifest {
+    pub regular: String,
+    pub bold: String,
+    pub mono: String,
+    pub scripts: Vec<Script>,
+    #[serde(default)]
+    pub fallbacks: Vec<FontFallback>,
+}
+
+/// Fonts for scripts the main fonts lack; missing roles use `regular`
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct FontFallback {
+    pub scripts: Vec<Script>,
+    pub regular: String,
+    #[serde(default)]
+    pub bold: Option<String>,
+    #[serde(default)]
+    pub mono: Option<String>,
+}
+
+impl Default for FontManifest {
+    fn default() -> Self {
+        ron::from_str(EMBEDDED_MANIFEST).expect("embedded font manifest is valid")
+    }
+}
+
+impl RonAsset for FontManifest {
+    const EXTENSION: &'static str = "fonts.ron";
+    const DEFAULT_PATH: &'static str = DEFAULT_FONT_MANIFEST_PATH;
+    const DESCRIPTION: &'static str = "font manifest";
+}
+
+/// Font manifest the menu loads, [`DEFAULT_FONT_MANIFEST_PATH`] unless the host sets another
+pub type FontManifestPath = RonAssetPath<FontManifest>;
+
+/// One font per [`FontRole`]
+#[derive(Clone, Default, Debug)]
+pub struct FontSet {
+    pub regular: Handle<Font>,
+    pub bold: Handle<Font>,
+    pub mono: Handle<Font>,
+}
+
+impl FontSet {
+    pub fn get(&self, role: FontRole) -> &Handle<Font> {
+        match role {
+            FontRole::Regular => &self.regular,
+            FontRole::Bold => &self.bold,
+            FontRole::Mono => &self.mono,
+        }
+    }
+}
+
+/// Fallback fonts from the manifest, loaded the first time a text needs them
+#[derive(Clone, Debug)]
+struct FallbackFonts {
+    files: FontFallback,
+    fonts: OnceLock<FontSet>,
+}
+
+impl FallbackFonts {
+    fn fonts(&self, asset_server: &AssetServer) -> &FontSet {
+        self.fonts.get_or_init(|| {
+            let regular: Handle<Font> = asset_server.load(&self.files.regular);
+            let load_or_regular = |path: &Option<String>| {
+                path.as_ref().map_or_else(|| regular.clone(), |path| asset_server.load(path))
+            };
+            FontSet {
+                bold: load_or_regular(&self.files.bold),
+                mono: load_or_regular(&self.files.mono),
+                regular,
+            }
+        })
+    }
+}
+
+/// Fonts of the settings menu, loaded from the [`FontManifest`]
+///
+/// The main fonts load with the manifest and each fallback the first time a
+/// text in one of its scripts asks for it. Until an asset server is available
+/// every role uses Bevy's built-in font.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct SettingsFonts {
+    pub main: FontSet,
+    /// Scripts `main` covers
+    pub scripts: Vec<Script>,
+    fallbacks: Vec<FallbackFonts>,
+    asset_server: Option<AssetServer>,
+    /// Fonts that failed to load, skipped by [`SettingsFonts::font_for`]
+    failed: HashSet<AssetId<Font>>,
+}
+
+impl SettingsFonts {
+    pub fn load(manifest: &FontManifest, asset_server: &AssetServer) -> Self {
+        let regular: Handle<Font> = asset_server.load(&manifest.regular);
+        Self {
+            main: FontSet {
+                regular: regular.clone(),
+                bold: asset_server.load(&manifest.bold),
+                mono: asset_server.load(&manifest.mono),
+            },
+            scripts: manifest.scripts.clone(),
+            fallbacks: manifest
+                .fallbacks
+                .iter()
+                .map(|files| FallbackFonts { files: files.clone(), fonts: OnceLock::new() })
+                .collect(),
+            asset_server: Some(asset_server.clone()),
+            failed: HashSet::new(),
+        }
+    }
+
+    /// Font for `text` in `role`, going down the fallback chain for scripts the
+    /// main fonts don't cover and past fonts that failed to load
+    ///
+    /// A fallback is loaded when it is first reached, so the font returned for
+    /// it shows once the file has loaded.
+    pub fn font_for(&self, role: FontRole, text: &str) -> Handle<Font> {
+        let uncovered = text
+            .chars()
+            .filter_map(Script::of)
+            .find(|script| !self.scripts.contains(script));
+        let asset_server = self.asset_server.as_ref();
+        let fallbacks = self
+            .fallbacks
+            .iter()
+            .filter(|fallback| uncovered.is_some_and(|script| fallback.files.scripts.contains(&script)))
+            .filter_map(|fallback| Some(fallback.fonts(asset_server?)))
+            .flat_map(|fonts| [fonts.get(role), &fonts.regular]);
+
+        fallbacks
+            .chain([self.main.get(role), &self.main.regular])
+            .find(|font| !self.failed.contains(&font.id()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Whether `font` has failed to load
+    pub fn has_failed(&self, font: &Handle<Font>) -> bool {
+        self.failed.contains(&font.id())
+    }
+}
+
+pub struct SettingsFontsPlugin;
+
+impl Plugin for SettingsFontsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<FontManifest>::default())
+            .init_resource::<SettingsFonts>()
+            .add_systems(
+                PostUpdate,
+                apply_settings_fonts
+                    .after(LocalizationSet)
+                    .after(UiAccessibilitySet)
+                    .before(bevy::ui::UiSystem::Layout),
+            );
+
+        // Headless apps without assets keep the built-in font
+        if app.world().contains_resource::<AssetServer>() {
+            app.add_systems(
+                PreUpdate,
+                (rebuild_settings_fonts, track_failed_fonts).chain().after(RonAssetSet),
+            );
+        }
+    }
+}
+
+/// Rebuild the fonts from the manifest in use whenever it changes
+fn rebuild_settings_fonts(
+    manifest: Res<FontManifest>,
+    asset_server: Res<AssetServer>,
+    font_assets: Option<Res<Assets<Font>>>,
+    mut fonts: ResMut<SettingsFonts>,
+) {
+    if !manifest.is_changed() || font_assets.is_none() {
+        return;
+    }
+    // Paths that failed before keep their handles and won't report again
+    let failed = std::mem::take(&mut fonts.failed);
+    *fonts = SettingsFonts::load(&manifest, &asset_server);
+    fonts.failed = failed;
+}
+
+fn track_failed_fonts(mut failures: EventReader<AssetLoadFailedEvent<Font>>, mut fonts: ResMut<SettingsFonts>) {
+    for failure in failures.read() {
+        warn!("Font {:?} didn't load, using the next font in the chain: {}", failure.path, failure.error);
+        fonts.failed.insert(failure.id);
+    }
+}
+
+/// Give each settings text the font for its role and script
+fn apply_settings_fonts(
+    fonts: Res<SettingsFonts>,
+    options: Res<UiAccessibility>,
+    mut texts: Query<(Ref<FontRole>, &mut Text)>,
+) {
+    let refresh = fonts.is_changed() || options.is_changed();
+    for (role, mut text) in &mut texts {
+        if !refresh && !role.is_changed() && !text.is_changed() {
+            continue;
+        }
+        let chosen: Vec<Handle<Font>> = text
+            .sections
+            .iter()
+            .map(|section| {
+                let only_latin = section.value.chars().filter_map(Script::of).all(|script| script == Script::Latin);
+                match &options.dyslexia_font {
+                    Some(font) if only_latin && !fonts.has_failed(font) => font.clone(),
+                    _ => fonts.font_for(*role, &section.value),
+                }
+            })
+            .collect();
+        if text.sections.iter().zip(&chosen).any(|(section, font)| section.style.font != *font) {
+            for (section, font) in text.sections.iter_mut().zip(chosen) {
+                section.style.font = font;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_map_to_their_script() {
+        assert_eq!(Script::of('a'), Some(Script::Latin));
+        assert_eq!(Script::of('é'), Some(Script::Latin));
+        assert_eq!(Script::of('Ω'), Some(Script::Greek));
+        assert_eq!(Script::of('ж'), Some(Script::Cyrillic));
+        assert_eq!(Script::of('ש'), Some(Script::Hebrew));
+        assert_eq!(Script::of('ع'), Some(Script::Arabic));
+        assert_eq!(Script::of('क'), Some(Script::Devanagari));
+        assert_eq!(Script::of('ก'), Some(Script::Thai));
+        assert_eq!(Script::of('한'), Some(Script::Hangul));
+        assert_eq!(Script::of('か'), Some(Script::Kana));
+        assert_eq!(Script::of('カ'), Some(Script::Kana));
+        assert_eq!(Script::of('漢'), Some(Script::Han));
+    }
+
+    #[test]
+    fn shared_characters_have_no_script() {
+        for c in ['1', ' ', '.', '%', '×', '÷', '→'] {
+            assert_eq!(Script::of(c), None, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn embedded_manifest_is_valid() {
+        let manifest = FontManifest::default();
+        assert!(manifest.scripts.contains(&Script::Latin));
+        assert!(manifest.fallbacks.iter().all(|fallback| !fallback.scripts.is_empty()));
+    }
+}
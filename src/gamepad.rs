@@ -0,0 +1,209 @@
+//! Gamepad stick, trigger and rumble processing
+//!
+//! Applies the player's [`ControllerSettings`](crate::settings::ControllerSettings)
+//! to the first connected gamepad and publishes the result in [`GamepadInput`].
+//! Bevy's own dead zones are disabled for the stick axes of connected
+//! gamepads so the settings here are the only filtering applied to them; the
+//! host's default axis settings, and axis settings it sets itself, are left
+//! alone.
+
+use std::time::Duration;
+
+use bevy::input::gamepad::{
+    AxisSettings, GamepadRumbleIntensity, GamepadRumbleRequest, GamepadSettings,
+};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use crate::settings::{ResponseCurve, Settings, StickSettings};
+
+/// Raw and processed gamepad state for the current frame
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct GamepadInput {
+    /// Gamepad the values were read from
+    pub gamepad: Option<Gamepad>,
+    pub raw_left_stick: Vec2,
+    pub raw_right_stick: Vec2,
+    /// Left stick after dead zones and the response curve
+    pub left_stick: Vec2,
+    /// Right stick after dead zones and the response curve
+    pub right_stick: Vec2,
+    pub left_trigger: f32,
+    pub right_trigger: f32,
+    pub left_trigger_pressed: bool,
+    pub right_trigger_pressed: bool,
+    /// Virtual cursor movement for this frame in logical pixels, driven by the left stick
+    pub cursor_delta: Vec2,
+}
+
+/// [`GamepadInput`] for the settings menu itself, which keeps updating while
+/// the overlay makes [`GamepadInput`] read as idle for game systems
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub(crate) struct MenuGamepadInput(pub GamepadInput);
+
+/// Rumble request from game code, scaled by the player's rumble strength
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ControllerRumble {
+    /// Target gamepad, `None` rumbles the active gamepad
+    pub gamepad: Option<Gamepad>,
+    pub strong_motor: f32,
+    pub weak_motor: f32,
+    pub duration: Duration,
+}
+
+/// Set containing the gamepad processing, order gameplay systems after it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GamepadInputSet;
+
+pub struct GamepadInputPlugin;
+
+impl Plugin for GamepadInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadInput>()
+            .init_resource::<MenuGamepadInput>()
+            .add_event::<ControllerRumble>()
+            .configure_sets(PreUpdate, GamepadInputSet.after(InputSystem))
+            .add_systems(PreUpdate, (disable_stick_dead_zones, process_gamepad_input).chain().in_set(GamepadInputSet))
+            .add_systems(Update, forward_rumble_requests);
+    }
+}
+
+/// Stick axes [`process_gamepad_input`] reads
+const STICK_AXES: [GamepadAxisType; 4] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+];
+
+/// Make Bevy report unfiltered stick values for newly connected gamepads so
+/// our dead zones see the full range
+fn disable_stick_dead_zones(gamepads: Res<Gamepads>, gamepad_settings: Option<ResMut<GamepadSettings>>) {
+    let Some(mut gamepad_settings) = gamepad_settings else {
+        return;
+    };
+    if !gamepads.is_changed() {
+        return;
+    }
+
+    let axis_settings = match AxisSettings::new(-1.0, 0.0, 0.0, 1.0, 0.0) {
+        Ok(axis_settings) => axis_settings,
+        Err(err) => {
+            warn!("Failed to disable gamepad dead zones: {:?}", err);
+            return;
+        }
+    };
+    for gamepad in gamepads.iter() {
+        for axis_type in STICK_AXES {
+            gamepad_settings
+                .axis_settings
+                .entry(GamepadAxis::new(gamepad, axis_type))
+                .or_insert_with(|| axis_settings.clone());
+        }
+    }
+}
+
+fn process_gamepad_input(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    mut input: ResMut<GamepadInput>,
+    mut menu_input: ResMut<MenuGamepadInput>,
+) {
+    let Some(gamepad) = gamepads.iter().next() else {
+        *input = GamepadInput::default();
+        menu_input.0 = *input;
+        return;
+    };
+
+    let controller = &settings.controls.controller;
+    let stick = |x, y| {
+        Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+            axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+        )
+    };
+    let trigger = |button| {
+        button_axes
+            .get(GamepadButton::new(gamepad, button))
+            .unwrap_or(0.0)
+    };
+
+    let raw_left_stick = stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY);
+    let raw_right_stick = stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY);
+    let left_stick = process_stick(raw_left_stick, &controller.left_stick);
+    let right_stick = process_stick(raw_right_stick, &controller.right_stick);
+    let left_trigger = trigger(GamepadButtonType::LeftTrigger2);
+    let right_trigger = trigger(GamepadButtonType::RightTrigger2);
+
+    // Screen space grows downwards while stick up is positive
+    let cursor_delta = Vec2::new(left_stick.x, -left_stick.y)
+        * controller.cursor_speed
+        * time.delta_seconds();
+
+    *input = GamepadInput {
+        gamepad: Some(gamepad),
+        raw_left_stick,
+        raw_right_stick,
+        left_stick,
+        right_stick,
+        left_trigger,
+        right_trigger,
+        left_trigger_pressed: left_trigger >= controller.left_trigger_threshold,
+        right_trigger_pressed: right_trigger >= controller.right_trigger_threshold,
+        cursor_delta,
+    };
+    menu_input.0 = *input;
+}
+
+/// Scale game rumble requests by the player's rumble strength and hand them to Bevy
+fn forward_rumble_requests(
+    settings: Res<Settings>,
+    input: Res<GamepadInput>,
+    mut rumble_events: EventReader<ControllerRumble>,
+    mut requests: EventWriter<GamepadRumbleRequest>,
+) {
+    let strength = settings.controls.controller.rumble_strength;
+    for rumble in rumble_events.read() {
+        let Some(gamepad) = rumble.gamepad.or(input.gamepad) else {
+            continue;
+        };
+        if strength <= 0.0 {
+            continue;
+        }
+
+        requests.send(GamepadRumbleRequest::Add {
+            gamepad,
+            duration: rumble.duration,
+            intensity: GamepadRumbleIntensity {
+                strong_motor: (rumble.strong_motor * strength).clamp(0.0, 1.0),
+                weak_motor: (rumble.weak_motor * strength).clamp(0.0, 1.0),
+            },
+        });
+    }
+}
+
+/// Apply radial inner/outer dead zones and the response curve to a stick reading
+pub fn process_stick(raw: Vec2, stick: &StickSettings) -> Vec2 {
+    let magnitude = raw.length();
+    if magnitude <= stick.inner_dead_zone {
+        return Vec2::ZERO;
+    }
+
+    let live_range = (stick.outer_dead_zone - stick.inner_dead_zone).max(f32::EPSILON);
+    let normalized = ((magnitude - stick.inner_dead_zone) / live_range).clamp(0.0, 1.0);
+    let curved = apply_response_curve(normalized, stick);
+
+    raw / magnitude * curved
+}
+
+/// Map a `0..=1` stick deflection through the stick's response curve
+pub fn apply_response_curve(value: f32, stick: &StickSettings) -> f32 {
+    match stick.response_curve {
+        ResponseCurve::Linear => value,
+        ResponseCurve::Quadratic => value * value,
+        ResponseCurve::Custom => value.powf(stick.custom_exponent.max(f32::EPSILON)),
+    }
+}
@@ -0,0 +1,97 @@
+//! Gameplay options for the game to follow
+//!
+//! [`GameplayOptions`] mirrors the game speed, autosave, alert, health bar and
+//! waypoint settings in the form game systems use:
+//!
+//! ```ignore
+//! fn alert_idle_worker(options: Res<GameplayOptions>, mut alerts: EventWriter<Alert>) {
+//!     if options.alert_enabled(AlertKind::IdleWorkers) {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! Game speed is left to the game, which should only apply it to single
+//! player matches, e.g. with `Time<Virtual>::set_relative_speed`.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::settings::{AlertSettings, GameplaySettings, HealthBarVisibility, Settings, WaypointDisplay};
+
+/// Alerts the player can turn off
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AlertKind {
+    UnderAttack,
+    IdleWorkers,
+    ResearchComplete,
+}
+
+/// Current gameplay options, kept in sync with [`GameplaySettings`]
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct GameplayOptions {
+    /// Relative speed of single player matches
+    pub game_speed: f32,
+    /// Time between autosaves, `None` to not autosave
+    pub autosave_interval: Option<Duration>,
+    /// Autosaves to keep
+    pub autosave_slots: u32,
+    pub health_bars: HealthBarVisibility,
+    pub waypoints: WaypointDisplay,
+    pub alerts: AlertSettings,
+}
+
+impl GameplayOptions {
+    pub fn from_settings(gameplay: &GameplaySettings) -> Self {
+        Self {
+            game_speed: gameplay.game_speed,
+            autosave_interval: gameplay.autosave.interval_minutes.map(|minutes| Duration::from_secs(u64::from(minutes) * 60)),
+            autosave_slots: gameplay.autosave.slots,
+            health_bars: gameplay.health_bars,
+            waypoints: gameplay.waypoints,
+            alerts: gameplay.alerts.clone(),
+        }
+    }
+
+    pub fn alert_enabled(&self, alert: AlertKind) -> bool {
+        match alert {
+            AlertKind::UnderAttack => self.alerts.under_attack,
+            AlertKind::IdleWorkers => self.alerts.idle_workers,
+            AlertKind::ResearchComplete => self.alerts.research_complete,
+        }
+    }
+
+    /// Whether a unit shows its health bar
+    pub fn shows_health_bar(&self, selected: bool, damaged: bool) -> bool {
+        match self.health_bars {
+            HealthBarVisibility::Always => true,
+            HealthBarVisibility::Selected => selected,
+            HealthBarVisibility::Damaged => selected || damaged,
+        }
+    }
+}
+
+impl Default for GameplayOptions {
+    fn default() -> Self {
+        Self::from_settings(&GameplaySettings::default())
+    }
+}
+
+pub struct GameplayOptionsPlugin;
+
+impl Plugin for GameplayOptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameplayOptions>()
+            .register_type::<HealthBarVisibility>()
+            .register_type::<WaypointDisplay>()
+            .add_systems(Update, sync_gameplay_options);
+    }
+}
+
+fn sync_gameplay_options(settings: Res<Settings>, mut options: ResMut<GameplayOptions>) {
+    if !settings.is_changed() {
+        return;
+    }
+    options.set_if_neq(GameplayOptions::from_settings(&settings.gameplay));
+}
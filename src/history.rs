@@ -0,0 +1,483 @@
+//! Undo/redo for settings edits
+//!
+//! Every commit that changes settings (see [`crate::changes`]) becomes one
+//! history entry. Repeated edits of the same field in quick succession, such
+//! as dragging a slider or holding a stepper, are coalesced into a single
+//! entry. While the settings menu is shown Ctrl+Z undoes, Ctrl+Y or
+//! Ctrl+Shift+Z redoes, and [`SettingsHistoryBindings`] holds the gamepad
+//! buttons for both. A footer hint shows what the next undo would do.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::reflect::{ReflectMut, ReflectRef};
+
+use crate::changes::{clone_concrete, SettingChanged, SettingsCommitSet};
+use crate::fonts::FontRole;
+use crate::localization::{keys, Localization, LocalizedText};
+use crate::overlay::{settings_menu_shown, SettingsOverlayToggled};
+use crate::registry;
+use crate::theme::ThemeElement;
+use crate::widgets::field_label;
+
+/// Edits of the same field closer together than this are merged into one entry
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(750);
+
+/// Oldest entries are dropped beyond this
+pub const MAX_HISTORY: usize = 100;
+
+/// One field changed by a history entry
+pub struct SettingEdit {
+    pub path: String,
+    pub old: Box<dyn Reflect>,
+    pub new: Box<dyn Reflect>,
+}
+
+/// Edits committed together, undone and redone as a unit
+pub struct HistoryEntry {
+    pub edits: Vec<SettingEdit>,
+    /// Real time of the latest edit merged into this entry
+    last_edit: Duration,
+}
+
+impl HistoryEntry {
+    /// "Music volume 0.6 → 0.3", or "Move up and 12 more" for larger entries
+    pub fn describe(&self, localization: &Localization) -> String {
+        match self.edits.as_slice() {
+            [edit] => localization.text(
+                &LocalizedText::new(keys::HISTORY_EDIT)
+                    .with_arg("setting", path_label(&edit.path))
+                    .with_arg("from", short_value(localization, &edit.path, edit.old.as_ref()))
+                    .with_arg("to", short_value(localization, &edit.path, edit.new.as_ref())),
+            ),
+            [first, rest @ ..] => localization.text(
+                &LocalizedText::new(keys::HISTORY_EDITS)
+                    .with_arg("setting", path_label(&first.path))
+                    .with_arg("more", rest.len()),
+            ),
+            [] => String::new(),
+        }
+    }
+
+    /// Whether any setting the entry edits is locked
+    fn is_locked(&self, world: &World) -> bool {
+        self.edits.iter().any(|edit| registry::is_locked(world, &edit.path))
+    }
+}
+
+/// Undo and redo stacks for the current session
+///
+/// The history is cleared whenever the settings overlay opens.
+#[derive(Resource, Default)]
+pub struct SettingsHistory {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    /// Paths written by undo/redo whose change events must not be recorded
+    applying: Vec<String>,
+}
+
+impl SettingsHistory {
+    /// Entry the next undo reverts
+    pub fn next_undo(&self) -> Option<&HistoryEntry> {
+        self.undo.last()
+    }
+
+    /// Entry the next redo reapplies
+    pub fn next_redo(&self) -> Option<&HistoryEntry> {
+        self.redo.last()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn record(&mut self, mut edits: Vec<SettingEdit>, now: Duration) {
+        self.redo.clear();
+
+        if let ([edit], Some(top)) = (edits.as_mut_slice(), self.undo.last_mut()) {
+            let same_field = matches!(top.edits.as_slice(), [previous] if previous.path == edit.path);
+            if same_field && now.saturating_sub(top.last_edit) <= COALESCE_WINDOW {
+                std::mem::swap(&mut top.edits[0].new, &mut edit.new);
+                top.last_edit = now;
+                // Dragged back to where it started
+                if top.edits[0].old.reflect_partial_eq(top.edits[0].new.as_ref()) == Some(true) {
+                    self.undo.pop();
+                }
+                return;
+            }
+        }
+
+        self.undo.push(HistoryEntry { edits, last_edit: now });
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+    }
+}
+
+/// Gamepad buttons for undo and redo, `None` disables the binding
+#[derive(Resource, Clone, Debug)]
+pub struct SettingsHistoryBindings {
+    pub gamepad_undo: Option<GamepadButtonType>,
+    pub gamepad_redo: Option<GamepadButtonType>,
+}
+
+impl Default for SettingsHistoryBindings {
+    fn default() -> Self {
+        Self {
+            gamepad_undo: Some(GamepadButtonType::West),
+            gamepad_redo: Some(GamepadButtonType::North),
+        }
+    }
+}
+
+/// Footer text describing the next undo and redo
+#[derive(Component)]
+pub struct SettingsHistoryHint;
+
+pub struct SettingsHistoryPlugin;
+
+impl Plugin for SettingsHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsHistory>()
+            .init_resource::<SettingsHistoryBindings>()
+            // Also sent without the overlay plugin when the host drives the panel
+            .add_event::<SettingsOverlayToggled>()
+            .add_systems(PostUpdate, record_setting_changes.after(SettingsCommitSet))
+            .add_systems(Update, (
+                clear_history_on_open,
+                handle_history_shortcuts.run_if(settings_menu_shown),
+                update_history_hint,
+            ).chain());
+    }
+}
+
+fn record_setting_changes(
+    time: Res<Time<Real>>,
+    registry: Res<AppTypeRegistry>,
+    mut history: ResMut<SettingsHistory>,
+    mut changes: EventReader<SettingChanged>,
+) {
+    let registry = registry.read();
+    let mut edits = Vec::new();
+    for change in changes.read() {
+        if let Some(index) = history.applying.iter().position(|path| *path == change.path) {
+            history.applying.swap_remove(index);
+            continue;
+        }
+        edits.push(SettingEdit {
+            path: change.path.clone(),
+            old: clone_concrete(&registry, change.old.as_ref()),
+            new: clone_concrete(&registry, change.new.as_ref()),
+        });
+    }
+
+    if !edits.is_empty() {
+        history.record(edits, time.elapsed());
+    }
+    // Undo/redo commit in the same frame, so anything left didn't change a value
+    if !history.applying.is_empty() {
+        history.applying.clear();
+    }
+}
+
+fn clear_history_on_open(
+    mut history: ResMut<SettingsHistory>,
+    mut toggled: EventReader<SettingsOverlayToggled>,
+) {
+    if toggled.read().any(|toggled| toggled.open) {
+        history.clear();
+    }
+}
+
+fn handle_history_shortcuts(world: &mut World) {
+    let keys = world.resource::<ButtonInput<KeyCode>>();
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let mut undo = ctrl && !shift && keys.just_pressed(KeyCode::KeyZ);
+    let mut redo = ctrl && (keys.just_pressed(KeyCode::KeyY) || (shift && keys.just_pressed(KeyCode::KeyZ)));
+
+    let bindings = world.resource::<SettingsHistoryBindings>().clone();
+    let gamepad_buttons = world.resource::<ButtonInput<GamepadButton>>();
+    for button in gamepad_buttons.get_just_pressed() {
+        undo |= Some(button.button_type) == bindings.gamepad_undo;
+        redo |= Some(button.button_type) == bindings.gamepad_redo;
+    }
+
+    if undo {
+        undo_setting_edit(world);
+    } else if redo {
+        redo_setting_edit(world);
+    }
+}
+
+/// Revert the most recent history entry, returning whether there was one
+///
+/// An entry editing a locked setting stays in place until it is unlocked.
+pub fn undo_setting_edit(world: &mut World) -> bool {
+    if world.resource::<SettingsHistory>().next_undo().is_none_or(|entry| entry.is_locked(world)) {
+        return false;
+    }
+    let Some(entry) = world.resource_mut::<SettingsHistory>().undo.pop() else {
+        return false;
+    };
+    for edit in entry.edits.iter().rev() {
+        apply_value(world, &edit.path, edit.old.as_ref());
+    }
+    world.resource_mut::<SettingsHistory>().redo.push(entry);
+    true
+}
+
+/// Reapply the most recently undone entry, returning whether there was one
+///
+/// An entry editing a locked setting stays in place until it is unlocked.
+pub fn redo_setting_edit(world: &mut World) -> bool {
+    if world.resource::<SettingsHistory>().next_redo().is_none_or(|entry| entry.is_locked(world)) {
+        return false;
+    }
+    let Some(entry) = world.resource_mut::<SettingsHistory>().redo.pop() else {
+        return false;
+    };
+    for edit in &entry.edits {
+        apply_value(world, &edit.path, edit.new.as_ref());
+    }
+    world.resource_mut::<SettingsHistory>().undo.push(entry);
+    true
+}
+
+fn apply_value(world: &mut World, path: &str, value: &dyn Reflect) {
+    let found = registry::edit_setting(world, path, |field| {
+        // Applying a list only overwrites as many items as it has, so drop the extra ones first
+        if let (ReflectMut::List(list), ReflectRef::List(new)) = (field.reflect_mut(), value.reflect_ref()) {
+            while list.len() > new.len() {
+                list.pop();
+            }
+        }
+        if let Err(err) = field.try_apply(value) {
+            warn!("Failed to restore setting {:?}: {}", path, err);
+        }
+    });
+    if found {
+        world.resource_mut::<SettingsHistory>().applying.push(path.to_string());
+    }
+}
+
+fn update_history_hint(
+    history: Res<SettingsHistory>,
+    localization: Res<Localization>,
+    mut hints: Query<(Ref<SettingsHistoryHint>, &mut Text)>,
+) {
+    for (hint, mut text) in &mut hints {
+        if !history.is_changed() && !localization.is_changed() && !hint.is_added() {
+            continue;
+        }
+
+        let mut parts = Vec::new();
+        if let Some(entry) = history.next_undo() {
+            parts.push(localization.text(
+                &LocalizedText::new(keys::HISTORY_UNDO).with_arg("change", entry.describe(&localization)),
+            ));
+        }
+        if let Some(entry) = history.next_redo() {
+            parts.push(localization.text(
+                &LocalizedText::new(keys::HISTORY_REDO).with_arg("change", entry.describe(&localization)),
+            ));
+        }
+        text.sections[0].value = parts.join("   ");
+    }
+}
+
+/// Spawn the footer showing the next undo and redo
+pub fn spawn_history_hint(parent: &mut ChildBuilder) {
+    parent.spawn((
+        TextBundle::from_section("", TextStyle::default()),
+        ThemeElement::Hint,
+        SettingsHistoryHint,
+        FontRole::Regular,
+    ));
+}
+
+/// Label of the setting at `path`, e.g. "Music volume"
+fn path_label(path: &str) -> LocalizedText {
+    LocalizedText::setting(path, field_label(path.rsplit('.').next().unwrap_or(path)))
+}
+
+/// The value as shown in the menu without trailing zeros, so 0.60 reads as 0.6
+fn short_value(localization: &Localization, path: &str, value: &dyn Reflect) -> String {
+    let formatted = localization.format_value(path, value, None);
+    if value.downcast_ref::<f32>().is_some() && formatted.contains(['.', ',']) {
+        formatted.trim_end_matches('0').trim_end_matches(['.', ',']).to_string()
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changes::SettingsChangesPlugin;
+    use crate::localization::Language;
+    use crate::settings::{Difficulty, Settings};
+    use crate::widgets::LockedSettings;
+
+    fn edit(path: &str, old: f32, new: f32) -> SettingEdit {
+        SettingEdit {
+            path: path.to_string(),
+            old: Box::new(old),
+            new: Box::new(new),
+        }
+    }
+
+    fn values(entry: &HistoryEntry) -> Vec<(&str, f32, f32)> {
+        entry
+            .edits
+            .iter()
+            .map(|edit| {
+                let value = |value: &dyn Reflect| *value.downcast_ref::<f32>().unwrap();
+                (edit.path.as_str(), value(edit.old.as_ref()), value(edit.new.as_ref()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn coalesces_quick_edits_of_the_same_field() {
+        let mut history = SettingsHistory::default();
+        history.record(vec![edit("audio.music_volume", 0.5, 0.6)], Duration::ZERO);
+        history.record(vec![edit("audio.music_volume", 0.6, 0.7)], COALESCE_WINDOW);
+        history.record(vec![edit("audio.music_volume", 0.7, 0.8)], COALESCE_WINDOW * 2);
+
+        assert_eq!(history.undo.len(), 1);
+        assert_eq!(values(history.next_undo().unwrap()), [("audio.music_volume", 0.5, 0.8)]);
+    }
+
+    #[test]
+    fn keeps_slow_or_different_edits_apart() {
+        let mut history = SettingsHistory::default();
+        history.record(vec![edit("audio.music_volume", 0.5, 0.6)], Duration::ZERO);
+        history.record(vec![edit("audio.music_volume", 0.6, 0.7)], COALESCE_WINDOW * 2);
+        history.record(vec![edit("audio.sfx_volume", 0.5, 0.4)], COALESCE_WINDOW * 2);
+
+        assert_eq!(history.undo.len(), 3);
+    }
+
+    #[test]
+    fn does_not_coalesce_entries_with_several_edits() {
+        let mut history = SettingsHistory::default();
+        let both = || vec![edit("audio.music_volume", 0.5, 0.6), edit("audio.sfx_volume", 0.5, 0.6)];
+        history.record(both(), Duration::ZERO);
+        history.record(vec![edit("audio.music_volume", 0.6, 0.7)], Duration::ZERO);
+        history.record(both(), Duration::ZERO);
+
+        assert_eq!(history.undo.len(), 3);
+    }
+
+    #[test]
+    fn drops_an_entry_dragged_back_to_its_start() {
+        let mut history = SettingsHistory::default();
+        history.record(vec![edit("audio.music_volume", 0.5, 0.6)], Duration::ZERO);
+        history.record(vec![edit("audio.music_volume", 0.6, 0.5)], COALESCE_WINDOW);
+
+        assert!(history.next_undo().is_none());
+    }
+
+    #[test]
+    fn new_edit_clears_redo_and_old_entries_fall_off() {
+        let mut history = SettingsHistory::default();
+        history.redo.push(HistoryEntry {
+            edits: vec![edit("audio.music_volume", 0.5, 0.6)],
+            last_edit: Duration::ZERO,
+        });
+        for index in 0..=MAX_HISTORY {
+            history.record(vec![edit("audio.music_volume", 0.0, index as f32)], COALESCE_WINDOW * 2 * index as u32);
+        }
+
+        assert!(history.next_redo().is_none());
+        assert_eq!(history.undo.len(), MAX_HISTORY);
+        assert_eq!(values(&history.undo[0])[0].2, 1.0);
+    }
+
+    fn world_with_volume(volume: f32) -> World {
+        let mut world = World::new();
+        let mut settings = Settings::default();
+        settings.audio.music_volume = volume;
+        world.insert_resource(settings);
+        world.init_resource::<SettingsHistory>();
+        world
+    }
+
+    fn music_volume(world: &World) -> f32 {
+        world.resource::<Settings>().audio.music_volume
+    }
+
+    #[test]
+    fn undo_and_redo_move_entries_between_stacks() {
+        let mut world = world_with_volume(0.8);
+        world
+            .resource_mut::<SettingsHistory>()
+            .record(vec![edit("audio.music_volume", 0.5, 0.8)], Duration::ZERO);
+
+        assert!(undo_setting_edit(&mut world));
+        assert_eq!(music_volume(&world), 0.5);
+        assert!(!undo_setting_edit(&mut world));
+
+        assert!(redo_setting_edit(&mut world));
+        assert_eq!(music_volume(&world), 0.8);
+        assert!(!redo_setting_edit(&mut world));
+
+        let history = world.resource::<SettingsHistory>();
+        assert_eq!(history.undo.len(), 1);
+        assert_eq!(history.applying, ["audio.music_volume", "audio.music_volume"]);
+    }
+
+    #[test]
+    fn undo_leaves_locked_settings_alone() {
+        let mut world = world_with_volume(0.8);
+        world
+            .resource_mut::<SettingsHistory>()
+            .record(vec![edit("audio.music_volume", 0.5, 0.8)], Duration::ZERO);
+        let mut locks = LockedSettings::default();
+        locks.lock("audio");
+        world.insert_resource(locks);
+
+        assert!(!undo_setting_edit(&mut world));
+        assert_eq!(music_volume(&world), 0.8);
+        assert!(world.resource::<SettingsHistory>().next_undo().is_some());
+
+        world.resource_mut::<LockedSettings>().unlock("audio");
+        assert!(undo_setting_edit(&mut world));
+        assert_eq!(music_volume(&world), 0.5);
+    }
+
+    /// Footer of the entry recorded after `edit` changes the settings
+    fn describe_recorded(edit: impl FnOnce(&mut Settings), language: Language) -> String {
+        let mut app = App::new();
+        app.add_plugins(SettingsChangesPlugin)
+            .register_type::<Settings>()
+            .init_resource::<Settings>()
+            .init_resource::<SettingsHistory>()
+            .init_resource::<Time<Real>>()
+            .add_systems(PostUpdate, record_setting_changes.after(SettingsCommitSet));
+        app.update();
+        edit(&mut app.world_mut().resource_mut::<Settings>());
+        app.update();
+
+        let mut localization = Localization::shipped();
+        localization.set_language(language);
+        app.world().resource::<SettingsHistory>().next_undo().unwrap().describe(&localization)
+    }
+
+    #[test]
+    fn describes_recorded_values_like_the_menu() {
+        let difficulty = describe_recorded(|settings| settings.gameplay.difficulty = Difficulty::Hard, Language::German);
+        assert!(difficulty.contains("Normal") && difficulty.contains("Schwer"), "{difficulty}");
+
+        let localization = Localization::shipped();
+        let resolution = describe_recorded(|settings| settings.video.resolution = (2560, 1440), Language::English);
+        let expected = localization.format_value("video.resolution", &(2560_u32, 1440_u32), None);
+        assert!(resolution.ends_with(&expected), "{resolution}");
+
+        let fps_limit = describe_recorded(|settings| settings.video.fps_limit = Some(144), Language::English);
+        let unlimited = localization.format_value("video.fps_limit", &None::<u32>, None);
+        assert!(fps_limit.contains(&unlimited) && fps_limit.contains("144"), "{fps_limit}");
+    }
+}
@@ -0,0 +1,126 @@
+//! Unloading and reloading the plugin library during development
+//!
+//! Before the host drops the library it calls `settings_plugin_unload`, which
+//! snapshots [`Settings`](crate::Settings) (with every registered section) and [`SettingsState`]
+//! to RON and despawns every entity tagged with [`SettingsMenuEntity`]. Nothing
+//! that points into the old library's code is left in the world once the host
+//! drops the app it built the plugin into. After loading the new library and
+//! building the plugin again, `settings_plugin_restore` applies the snapshot.
+//!
+//! Bevy can't remove systems or type registrations, so the host has to rebuild
+//! its app around each load. The host and every loaded copy of the library must
+//! share one Bevy, which the feature ensures by enabling `bevy/dynamic_linking`,
+//! and old images must stay mapped since Bevy's global label interner keeps
+//! pointing into them. `src/bin/hot_reload_host.rs` shows the full cycle.
+
+use std::fmt;
+
+use bevy::prelude::*;
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+
+use crate::display::restore_system_cursor;
+use crate::persistence::{apply_settings, deserialize_settings, save_pending_settings, serialize_settings, SettingsFileError};
+use crate::settings::SettingsState;
+use crate::SettingsMenuEntity;
+
+/// State carried across a reload
+#[derive(Serialize, Deserialize)]
+struct SettingsSnapshot {
+    /// Same format as the settings file
+    settings: String,
+    state: String,
+}
+
+#[derive(Debug)]
+pub enum HotReloadError {
+    Settings(SettingsFileError),
+    Snapshot(String),
+}
+
+impl fmt::Display for HotReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotReloadError::Settings(err) => write!(f, "{err}"),
+            HotReloadError::Snapshot(err) => write!(f, "invalid settings snapshot: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for HotReloadError {}
+
+impl From<SettingsFileError> for HotReloadError {
+    fn from(err: SettingsFileError) -> Self {
+        HotReloadError::Settings(err)
+    }
+}
+
+/// Save pending edits, snapshot the settings and despawn everything the plugin spawned
+pub fn prepare_unload(world: &mut World) -> Result<String, HotReloadError> {
+    save_pending_settings(world);
+    restore_system_cursor(world);
+    let snapshot = snapshot(world)?;
+    let despawned = despawn_plugin_entities(world);
+    info!("Settings plugin unloading, despawned {} entities", despawned);
+    Ok(snapshot)
+}
+
+/// Serialize [`Settings`](crate::Settings), plugin sections and [`SettingsState`]
+pub fn snapshot(world: &World) -> Result<String, HotReloadError> {
+    let settings = serialize_settings(world)?;
+    let state = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let serializer = TypedReflectSerializer::new(world.resource::<SettingsState>(), &registry);
+        ron::to_string(&serializer).map_err(|err| HotReloadError::Snapshot(err.to_string()))?
+    };
+
+    ron::to_string(&SettingsSnapshot { settings, state })
+        .map_err(|err| HotReloadError::Snapshot(err.to_string()))
+}
+
+/// Apply a snapshot taken by [`prepare_unload`] in a previous load of the library
+pub fn restore(world: &mut World, source: &str) -> Result<(), HotReloadError> {
+    let snapshot: SettingsSnapshot =
+        ron::from_str(source).map_err(|err| HotReloadError::Snapshot(err.to_string()))?;
+
+    let (values, skipped) = deserialize_settings(world, &snapshot.settings)?;
+    for setting in skipped {
+        warn!("Setting {:?} not restored after reload: {}", setting.path, setting.reason);
+    }
+    for (key, err) in apply_settings(world, values) {
+        warn!("Settings section {:?} not restored after reload: {}", key, err);
+    }
+
+    let state = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let registration = registry
+            .get(std::any::TypeId::of::<SettingsState>())
+            .ok_or_else(|| HotReloadError::Snapshot("SettingsState is not registered".to_string()))?;
+        let mut deserializer = ron::Deserializer::from_str(&snapshot.state)
+            .map_err(|err| HotReloadError::Snapshot(err.to_string()))?;
+        TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(&mut deserializer)
+            .map_err(|err| HotReloadError::Snapshot(err.to_string()))?
+    };
+    world
+        .resource_mut::<SettingsState>()
+        .try_apply(state.as_ref())
+        .map_err(|err| HotReloadError::Snapshot(err.to_string()))
+}
+
+/// Recursively despawn every [`SettingsMenuEntity`], returning how many were tagged
+pub fn despawn_plugin_entities(world: &mut World) -> usize {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<SettingsMenuEntity>>()
+        .iter(world)
+        .collect();
+
+    for &entity in &entities {
+        // Tagged entities can be nested, so an earlier despawn may have removed it
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+    entities.len()
+}
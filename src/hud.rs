@@ -0,0 +1,207 @@
+//! Where the in-match HUD goes
+//!
+//! [`HudLayout`] turns [`HudSettings`] into screen areas, in fractions of the
+//! screen from its top left corner. The Interface tab draws its HUD preview
+//! from it, and the game can place its own HUD with it:
+//!
+//! ```ignore
+//! let layout = HudLayout::new(&settings.interface.hud, window.width() / window.height());
+//! minimap_style.left = Val::Percent(layout.minimap.min.x * 100.0);
+//! ```
+
+use bevy::math::{Rect, Vec2};
+
+use crate::settings::{CommandCardLayout, HudSettings, ResourceBarPosition, ScreenCorner};
+
+/// Gap between HUD elements and the screen edge
+const EDGE: f32 = 0.01;
+/// Minimap height at size 1.0
+const MINIMAP_HEIGHT: f32 = 0.25;
+/// The grid command card has square buttons in 5 columns and 3 rows
+const COMMAND_CARD_HEIGHT: f32 = 0.2;
+const COMMAND_CARD_COLUMNS: f32 = 5.0;
+const COMMAND_CARD_ROWS: f32 = 3.0;
+const CHAT_SIZE: Vec2 = Vec2::new(0.3, 0.2);
+const RESOURCE_BAR_SIZE: Vec2 = Vec2::new(0.3, 0.04);
+const PERFORMANCE_OVERLAY_SIZE: Vec2 = Vec2::new(0.08, 0.03);
+
+/// Screen areas of the HUD elements
+///
+/// The areas never overlap: the resource bar moves below a minimap in its
+/// corner, the chat sits above whatever is at the bottom left and the
+/// performance overlay below whatever is at the top right.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HudLayout {
+    pub minimap: Rect,
+    pub command_card: Rect,
+    pub resource_bar: Rect,
+    pub chat: Rect,
+    /// Frame rate and latency, when the overlay is on
+    pub performance_overlay: Option<Rect>,
+}
+
+impl HudLayout {
+    /// Layout for a screen `aspect` times as wide as it is high
+    pub fn new(hud: &HudSettings, aspect: f32) -> Self {
+        let minimap_size = Vec2::new(MINIMAP_HEIGHT * hud.minimap_size / aspect, MINIMAP_HEIGHT * hud.minimap_size);
+        let minimap_left = matches!(hud.minimap_position, ScreenCorner::BottomLeft | ScreenCorner::TopLeft);
+        let minimap_top = matches!(hud.minimap_position, ScreenCorner::TopLeft | ScreenCorner::TopRight);
+        let minimap = corner_rect(minimap_left, minimap_top, minimap_size);
+
+        let command_card = match hud.command_card_layout {
+            CommandCardLayout::Grid => {
+                let width = COMMAND_CARD_HEIGHT / COMMAND_CARD_ROWS * COMMAND_CARD_COLUMNS / aspect;
+                corner_rect(!minimap_left, false, Vec2::new(width, COMMAND_CARD_HEIGHT))
+            }
+            CommandCardLayout::Row => Rect::new(0.3, 1.0 - EDGE - 0.08, 0.7, 1.0 - EDGE),
+        };
+
+        let resource_bar_left = match hud.resource_bar_position {
+            ResourceBarPosition::TopLeft => EDGE,
+            ResourceBarPosition::TopCenter => (1.0 - RESOURCE_BAR_SIZE.x) / 2.0,
+            ResourceBarPosition::TopRight => 1.0 - EDGE - RESOURCE_BAR_SIZE.x,
+        };
+        let mut resource_bar = area(Vec2::new(resource_bar_left, EDGE), RESOURCE_BAR_SIZE);
+        // Below the minimap when they share a corner
+        if overlaps(resource_bar, minimap) {
+            resource_bar = area(Vec2::new(resource_bar_left, minimap.max.y + EDGE), RESOURCE_BAR_SIZE);
+        }
+
+        // Chat sits on the left, above whatever is at the bottom of that side
+        let chat_bottom = [minimap, command_card]
+            .into_iter()
+            .filter(|rect| rect.center().y > 0.5 && overlaps(*rect, column(EDGE, CHAT_SIZE.x)))
+            .fold(1.0 - EDGE, |bottom, rect| bottom.min(rect.min.y - EDGE));
+        let chat = Rect::new(EDGE, chat_bottom - CHAT_SIZE.y, EDGE + CHAT_SIZE.x, chat_bottom);
+
+        // Top right, below whatever else is at the top of that side
+        let performance_left = 1.0 - EDGE - PERFORMANCE_OVERLAY_SIZE.x;
+        let performance_top = [minimap, resource_bar]
+            .into_iter()
+            .filter(|rect| rect.center().y < 0.5 && overlaps(*rect, column(performance_left, PERFORMANCE_OVERLAY_SIZE.x)))
+            .fold(EDGE, |top, rect| top.max(rect.max.y + EDGE));
+        let performance_overlay = hud
+            .performance_overlay
+            .then(|| area(Vec2::new(performance_left, performance_top), PERFORMANCE_OVERLAY_SIZE));
+
+        Self {
+            minimap,
+            command_card,
+            resource_bar,
+            chat,
+            performance_overlay,
+        }
+    }
+}
+
+/// Area of `size` in a screen corner
+fn corner_rect(left: bool, top: bool, size: Vec2) -> Rect {
+    let x = if left { EDGE } else { 1.0 - EDGE - size.x };
+    let y = if top { EDGE } else { 1.0 - EDGE - size.y };
+    area(Vec2::new(x, y), size)
+}
+
+/// Area of `size` with its top left corner at `min`
+fn area(min: Vec2, size: Vec2) -> Rect {
+    Rect::from_corners(min, min + size)
+}
+
+/// Screen column `width` wide from `left`
+fn column(left: f32, width: f32) -> Rect {
+    Rect::new(left, 0.0, left + width, 1.0)
+}
+
+/// Whether `a` and `b` cover some of the same area; touching edges don't count
+fn overlaps(a: Rect, b: Rect) -> bool {
+    !a.intersect(b).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORNERS: [ScreenCorner; 4] =
+        [ScreenCorner::BottomLeft, ScreenCorner::BottomRight, ScreenCorner::TopLeft, ScreenCorner::TopRight];
+    const RESOURCE_BAR_POSITIONS: [ResourceBarPosition; 3] =
+        [ResourceBarPosition::TopLeft, ResourceBarPosition::TopCenter, ResourceBarPosition::TopRight];
+
+    fn hud(minimap: ScreenCorner, resource_bar: ResourceBarPosition) -> HudSettings {
+        HudSettings {
+            minimap_position: minimap,
+            resource_bar_position: resource_bar,
+            performance_overlay: true,
+            ..Default::default()
+        }
+    }
+
+    fn areas(layout: &HudLayout) -> Vec<(&'static str, Rect)> {
+        let mut areas = vec![
+            ("minimap", layout.minimap),
+            ("command card", layout.command_card),
+            ("resource bar", layout.resource_bar),
+            ("chat", layout.chat),
+        ];
+        areas.extend(layout.performance_overlay.map(|rect| ("performance overlay", rect)));
+        areas
+    }
+
+    #[test]
+    fn elements_stay_apart_and_on_screen() {
+        for aspect in [4.0 / 3.0, 16.0 / 9.0, 21.0 / 9.0] {
+            for minimap_size in [0.5, 1.0, 1.5] {
+                for layout in [CommandCardLayout::Grid, CommandCardLayout::Row] {
+                    for corner in CORNERS {
+                        for position in RESOURCE_BAR_POSITIONS {
+                            let settings = HudSettings {
+                                minimap_size,
+                                command_card_layout: layout,
+                                ..hud(corner, position)
+                            };
+                            let areas = areas(&HudLayout::new(&settings, aspect));
+                            for (index, (name, rect)) in areas.iter().enumerate() {
+                                assert!(
+                                    rect.min.cmpge(Vec2::ZERO).all() && rect.max.cmple(Vec2::ONE).all(),
+                                    "{name} off screen in {settings:?} at {aspect}",
+                                );
+                                for (other, other_rect) in &areas[index + 1..] {
+                                    assert!(
+                                        !overlaps(*rect, *other_rect),
+                                        "{name} overlaps {other} in {settings:?} at {aspect}",
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resource_bar_moves_below_minimap_in_the_same_corner() {
+        let layout = HudLayout::new(&hud(ScreenCorner::TopRight, ResourceBarPosition::TopRight), 16.0 / 9.0);
+        assert_eq!(layout.resource_bar.min.y, layout.minimap.max.y + EDGE);
+        assert_eq!(layout.resource_bar.max.x, layout.minimap.max.x);
+
+        let layout = HudLayout::new(&hud(ScreenCorner::BottomRight, ResourceBarPosition::TopRight), 16.0 / 9.0);
+        assert_eq!(layout.resource_bar.min.y, EDGE);
+    }
+
+    #[test]
+    fn performance_overlay_goes_below_top_right_elements() {
+        let layout = HudLayout::new(&hud(ScreenCorner::TopRight, ResourceBarPosition::TopRight), 16.0 / 9.0);
+        assert_eq!(layout.performance_overlay.unwrap().min.y, layout.resource_bar.max.y + EDGE);
+
+        let layout = HudLayout::new(&hud(ScreenCorner::BottomLeft, ResourceBarPosition::TopLeft), 16.0 / 9.0);
+        assert_eq!(layout.performance_overlay.unwrap().min.y, EDGE);
+    }
+
+    #[test]
+    fn chat_sits_above_bottom_left_element() {
+        let layout = HudLayout::new(&hud(ScreenCorner::BottomLeft, ResourceBarPosition::TopRight), 16.0 / 9.0);
+        assert_eq!(layout.chat.max.y, layout.minimap.min.y - EDGE);
+
+        let layout = HudLayout::new(&hud(ScreenCorner::BottomRight, ResourceBarPosition::TopRight), 16.0 / 9.0);
+        assert_eq!(layout.chat.max.y, layout.command_card.min.y - EDGE);
+    }
+}
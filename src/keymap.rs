@@ -0,0 +1,218 @@
+//! Keybinding presets and keymap import/export
+//!
+//! Keymaps are stored as RON files mapping action ids (the field names of
+//! [`KeybindSettings`]) to `KeyCode` variant names. The shipped presets live in
+//! `assets/keymaps/` and are embedded at compile time so the plugin works
+//! regardless of the host game's asset folder.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, Enum, FromReflect, Struct};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::KeybindSettings;
+
+/// Default location for exported keymaps, relative to the working directory
+pub const DEFAULT_KEYMAP_PATH: &str = "keymaps/custom.keymap.ron";
+
+/// Named keymap presets shipped with the plugin
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeymapPreset {
+    #[default]
+    Classic,
+    Grid,
+    LeftHanded,
+}
+
+impl KeymapPreset {
+    pub const ALL: [KeymapPreset; 3] = [
+        KeymapPreset::Classic,
+        KeymapPreset::Grid,
+        KeymapPreset::LeftHanded,
+    ];
+
+    /// Label shown on the preset selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeymapPreset::Classic => "Classic",
+            KeymapPreset::Grid => "Grid",
+            KeymapPreset::LeftHanded => "Left-handed",
+        }
+    }
+
+    /// Raw RON source of the preset
+    pub fn source(&self) -> &'static str {
+        match self {
+            KeymapPreset::Classic => include_str!("../assets/keymaps/classic.keymap.ron"),
+            KeymapPreset::Grid => include_str!("../assets/keymaps/grid.keymap.ron"),
+            KeymapPreset::LeftHanded => include_str!("../assets/keymaps/left_handed.keymap.ron"),
+        }
+    }
+
+    /// Resolve the preset into a full set of keybinds
+    pub fn keybinds(&self) -> Result<KeymapImport, KeymapError> {
+        let file = KeymapFile::from_ron(self.source())?;
+        Ok(file.resolve(&KeybindSettings::default()))
+    }
+}
+
+/// On-disk representation of a keymap
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct KeymapFile {
+    pub name: String,
+    pub bindings: BTreeMap<String, String>,
+}
+
+impl KeymapFile {
+    /// Capture every binding in `keybinds` under the given name
+    pub fn from_keybinds(name: impl Into<String>, keybinds: &KeybindSettings) -> Self {
+        let mut bindings = BTreeMap::new();
+        for (index, value) in keybinds.iter_fields().enumerate() {
+            let (Some(action), Some(key)) = (keybinds.name_at(index), value.downcast_ref::<KeyCode>()) else {
+                continue;
+            };
+            bindings.insert(action.to_string(), key.variant_name().to_string());
+        }
+
+        Self {
+            name: name.into(),
+            bindings,
+        }
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, KeymapError> {
+        ron::from_str(source).map_err(|err| KeymapError::Parse(err.to_string()))
+    }
+
+    pub fn to_ron(&self) -> Result<String, KeymapError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| KeymapError::Parse(err.to_string()))
+    }
+
+    /// Apply the bindings on top of `base`, collecting anything that could not be applied
+    pub fn resolve(&self, base: &KeybindSettings) -> KeymapImport {
+        let mut keybinds = base.clone();
+        let mut unknown_actions = Vec::new();
+        let mut unknown_keys = Vec::new();
+
+        for (action, key_name) in &self.bindings {
+            let Some(key) = key_code_from_name(key_name) else {
+                unknown_keys.push((action.clone(), key_name.clone()));
+                continue;
+            };
+
+            match keybinds
+                .field_mut(action)
+                .and_then(|field| field.downcast_mut::<KeyCode>())
+            {
+                Some(binding) => *binding = key,
+                None => unknown_actions.push(action.clone()),
+            }
+        }
+
+        KeymapImport {
+            name: self.name.clone(),
+            keybinds,
+            unknown_actions,
+            unknown_keys,
+        }
+    }
+}
+
+/// Result of applying a keymap file
+#[derive(Clone)]
+pub struct KeymapImport {
+    pub name: String,
+    pub keybinds: KeybindSettings,
+    /// Action ids in the file that don't exist in [`KeybindSettings`]
+    pub unknown_actions: Vec<String>,
+    /// `(action, key)` pairs whose key name isn't a valid `KeyCode`
+    pub unknown_keys: Vec<(String, String)>,
+}
+
+impl KeymapImport {
+    pub fn is_clean(&self) -> bool {
+        self.unknown_actions.is_empty() && self.unknown_keys.is_empty()
+    }
+
+    /// Human readable summary of the import, suitable for the status line
+    pub fn report(&self) -> String {
+        let mut report = format!("Loaded keymap \"{}\"", self.name);
+        if !self.unknown_actions.is_empty() {
+            report.push_str(&format!(
+                "; ignored unknown actions: {}",
+                self.unknown_actions.join(", ")
+            ));
+        }
+        if !self.unknown_keys.is_empty() {
+            let keys: Vec<String> = self
+                .unknown_keys
+                .iter()
+                .map(|(action, key)| format!("{action} = {key}"))
+                .collect();
+            report.push_str(&format!("; ignored unknown keys: {}", keys.join(", ")));
+        }
+        report
+    }
+}
+
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::Io(err) => write!(f, "keymap file error: {err}"),
+            KeymapError::Parse(err) => write!(f, "invalid keymap: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+impl From<io::Error> for KeymapError {
+    fn from(err: io::Error) -> Self {
+        KeymapError::Io(err)
+    }
+}
+
+/// Read a keymap file and apply it on top of `base`
+pub fn import_keymap(path: &Path, base: &KeybindSettings) -> Result<KeymapImport, KeymapError> {
+    let source = fs::read_to_string(path)?;
+    let file = KeymapFile::from_ron(&source)?;
+    Ok(file.resolve(base))
+}
+
+/// Write `keybinds` to a standalone keymap file that can be shared
+pub fn export_keymap(path: &Path, name: &str, keybinds: &KeybindSettings) -> Result<(), KeymapError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let source = KeymapFile::from_keybinds(name, keybinds).to_ron()?;
+    fs::write(path, source)?;
+    Ok(())
+}
+
+/// Look up a `KeyCode` by its variant name (e.g. `"KeyW"`, `"ArrowUp"`)
+pub fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    let dynamic = DynamicEnum::new(name, DynamicVariant::Unit);
+    KeyCode::from_reflect(&dynamic)
+}
+
+/// Path used by the Controls tab for keymap import/export
+#[derive(Resource, Clone)]
+pub struct KeymapPath(pub PathBuf);
+
+impl Default for KeymapPath {
+    fn default() -> Self {
+        Self(PathBuf::from(DEFAULT_KEYMAP_PATH))
+    }
+}
@@ -1,7 +1,32 @@
 //! Settings menu implementation for StrategyForge
 
+pub mod accessibility;
+pub mod camera_input;
+pub mod changes;
+pub mod colorblind;
+pub mod difficulty;
+pub mod display;
+pub mod fonts;
+pub mod gameplay_options;
+pub mod gamepad;
+pub mod history;
+pub mod hud;
+#[cfg(feature = "hot-reload")]
+pub mod hot_reload;
+pub mod keymap;
+pub mod localization;
 pub mod modules;
+pub mod overlay;
+pub mod persistence;
+pub mod registry;
+pub mod ron_asset;
 pub mod settings;
+pub mod subtitles;
+pub mod tabs;
+pub mod theme;
+pub mod tutorials;
+pub mod validation;
+pub mod widgets;
 mod create_plugin;
 
 // Re-exports - only export the spawn functions to avoid conflicts
@@ -11,11 +36,15 @@ pub use modules::gameplay::spawn_gameplay_settings;
 pub use modules::interface::spawn_interface_settings;
 pub use modules::video::spawn_video_settings;
 
+use std::path::PathBuf;
+
+use bevy::ecs::world::CommandQueue;
 use bevy::prelude::*;
-use bevy_reflect::Reflect;
-use sf_plugin_template::{MenuItemPlugin, MenuItem, MenuContent, GamePlugin};
-use sf_ui_common::colors;
-use sf_ui_common::components::{Focusable, FocusState, FocusableType};
+use sf_plugin_template::{MenuItemPlugin, MenuItem, GamePlugin};
+
+use overlay::{EmbeddedSettingsMenu, SettingsPanel};
+use registry::SettingsSections;
+use tabs::SettingsTabContent;
 
 // Import PluginHandle from sf-plugin-template but rename it to avoid conflicts
 use sf_plugin_template::PluginHandle as SfPluginHandle;
@@ -31,29 +60,232 @@ pub use settings::{
     SettingsState,
     SettingsTab,
 };
+pub use changes::{
+    AudioSettingsChanged,
+    ControlsSettingsChanged,
+    GameplaySettingsChanged,
+    InterfaceSettingsChanged,
+    SettingChanged,
+    SettingsCommitSet,
+    SettingsSectionChanged,
+    VideoSettingsChanged,
+};
+pub use accessibility::{AccessibleControl, ControlRole};
+pub use colorblind::{ColorblindMode, ColorblindPreview, TeamPalette};
+pub use difficulty::{DifficultyModifiers, DifficultyTable, DifficultyTablePath, MatchInProgress};
+pub use display::{CursorSize, GameCursor, UiAccessibility};
+pub use fonts::{FontRole, SettingsFonts};
+pub use gameplay_options::{AlertKind, GameplayOptions};
+pub use history::{SettingsHistory, SettingsHistoryBindings};
+pub use hud::HudLayout;
+pub use localization::{Language, Localization, LocalizedText};
+pub use overlay::{settings_overlay_closed, OverlayPausePolicy, SettingsOverlayToggled};
+pub use registry::RegisterSettingsSection;
+pub use ron_asset::RonAssetPath;
+pub use subtitles::{SpeakerColors, SubtitleLine, SubtitleQueue, SubtitleSize};
+pub use tabs::{RegisterSettingsTab, SettingsTabInfo, SettingsTabs};
+pub use theme::{SettingsTheme, SettingsThemePath, ThemeElement};
+pub use tutorials::{RegisterTutorialHint, SeenTutorialHints, ShowTutorialHint, TutorialHint, TutorialHints};
+pub use create_plugin::{
+    BEVY_VERSION,
+    PLUGIN_TEMPLATE_VERSION,
+    RUSTC_VERSION,
+    SETTINGS_PLUGIN_ABI_VERSION,
+};
+pub use validation::{SettingIssue, SettingsRepairReport, SettingsValidation};
+pub use widgets::{LockedSettings, SettingRange};
+
+/// Tags every root entity the plugin spawns so it can be despawned before the
+/// library is unloaded
+#[derive(Component)]
+pub struct SettingsMenuEntity;
+
+/// Configuration of [`SettingsMenuPlugin`], available as a resource once the
+/// plugin is built
+#[derive(Resource, Clone, Debug)]
+pub struct SettingsMenuConfig {
+    /// Built-in tabs to show, in order; tabs added by plugin settings sections
+    /// are always shown after them
+    pub tabs: Vec<SettingsTab>,
+    /// Settings file location, `None` keeps [`persistence::DEFAULT_SETTINGS_PATH`]
+    pub config_path: Option<PathBuf>,
+    /// Spawn the standalone settings panel at startup
+    pub standalone_panel: bool,
+    /// Offer the settings menu as a [`MenuItemPlugin`] to the host menu; when
+    /// disabled the menu item hooks spawn nothing
+    pub menu_item: bool,
+    /// Key toggling the standalone panel as an in-game overlay, `None` disables it
+    pub toggle_key: Option<KeyCode>,
+    /// Whether the overlay pauses the game while open
+    pub pause_policy: OverlayPausePolicy,
+}
+
+impl Default for SettingsMenuConfig {
+    fn default() -> Self {
+        Self {
+            tabs: vec![
+                SettingsTab::Video,
+                SettingsTab::Audio,
+                SettingsTab::Gameplay,
+                SettingsTab::Controls,
+                SettingsTab::Interface,
+            ],
+            config_path: None,
+            standalone_panel: true,
+            menu_item: true,
+            toggle_key: Some(KeyCode::Escape),
+            pause_policy: OverlayPausePolicy::default(),
+        }
+    }
+}
 
 /// Main plugin for settings menu
+///
+/// Inserts the settings resources, loads and saves the settings file and adds
+/// the menu systems. Configure it with the builder methods:
+///
+/// ```ignore
+/// app.add_plugins(
+///     SettingsMenuPlugin::new()
+///         .with_tabs([SettingsTab::Video, SettingsTab::Audio])
+///         .with_config_path("config/settings.ron")
+///         .with_standalone_panel(false),
+/// );
+/// ```
 #[derive(Default, Clone)]
 pub struct SettingsMenuPlugin {
-    /// Whether this plugin has been registered with the menu system
-    registered: bool,
+    config: SettingsMenuConfig,
+}
+
+impl SettingsMenuPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show only these built-in tabs, in this order
+    pub fn with_tabs(mut self, tabs: impl IntoIterator<Item = SettingsTab>) -> Self {
+        self.config.tabs = tabs.into_iter().collect();
+        self
+    }
+
+    /// Load and save settings at `path` instead of the default location
+    pub fn with_config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.config_path = Some(path.into());
+        self
+    }
+
+    /// Whether to spawn the standalone settings panel at startup
+    pub fn with_standalone_panel(mut self, enabled: bool) -> Self {
+        self.config.standalone_panel = enabled;
+        self
+    }
+
+    /// Whether to offer the settings menu to the host menu as a [`MenuItemPlugin`]
+    pub fn with_menu_item(mut self, enabled: bool) -> Self {
+        self.config.menu_item = enabled;
+        self
+    }
+
+    /// Key toggling the in-game overlay, `None` leaves showing it to the host
+    pub fn with_toggle_key(mut self, key: Option<KeyCode>) -> Self {
+        self.config.toggle_key = key;
+        self
+    }
+
+    /// Whether the in-game overlay pauses the game
+    pub fn with_pause_policy(mut self, policy: OverlayPausePolicy) -> Self {
+        self.config.pause_policy = policy;
+        self
+    }
+
+    pub fn config(&self) -> &SettingsMenuConfig {
+        &self.config
+    }
+
+    /// The menu item the host menu should add, if menu integration is enabled
+    pub fn menu_item(&self) -> Option<Box<dyn MenuItemPlugin>> {
+        self.config.menu_item.then(|| self.clone_box())
+    }
 }
 
 impl Plugin for SettingsMenuPlugin {
     fn build(&self, app: &mut App) {
-        // Register types for reflection
-        app.register_type::<Settings>()
-            .register_type::<AudioSettings>()
+        if let Some(path) = &self.config.config_path {
+            app.insert_resource(persistence::SettingsPath(path.clone()));
+        }
+
+        let mut state = SettingsState::default();
+        if let Some(first) = self.config.tabs.first() {
+            if !self.config.tabs.contains(&state.current_tab) {
+                state.current_tab = first.clone();
+            }
+        }
+
+        // Built-in tabs follow the configured order; a tab already registered
+        // by the host keeps its label, icon and order
+        let mut tabs = app
+            .world_mut()
+            .get_resource_or_insert_with(SettingsTabs::default);
+        for (index, tab) in self.config.tabs.iter().enumerate() {
+            if !tabs.contains(tab) {
+                tabs.insert(SettingsTabInfo::new(tab.clone(), tab.label()).with_order(index as i32 * 100));
+            }
+        }
+
+        app.insert_resource(self.config.clone())
+            .insert_resource(state)
+            .init_resource::<Settings>()
+            .init_resource::<SettingsSections>()
+            .register_type::<Settings>()
+            .register_type::<SettingsState>()
+            .register_type::<SettingsTab>()
             .register_type::<VideoSettings>()
-            .register_type::<ControlsSettings>()
+            .register_type::<AudioSettings>()
             .register_type::<GameplaySettings>()
+            .register_type::<ControlsSettings>()
             .register_type::<InterfaceSettings>()
-            .register_type::<SettingsTab>()
-            .register_type::<SettingsState>();
-            
-        // Only add the setup system if we haven't registered yet
-        if !self.registered {
-            app.add_systems(Startup, setup_settings_menu);
+            .register_type::<settings::KeybindSettings>()
+            .register_type::<keymap::KeymapPreset>()
+            .register_type::<settings::AccelerationCurve>()
+            .register_type::<settings::CameraSettings>()
+            .register_type::<settings::ControllerSettings>()
+            .register_type::<settings::StickSettings>()
+            .register_type::<settings::ResponseCurve>()
+            .register_type::<settings::SubtitleStyle>()
+            .register_type::<settings::AutosaveSettings>()
+            .register_type::<settings::AlertSettings>()
+            .register_type::<settings::HudSettings>()
+            .add_plugins((
+                modules::VideoPlugin,
+                modules::AudioPlugin,
+                modules::GameplayPlugin,
+                modules::ControlsPlugin,
+                modules::InterfacePlugin,
+                camera_input::CameraInputPlugin,
+                gamepad::GamepadInputPlugin,
+                widgets::SettingsWidgetsPlugin,
+                tabs::SettingsTabsPlugin,
+                persistence::SettingsPersistencePlugin,
+                changes::SettingsChangesPlugin,
+                history::SettingsHistoryPlugin,
+                validation::SettingsValidationPlugin,
+                (
+                    colorblind::ColorblindPlugin,
+                    accessibility::SettingsAccessibilityPlugin,
+                    display::UiAccessibilityPlugin,
+                    localization::LocalizationPlugin,
+                    fonts::SettingsFontsPlugin,
+                    theme::SettingsThemePlugin,
+                    subtitles::SubtitlesPlugin,
+                    tutorials::TutorialHintsPlugin,
+                    difficulty::DifficultyPlugin,
+                    gameplay_options::GameplayOptionsPlugin,
+                ),
+            ));
+
+        if self.config.standalone_panel {
+            app.add_plugins(overlay::SettingsOverlayPlugin)
+                .add_systems(Startup, setup_settings_menu);
         }
     }
 }
@@ -77,48 +309,56 @@ impl MenuItemPlugin for SettingsMenuPlugin {
         "Settings"
     }
     
-    fn add_menu_item(&self, parent: &mut ChildBuilder, asset_server: &AssetServer) -> Entity {
+    fn add_menu_item(&self, parent: &mut ChildBuilder, _asset_server: &AssetServer) -> Entity {
+        // Hosts that register every plugin as a menu item get nothing to show
+        // when menu integration is disabled
+        if !self.config.menu_item {
+            return Entity::PLACEHOLDER;
+        }
+
         // Create a menu item button
         parent.spawn((
             ButtonBundle {
                 style: Style {
-                    width: Val::Px(200.0),
-                    height: Val::Px(50.0),
-                    margin: UiRect::all(Val::Px(5.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
-                background_color: colors::button::NORMAL.into(),
                 ..default()
             },
+            ThemeElement::MenuItem,
             MenuItem {
                 plugin_name: self.menu_name().to_string(),
                 selected: false,
             },
             Name::new("SettingsMenuItem"),
+            SettingsMenuEntity,
         )).with_children(|button| {
-            button.spawn(
-                TextBundle::from_section(
-                    self.menu_label(),
-                    TextStyle {
-                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 24.0,
-                        color: Color::WHITE,
-                    },
-                ),
-            );
+            button.spawn((
+                TextBundle::from_section(self.menu_label(), TextStyle::default()),
+                ThemeElement::MenuItemLabel,
+                LocalizedText::new(localization::keys::MENU_ITEM).with_fallback(self.menu_label()),
+                FontRole::Bold,
+            ));
         }).id()
     }
     
 
     
     fn on_selected(&self, world: &mut World, content_entity: Entity) {
+        if !self.config.menu_item {
+            return;
+        }
+
         // Display settings content when this menu item is selected
-        // Get the asset server before we borrow world
+        // Collect the tabs before we borrow world
+        let tabs = world.get_resource::<SettingsTabs>().cloned().unwrap_or_default();
         let asset_server = world.resource::<AssetServer>().clone();
         
-        let mut entity = world.entity_mut(content_entity);
+        // Spawn through commands so the tab helpers get a regular ChildBuilder
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let mut entity = commands.entity(content_entity);
         entity.despawn_descendants();
         
         // Create settings panel with tabbed interface
@@ -130,352 +370,45 @@ impl MenuItemPlugin for SettingsMenuPlugin {
                         width: Val::Percent(100.0),
                         height: Val::Percent(100.0),
                         flex_direction: FlexDirection::Column,
-                        padding: UiRect::all(Val::Px(20.0)),
                         ..default()
                     },
                     ..default()
                 },
+                ThemeElement::EmbeddedMenu,
+                SettingsMenuEntity,
+                EmbeddedSettingsMenu,
             )).with_children(|parent| {
                 // Title
-                parent.spawn(
-                    TextBundle::from_section(
-                        "Game Settings",
-                        TextStyle {
-                            font_size: 32.0,
-                            color: Color::WHITE,
-                            ..default()
-                        }
-                    ).with_style(Style {
-                        margin: UiRect::bottom(Val::Px(20.0)),
-                        ..default()
-                    })
-                );
+                parent.spawn((
+                    TextBundle::from_section("Game Settings", TextStyle::default()),
+                    ThemeElement::Title,
+                    LocalizedText::new(localization::keys::MENU_TITLE).with_fallback("Game Settings"),
+                    FontRole::Bold,
+                ));
                 
                 // Tabs for different settings categories
-                parent.spawn(
-                    NodeBundle {
-                        style: Style {
-                            width: Val::Percent(100.0),
-                            height: Val::Px(50.0),
-                            flex_direction: FlexDirection::Row,
-                            ..default()
-                        },
-                        background_color: Color::srgb(0.2, 0.2, 0.2).into(),
-                        ..default()
-                    }
-                ).with_children(|parent| {
-                    // Video tab
-                    parent.spawn(
-                        ButtonBundle {
-                            style: Style {
-                                width: Val::Percent(25.0),
-                                height: Val::Percent(100.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            background_color: colors::button::PRESSED.into(),
-                            ..default()
-                        }
-                    ).with_children(|parent| {
-                        parent.spawn(
-                            TextBundle::from_section(
-                                "Video",
-                                TextStyle {
-                                    font_size: 18.0,
-                                    color: Color::WHITE,
-                                    ..default()
-                                }
-                            )
-                        );
-                    });
-                    
-                    // Audio tab
-                    parent.spawn(
-                        ButtonBundle {
-                            style: Style {
-                                width: Val::Percent(25.0),
-                                height: Val::Percent(100.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            background_color: colors::button::NORMAL.into(),
-                            ..default()
-                        }
-                    ).with_children(|parent| {
-                        parent.spawn(
-                            TextBundle::from_section(
-                                "Audio",
-                                TextStyle {
-                                    font_size: 18.0,
-                                    color: Color::WHITE,
-                                    ..default()
-                                }
-                            )
-                        );
-                    });
-                    
-                    // Controls tab
-                    parent.spawn(
-                        ButtonBundle {
-                            style: Style {
-                                width: Val::Percent(25.0),
-                                height: Val::Percent(100.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            background_color: colors::button::NORMAL.into(),
-                            ..default()
-                        }
-                    ).with_children(|parent| {
-                        parent.spawn(
-                            TextBundle::from_section(
-                                "Controls",
-                                TextStyle {
-                                    font_size: 18.0,
-                                    color: Color::WHITE,
-                                    ..default()
-                                }
-                            )
-                        );
-                    });
-                    
-                    // Other tab
-                    parent.spawn(
-                        ButtonBundle {
-                            style: Style {
-                                width: Val::Percent(25.0),
-                                height: Val::Percent(100.0),
-                                justify_content: JustifyContent::Center,
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            background_color: colors::button::NORMAL.into(),
-                            ..default()
-                        }
-                    ).with_children(|parent| {
-                        parent.spawn(
-                            TextBundle::from_section(
-                                "Other",
-                                TextStyle {
-                                    font_size: 18.0,
-                                    color: Color::WHITE,
-                                    ..default()
-                                }
-                            )
-                        );
-                    });
-                });
-                
-                // Content area for the selected tab
+                tabs::spawn_tab_bar(parent, &tabs, &asset_server);
+
+                // Content area for the selected tab, filled in by the tab systems
                 parent.spawn((
                     NodeBundle {
                         style: Style {
                             width: Val::Percent(100.0),
                             height: Val::Percent(100.0),
                             flex_direction: FlexDirection::Column,
-                            padding: UiRect::all(Val::Px(20.0)),
                             ..default()
                         },
-                        background_color: colors::button::NORMAL.into(),
                         ..default()
                     },
-                )).with_children(|parent| {
-                    // Add video settings content by default using our custom spawn function
-                    spawn_video_settings_for_world_builder(parent, &asset_server);
-                });
-            });
-        });
-    }
-    
-    fn clone_box(&self) -> Box<dyn MenuItemPlugin> {
-        Box::new(self.clone())
-    }
-}
-
-/// Helper function to spawn video settings in a WorldChildBuilder context
-/// This adapter function converts between different builder types
-fn spawn_video_settings_for_world_builder(parent: &mut WorldChildBuilder, asset_server: &AssetServer) {
-    // Create a custom UI directly to avoid type conversion issues
-    // Implement the video settings UI inline here
-    parent.spawn(
-        NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            ..default()
-        }
-    ).with_children(|parent| {
-        // Section title
-        parent.spawn(
-            TextBundle::from_section(
-                "Video Settings",
-                TextStyle {
-                    font_size: 24.0,
-                    color: colors::WHITE,
-                    ..default()
-                }
-            ).with_style(Style {
-                margin: UiRect::vertical(Val::Px(20.0)),
-                ..default()
-            })
-        );
-        
-        // Add settings options directly
-        // Resolution setting
-        parent.spawn(NodeBundle {
-            style: Style {
-                width: Val::Percent(80.0),
-                height: Val::Px(40.0),
-                margin: UiRect::bottom(Val::Px(10.0)),
-                justify_content: JustifyContent::SpaceBetween,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            ..default()
-        }).with_children(|parent| {
-            // Label
-            parent.spawn(TextBundle::from_section(
-                "Resolution",
-                TextStyle {
-                    font_size: 18.0,
-                    color: colors::WHITE,
-                    ..default()
-                }
-            ));
-            
-            // Dropdown button
-            parent.spawn(ButtonBundle {
-                style: Style {
-                    width: Val::Px(180.0),
-                    height: Val::Px(30.0),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                background_color: colors::button::NORMAL.into(),
-                ..default()
-            }).with_children(|parent| {
-                parent.spawn(TextBundle::from_section(
-                    "1920x1080",
-                    TextStyle {
-                        font_size: 16.0,
-                        color: colors::WHITE,
-                        ..default()
-                    }
+                    ThemeElement::EmbeddedContent,
+                    SettingsTabContent,
                 ));
-            });
-        });
-    });
-}
-
-// Video settings UI is now directly implemented in spawn_video_settings_for_world_builder
 
-/// Helper function to spawn a settings row with a label and content
-fn spawn_setting_row<F>(parent: &mut ChildBuilder, label: &str, content_builder: F) 
-where
-    F: FnOnce(&mut ChildBuilder),
-{
-    parent.spawn(
-        NodeBundle {
-            style: Style {
-                width: Val::Percent(80.0),
-                height: Val::Px(60.0),
-                flex_direction: FlexDirection::Row,
-                justify_content: JustifyContent::SpaceBetween,
-                align_items: AlignItems::Center,
-                margin: UiRect::vertical(Val::Px(5.0)),
-                padding: UiRect::horizontal(Val::Px(20.0)),
-                ..default()
-            },
-            background_color: Color::srgb(0.2, 0.2, 0.2).into(),
-            ..default()
-        }
-    ).with_children(|parent| {
-        // Setting label
-        parent.spawn(
-            TextBundle::from_section(
-                label,
-                TextStyle {
-                    font_size: 18.0,
-                    color: Color::WHITE,
-                    ..default()
-                }
-            )
-        );
-        
-        // Setting control - built by the provided function
-        content_builder(parent);
-    });
-}
-
-#[derive(Component)]
-struct SettingsButtonMarker;
-
-fn spawn_settings_button_for_marked_entities(
-    mut commands: Commands,
-    query: Query<Entity, With<SettingsButtonMarker>>,
-) {
-    for entity in &query {
-        commands.entity(entity).with_children(|parent| {
-            parent.spawn((
-                ButtonBundle {
-                    style: Style {
-                        width: Val::Px(200.0),
-                        height: Val::Px(50.0),
-                        margin: UiRect::all(Val::Px(10.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    ..default()
-                },
-                Focusable {
-                    state: FocusState::NotFocused,
-                    focus_type: FocusableType::Button,
-                },
-                Name::new("SettingsButton"),
-            )).with_children(|parent| {
-                parent.spawn(TextBundle::from_section(
-                    "Settings",
-                    TextStyle {
-                        font_size: 24.0,
-                        ..default()
-                    }
-                ));
+                history::spawn_history_hint(parent);
+                accessibility::spawn_settings_announcer(parent);
             });
         });
-        
-        // Remove the marker after processing
-        commands.entity(entity).remove::<SettingsButtonMarker>();
-    }
-}
-
-// Make Settings implement Clone
-#[derive(Clone)]
-pub struct SettingsWrapper(pub settings::Settings);
-
-// Also make Settings implement the new MenuItemPlugin trait
-impl MenuItemPlugin for SettingsWrapper {
-    fn menu_label(&self) -> &'static str {
-        self.menu_name()
-    }
-    fn menu_name(&self) -> &'static str {
-        "Settings"
-    }
-    
-
-    
-    fn on_selected(&self, world: &mut World, content_entity: Entity) {
-        // Use the same implementation as SettingsMenuPlugin
-        let settings_plugin = SettingsMenuPlugin::default();
-        settings_plugin.on_selected(world, content_entity);
+        queue.apply(world);
     }
     
     fn clone_box(&self) -> Box<dyn MenuItemPlugin> {
@@ -483,171 +416,54 @@ impl MenuItemPlugin for SettingsWrapper {
     }
 }
 
-#[derive(Default)]
-pub struct GameSettings {
-    pub audio: AudioSettings,
-    pub video: VideoSettings,
-    pub controls: ControlsSettings,
-    pub gameplay: GameplaySettings,
-    pub interface: InterfaceSettings,
-}
-
-fn setup_settings_button(
-    commands: &mut Commands,
-    parent: Entity,
+/// Spawn the standalone settings panel, shown as an overlay while
+/// [`SettingsState::is_visible`] is set
+fn setup_settings_menu(
+    mut commands: Commands,
+    tabs: Res<SettingsTabs>,
+    asset_server: Res<AssetServer>,
 ) {
-    commands.entity(parent).with_children(|parent| {
-        parent.spawn((
-            ButtonBundle {
-                style: Style {
-                    width: Val::Px(200.0),
-                    height: Val::Px(50.0),
-                    margin: UiRect::all(Val::Px(10.0)),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                ..default()
-            },
-            Focusable {
-                state: FocusState::NotFocused,
-                focus_type: FocusableType::Button,
-            },
-            Name::new("SettingsButton"),
-        )).with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "Settings",
-                TextStyle {
-                    font_size: 24.0,
-                    ..default()
-                }
-            ));
-        });
-    });
-}
-
-fn setup_settings_menu(mut commands: Commands) {
     commands.spawn((
         NodeBundle {
             style: Style {
                 width: Val::Percent(80.0),
                 height: Val::Percent(80.0),
+                left: Val::Percent(10.0),
+                top: Val::Percent(10.0),
                 position_type: PositionType::Absolute,
                 flex_direction: FlexDirection::Column,
                 ..default()
             },
-            background_color: Color::srgba(0.1, 0.1, 0.1, 0.9).into(),
             visibility: Visibility::Hidden,
+            // Above the game's HUD
+            z_index: ZIndex::Global(100),
             ..default()
         },
+        ThemeElement::Panel,
         Name::new("SettingsPanel"),
+        SettingsPanel,
+        SettingsMenuEntity,
     )).with_children(|parent| {
         // Add tab navigation for settings categories
-        parent.spawn((
-            NodeBundle {
-                style: Style {
-                    width: Val::Percent(100.0),
-                    height: Val::Px(50.0),
-                    flex_direction: FlexDirection::Row,
-                    ..default()
-                },
-                ..default()
-            },
-            Name::new("SettingsTabs"),
-        )).with_children(|parent| {
-            // Video tab
-            parent.spawn((
-                ButtonBundle {
-                    style: Style {
-                        width: Val::Percent(33.0),
-                        height: Val::Percent(100.0),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Name::new("VideoTab"),
-            )).with_children(|parent| {
-                parent.spawn(TextBundle::from_section(
-                    "Video",
-                    TextStyle {
-                        font_size: 20.0,
-                        ..default()
-                    },
-                ));
-            });
+        tabs::spawn_tab_bar(parent, &tabs, &asset_server);
 
-            // Audio tab
-            parent.spawn((
-                ButtonBundle {
-                    style: Style {
-                        width: Val::Percent(33.0),
-                        height: Val::Percent(100.0),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Name::new("AudioTab"),
-            )).with_children(|parent| {
-                parent.spawn(TextBundle::from_section(
-                    "Audio",
-                    TextStyle {
-                        font_size: 20.0,
-                        ..default()
-                    },
-                ));
-            });
-
-            // Controls tab
-            parent.spawn((
-                ButtonBundle {
-                    style: Style {
-                        width: Val::Percent(33.0),
-                        height: Val::Percent(100.0),
-                        ..default()
-                    },
-                    ..default()
-                },
-                Name::new("ControlsTab"),
-            )).with_children(|parent| {
-                parent.spawn(TextBundle::from_section(
-                    "Controls",
-                    TextStyle {
-                        font_size: 20.0,
-                        ..default()
-                    },
-                ));
-            });
-        });
-
-        // Settings content area
+        // Settings content area, filled in by the tab systems
         parent.spawn((
             NodeBundle {
                 style: Style {
                     width: Val::Percent(100.0),
                     height: Val::Percent(90.0),
                     flex_direction: FlexDirection::Column,
-                    padding: UiRect::all(Val::Px(20.0)),
                     ..default()
                 },
                 ..default()
             },
+            ThemeElement::Content,
             Name::new("SettingsContent"),
+            SettingsTabContent,
         ));
-    });
-}
 
-fn settings_menu_system() {
-    // Implementation placeholder
-}
-
-fn video_settings_system() {
-    // Implementation placeholder
-}
-
-fn audio_settings_system() {
-    // Implementation placeholder
-}
-
-fn controls_settings_system() {
-    // Implementation placeholder
+        history::spawn_history_hint(parent);
+        accessibility::spawn_settings_announcer(parent);
+    });
 }
@@ -0,0 +1,627 @@
+//! Localization of the settings menu
+//!
+//! Menu strings come from Fluent message files in
+//! `assets/locales/<language>/settings.ftl`, embedded at compile time like the
+//! keymap presets. [`InterfaceSettings::language`] picks the language. Messages
+//! missing from it fall back to English, and strings without any message, such
+//! as plugin sections nobody translated, to the English text given in code.
+//!
+//! Message ids follow the settings they describe:
+//!
+//! - `setting-<path>` labels the setting at `path`, with `.` and `_` written as
+//!   `-`, e.g. `setting-audio-music-volume`
+//! - `setting-<path>-value` optionally formats the setting's value from
+//!   `$value`, e.g. `{ $value } FPS`, and `setting-<path>-none` names an unset
+//!   optional value
+//! - `<enum>-<variant>` names enum values, e.g. `display-mode-fullscreen`
+//! - `tab-<tab>` and `section-<id>` label tabs and plugin sections
+//! - the fixed strings in [`keys`]
+//!
+//! Numbers are passed to Fluent as numbers, so messages can select plural
+//! forms, and are shown with the precision of the setting's step and the
+//! language's decimal separator.
+//!
+//! Text with a [`LocalizedText`] component is relabelled whenever the language
+//! changes. Plugins can translate their own sections with
+//! [`Localization::add_messages`].
+//!
+//! [`InterfaceSettings::language`]: crate::settings::InterfaceSettings::language
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy::reflect::{Enum, ReflectRef, TypeInfo, Typed, VariantInfo};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::types::{FluentNumber, FluentNumberOptions};
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::keymap::KeymapPreset;
+use crate::registry;
+use crate::settings::{Settings, SettingsTab};
+use crate::widgets::{format_value, SettingRange};
+
+/// Ids of the fixed menu strings
+pub mod keys {
+    pub const MENU_ITEM: &str = "menu-settings";
+    pub const MENU_TITLE: &str = "menu-title";
+    pub const VIDEO_TITLE: &str = "title-video";
+    pub const AUDIO_TITLE: &str = "title-audio";
+    pub const GAMEPLAY_TITLE: &str = "title-gameplay";
+    pub const CONTROLS_TITLE: &str = "title-controls";
+    pub const INTERFACE_TITLE: &str = "title-interface";
+    pub const TEST_BUTTON: &str = "button-test";
+
+    pub const KEYMAP_PRESET: &str = "keymap-preset";
+    pub const KEYMAP_IMPORT: &str = "keymap-import";
+    pub const KEYMAP_EXPORT: &str = "keymap-export";
+    /// `$preset`
+    pub const KEYMAP_APPLIED: &str = "keymap-applied";
+    /// `$preset`
+    pub const KEYMAP_PRESET_FAILED: &str = "keymap-preset-failed";
+    /// `$name` and the number of `$ignored` entries
+    pub const KEYMAP_IMPORTED: &str = "keymap-imported";
+    /// `$error`
+    pub const KEYMAP_IMPORT_FAILED: &str = "keymap-import-failed";
+    /// `$path`
+    pub const KEYMAP_EXPORTED: &str = "keymap-exported";
+    /// `$error`
+    pub const KEYMAP_EXPORT_FAILED: &str = "keymap-export-failed";
+
+    pub const VALUE_ON: &str = "value-on";
+    pub const VALUE_OFF: &str = "value-off";
+    /// `$value`, a plain number in the language's format
+    pub const VALUE_NUMBER: &str = "value-number";
+    /// `$width` and `$height`
+    pub const VALUE_SIZE: &str = "value-size";
+    pub const VALUE_NONE: &str = "value-none";
+
+    /// `$change`
+    pub const HISTORY_UNDO: &str = "history-undo";
+    /// `$change`
+    pub const HISTORY_REDO: &str = "history-redo";
+    /// `$setting`, `$from` and `$to`
+    pub const HISTORY_EDIT: &str = "history-edit";
+    /// `$setting` and the number of `$more` settings
+    pub const HISTORY_EDITS: &str = "history-edits";
+
+    pub const VALIDATION_NOT_A_NUMBER: &str = "validation-not-a-number";
+    /// `$value` and `$min`
+    pub const VALIDATION_BELOW_MINIMUM: &str = "validation-below-minimum";
+    /// `$value` and `$max`
+    pub const VALIDATION_ABOVE_MAXIMUM: &str = "validation-above-maximum";
+    /// `$width` and `$height`
+    pub const VALIDATION_UNSUPPORTED_RESOLUTION: &str = "validation-unsupported-resolution";
+    /// `$min`
+    pub const VALIDATION_ZOOM_ORDER: &str = "validation-zoom-order";
+    /// `$outer`
+    pub const VALIDATION_DEAD_ZONE_ORDER: &str = "validation-dead-zone-order";
+
+    /// `$setting`
+    pub const CONTROL_DECREASE: &str = "control-decrease";
+    /// `$setting`
+    pub const CONTROL_INCREASE: &str = "control-increase";
+    /// `$setting` and `$value`
+    pub const ANNOUNCE_CHANGE: &str = "announce-change";
+
+    pub const HUD_HEADING: &str = "hud-heading";
+
+    pub const DIFFICULTY_CUSTOM_HEADING: &str = "difficulty-custom-heading";
+    pub const DIFFICULTY_LOCKED: &str = "difficulty-locked";
+
+    pub const TUTORIAL_DISMISS: &str = "tutorial-dismiss";
+    pub const TUTORIAL_RESET: &str = "tutorial-reset";
+
+    pub const ALL: &[&str] = &[
+        MENU_ITEM,
+        MENU_TITLE,
+        VIDEO_TITLE,
+        AUDIO_TITLE,
+        GAMEPLAY_TITLE,
+        CONTROLS_TITLE,
+        INTERFACE_TITLE,
+        TEST_BUTTON,
+        KEYMAP_PRESET,
+        KEYMAP_IMPORT,
+        KEYMAP_EXPORT,
+        KEYMAP_APPLIED,
+        KEYMAP_PRESET_FAILED,
+        KEYMAP_IMPORTED,
+        KEYMAP_IMPORT_FAILED,
+        KEYMAP_EXPORTED,
+        KEYMAP_EXPORT_FAILED,
+        VALUE_ON,
+        VALUE_OFF,
+        VALUE_NUMBER,
+        VALUE_SIZE,
+        VALUE_NONE,
+        HISTORY_UNDO,
+        HISTORY_REDO,
+        HISTORY_EDIT,
+        HISTORY_EDITS,
+        VALIDATION_NOT_A_NUMBER,
+        VALIDATION_BELOW_MINIMUM,
+        VALIDATION_ABOVE_MAXIMUM,
+        VALIDATION_UNSUPPORTED_RESOLUTION,
+        VALIDATION_ZOOM_ORDER,
+        VALIDATION_DEAD_ZONE_ORDER,
+        CONTROL_DECREASE,
+        CONTROL_INCREASE,
+        ANNOUNCE_CHANGE,
+        HUD_HEADING,
+        DIFFICULTY_CUSTOM_HEADING,
+        DIFFICULTY_LOCKED,
+        TUTORIAL_DISMISS,
+        TUTORIAL_RESET,
+    ];
+}
+
+/// Languages shipped with the menu
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::English, Language::German, Language::French, Language::Spanish];
+
+    /// BCP 47 language tag, also the directory of its message file
+    pub fn id(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::German => "de-DE",
+            Language::French => "fr-FR",
+            Language::Spanish => "es-ES",
+        }
+    }
+
+    /// The shipped message file
+    pub fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en-US/settings.ftl"),
+            Language::German => include_str!("../assets/locales/de-DE/settings.ftl"),
+            Language::French => include_str!("../assets/locales/fr-FR/settings.ftl"),
+            Language::Spanish => include_str!("../assets/locales/es-ES/settings.ftl"),
+        }
+    }
+
+    /// Ids of the messages in the shipped message file
+    pub fn message_ids(self) -> Vec<&'static str> {
+        // Messages start at the beginning of a line; terms start with `-`,
+        // comments with `#` and continuation lines are indented
+        self.source()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('='))
+            .map(|(id, _)| id.trim())
+            .collect()
+    }
+
+    fn uses_decimal_comma(self) -> bool {
+        matches!(self, Language::German | Language::French | Language::Spanish)
+    }
+}
+
+/// A message id with its arguments, formatted in the current language when shown
+///
+/// As a component it keeps the entity's text in the current language.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct LocalizedText {
+    pub id: String,
+    pub args: Vec<(String, MessageArg)>,
+    /// Shown when no language has the message
+    pub fallback: String,
+}
+
+/// Argument of a [`LocalizedText`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MessageArg {
+    Text(String),
+    /// Shown with a fixed number of decimals
+    Number { value: f64, decimals: usize },
+    /// Another message, formatted in the same language
+    Message(Box<LocalizedText>),
+}
+
+impl From<String> for MessageArg {
+    fn from(text: String) -> Self {
+        MessageArg::Text(text)
+    }
+}
+
+impl From<&str> for MessageArg {
+    fn from(text: &str) -> Self {
+        MessageArg::Text(text.to_string())
+    }
+}
+
+impl From<LocalizedText> for MessageArg {
+    fn from(text: LocalizedText) -> Self {
+        MessageArg::Message(Box::new(text))
+    }
+}
+
+impl From<usize> for MessageArg {
+    fn from(value: usize) -> Self {
+        MessageArg::Number { value: value as f64, decimals: 0 }
+    }
+}
+
+impl From<u32> for MessageArg {
+    fn from(value: u32) -> Self {
+        MessageArg::Number { value: value.into(), decimals: 0 }
+    }
+}
+
+impl LocalizedText {
+    pub fn new(id: impl Into<String>) -> Self {
+        let id = id.into();
+        Self {
+            fallback: id.clone(),
+            id,
+            args: Vec::new(),
+        }
+    }
+
+    /// Label of the setting at `path`, `fallback` when it has no message
+    pub fn setting(path: &str, fallback: impl Into<String>) -> Self {
+        Self::new(setting_id(path)).with_fallback(fallback)
+    }
+
+    /// Name of an enum value, e.g. `display-mode-fullscreen`
+    pub fn variant(value: &dyn Enum) -> Self {
+        Self::new(variant_id(value.reflect_short_type_path(), value.variant_name())).with_fallback(value.variant_name())
+    }
+
+    pub fn with_fallback(mut self, fallback: impl Into<String>) -> Self {
+        self.fallback = fallback.into();
+        self
+    }
+
+    pub fn with_arg(mut self, name: &str, value: impl Into<MessageArg>) -> Self {
+        self.args.push((name.to_string(), value.into()));
+        self
+    }
+}
+
+/// English, for logs
+impl fmt::Display for LocalizedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        static ENGLISH: OnceLock<Localization> = OnceLock::new();
+        f.write_str(&ENGLISH.get_or_init(Localization::shipped).text(self))
+    }
+}
+
+type LanguageBundle = FluentBundle<FluentResource>;
+
+/// Message bundles of every shipped language and the selected language
+#[derive(Resource)]
+pub struct Localization {
+    language: Language,
+    bundles: Vec<(Language, LanguageBundle)>,
+    errors: Vec<String>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self::shipped()
+    }
+}
+
+impl Localization {
+    /// Load the shipped message files, logging any syntax errors
+    pub fn shipped() -> Self {
+        let mut errors = Vec::new();
+        let bundles = Language::ALL
+            .into_iter()
+            .map(|language| (language, build_bundle(language, &mut errors)))
+            .collect();
+        for error in &errors {
+            warn!("Invalid settings message: {}", error);
+        }
+        Self {
+            language: Language::English,
+            bundles,
+            errors,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
+
+    /// Syntax errors found in the message files
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Add Fluent messages for `language`, replacing existing ones with the same id
+    pub fn add_messages(&mut self, language: Language, source: &str) {
+        if let Some((_, bundle)) = self.bundles.iter_mut().find(|(bundle_language, _)| *bundle_language == language) {
+            add_source(bundle, language, source.to_string(), &mut self.errors);
+        }
+    }
+
+    pub fn has_message(&self, language: Language, id: &str) -> bool {
+        self.bundle(language).is_some_and(|bundle| bundle.has_message(id))
+    }
+
+    /// `text` in the current language, or English, or its fallback
+    pub fn text(&self, text: &LocalizedText) -> String {
+        let mut args = FluentArgs::new();
+        for (name, value) in &text.args {
+            match value {
+                MessageArg::Text(value) => args.set(name.clone(), value.clone()),
+                MessageArg::Number { value, decimals } => args.set(name.clone(), fluent_number(*value, *decimals)),
+                MessageArg::Message(message) => args.set(name.clone(), self.text(message)),
+            }
+        }
+
+        [self.language, Language::English]
+            .into_iter()
+            .filter_map(|language| self.bundle(language))
+            .find_map(|bundle| format_message(bundle, &text.id, &args))
+            .unwrap_or_else(|| text.fallback.clone())
+    }
+
+    /// The message `id` without arguments
+    pub fn message(&self, id: &str) -> String {
+        self.text(&LocalizedText::new(id))
+    }
+
+    /// Label of the setting at `path`
+    pub fn setting_label(&self, path: &str, fallback: &str) -> String {
+        self.text(&LocalizedText::setting(path, fallback))
+    }
+
+    /// Display string for the value of the setting at `path`
+    ///
+    /// Fractional numbers get as many decimals as the step of `range`, or two
+    /// without one.
+    pub fn format_value(&self, path: &str, value: &dyn Reflect, range: Option<SettingRange>) -> String {
+        if let Some(value) = value.downcast_ref::<bool>() {
+            return self.message(if *value { keys::VALUE_ON } else { keys::VALUE_OFF });
+        }
+        if let Some(&(width, height)) = value.downcast_ref::<(u32, u32)>() {
+            return self.text(&LocalizedText::new(keys::VALUE_SIZE).with_arg("width", width).with_arg("height", height));
+        }
+
+        let number = if let Some(value) = value.downcast_ref::<f32>() {
+            let decimals = range.map_or(2, |range| range.decimals());
+            MessageArg::Number { value: (*value).into(), decimals }
+        } else if let Some(value) = value.downcast_ref::<u32>() {
+            (*value).into()
+        } else if let Some(value) = value.downcast_ref::<Option<u32>>() {
+            match value {
+                Some(value) => (*value).into(),
+                None => {
+                    let none = self.message(keys::VALUE_NONE);
+                    return self.text(&LocalizedText::new(format!("{}-none", setting_id(path))).with_fallback(none));
+                }
+            }
+        } else if let ReflectRef::Enum(value) = value.reflect_ref() {
+            return self.text(&LocalizedText::variant(value));
+        } else {
+            return format_value(value);
+        };
+
+        let plain = self.text(&LocalizedText::new(keys::VALUE_NUMBER).with_arg("value", number.clone()));
+        self.text(
+            &LocalizedText::new(format!("{}-value", setting_id(path)))
+                .with_arg("value", number)
+                .with_fallback(plain),
+        )
+    }
+
+    fn bundle(&self, language: Language) -> Option<&LanguageBundle> {
+        self.bundles
+            .iter()
+            .find(|(bundle_language, _)| *bundle_language == language)
+            .map(|(_, bundle)| bundle)
+    }
+}
+
+/// Systems keeping [`LocalizedText`] in the current language, in `PostUpdate` before UI layout
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocalizationSet;
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Localization>()
+            .register_type::<Language>()
+            .add_systems(
+                PostUpdate,
+                (sync_language, relabel_localized_text)
+                    .chain()
+                    .in_set(LocalizationSet)
+                    .before(bevy::ui::UiSystem::Layout),
+            );
+    }
+}
+
+/// Message id of the label of the setting at `path`
+pub fn setting_id(path: &str) -> String {
+    format!("setting-{}", path.replace(['.', '_'], "-"))
+}
+
+/// Message id of a tab's label
+pub fn tab_id(tab: &SettingsTab) -> String {
+    format!("tab-{}", kebab_case(tab.label()))
+}
+
+/// Message id of a plugin section's heading
+pub fn section_id(id: &str) -> String {
+    format!("section-{}", id.replace(['.', '_'], "-"))
+}
+
+/// Message id of an enum value, e.g. `display-mode-fullscreen` for `DisplayMode::Fullscreen`
+pub fn variant_id(enum_name: &str, variant: &str) -> String {
+    format!("{}-{}", kebab_case(enum_name), kebab_case(variant))
+}
+
+/// Label of the setting at `path` in the current language
+pub fn setting_label(world: &World, path: &str, fallback: &str) -> String {
+    match world.get_resource::<Localization>() {
+        Some(localization) => localization.setting_label(path, fallback),
+        None => fallback.to_string(),
+    }
+}
+
+/// Value of the setting at `path` in the current language, see [`Localization::format_value`]
+pub fn format_setting(world: &World, path: &str, value: &dyn Reflect) -> String {
+    match world.get_resource::<Localization>() {
+        Some(localization) => {
+            let range = registry::read_field_info(world, path, |field| field.get_attribute::<SettingRange>().copied())
+                .flatten();
+            localization.format_value(path, value, range)
+        }
+        None => format_value(value),
+    }
+}
+
+/// Ids every shipped language has to define
+///
+/// These are the fixed strings, the built-in tabs, a label for every built-in
+/// setting and a name for every value of this crate's enums.
+pub fn required_messages() -> Vec<String> {
+    let mut ids: Vec<String> = keys::ALL.iter().map(|id| id.to_string()).collect();
+    if let TypeInfo::Enum(tabs) = SettingsTab::type_info() {
+        ids.extend(
+            tabs.iter()
+                .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+                .map(|variant| format!("tab-{}", kebab_case(variant.name()))),
+        );
+    }
+    collect_setting_ids("", &Settings::default(), &mut ids);
+    collect_variant_ids(KeymapPreset::type_info(), &mut ids);
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+fn collect_setting_ids(prefix: &str, value: &dyn Reflect, ids: &mut Vec<String>) {
+    let ReflectRef::Struct(value) = value.reflect_ref() else {
+        return;
+    };
+    for (index, field) in value.iter_fields().enumerate() {
+        let Some(name) = value.name_at(index) else {
+            continue;
+        };
+        let path = if prefix.is_empty() { name.to_string() } else { format!("{prefix}.{name}") };
+        if let ReflectRef::Struct(_) = field.reflect_ref() {
+            collect_setting_ids(&path, field, ids);
+            continue;
+        }
+        ids.push(setting_id(&path));
+        if let Some(info) = field.get_represented_type_info() {
+            collect_variant_ids(info, ids);
+        }
+    }
+}
+
+/// Value names of enums defined in this crate; `KeyCode` and `Option` are shown as they are
+fn collect_variant_ids(info: &TypeInfo, ids: &mut Vec<String>) {
+    let TypeInfo::Enum(info) = info else {
+        return;
+    };
+    if !info.type_path().starts_with(concat!(env!("CARGO_CRATE_NAME"), "::")) {
+        return;
+    }
+    let name = info.type_path_table().short_path();
+    ids.extend(info.iter().map(|variant| variant_id(name, variant.name())));
+}
+
+/// "DisplayMode" -> "display-mode", "Left stick" -> "left-stick"
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !kebab.is_empty() && !kebab.ends_with('-') {
+            kebab.push('-');
+        }
+        if c.is_alphanumeric() {
+            kebab.extend(c.to_lowercase());
+        } else if !kebab.ends_with('-') {
+            kebab.push('-');
+        }
+    }
+    kebab
+}
+
+fn build_bundle(language: Language, errors: &mut Vec<String>) -> LanguageBundle {
+    let id: LanguageIdentifier = language.id().parse().expect("shipped language ids are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Bidi isolation marks render as boxes in most game fonts
+    bundle.set_use_isolating(false);
+    if language.uses_decimal_comma() {
+        bundle.set_formatter(Some(format_decimal_comma));
+    }
+    add_source(&mut bundle, language, language.source().to_string(), errors);
+    bundle
+}
+
+fn add_source(bundle: &mut LanguageBundle, language: Language, source: String, errors: &mut Vec<String>) {
+    let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, parse_errors)| {
+        errors.extend(parse_errors.iter().map(|error| format!("{}: {}", language.id(), error)));
+        resource
+    });
+    bundle.add_resource_overriding(resource);
+}
+
+fn format_message(bundle: &LanguageBundle, id: &str, args: &FluentArgs) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(bundle.format_pattern(pattern, Some(args), &mut errors).into_owned())
+}
+
+fn fluent_number(value: f64, decimals: usize) -> FluentValue<'static> {
+    let scale = 10f64.powi(decimals as i32);
+    FluentValue::Number(FluentNumber::new(
+        (value * scale).round() / scale,
+        FluentNumberOptions {
+            // Fluent leaves a trailing separator for zero minimum digits
+            minimum_fraction_digits: (decimals > 0).then_some(decimals),
+            maximum_fraction_digits: Some(decimals),
+            ..default()
+        },
+    ))
+}
+
+fn format_decimal_comma<M>(value: &FluentValue, _: &M) -> Option<String> {
+    match value {
+        FluentValue::Number(number) => Some(number.as_string().replace('.', ",")),
+        _ => None,
+    }
+}
+
+fn sync_language(settings: Res<Settings>, mut localization: ResMut<Localization>) {
+    let language = settings.interface.language;
+    if localization.language != language {
+        localization.set_language(language);
+    }
+}
+
+fn relabel_localized_text(
+    localization: Res<Localization>,
+    mut texts: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    for (localized, mut text) in &mut texts {
+        if !localization.is_changed() && !localized.is_changed() {
+            continue;
+        }
+        let value = localization.text(&localized);
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
@@ -3,15 +3,62 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::ButtonInput;
 use bevy::ui::{Interaction, BackgroundColor};
 use sf_ui_common::components::{Focusable, FocusState};
-use sf_ui_common::colors::{
-    button::NORMAL as NORMAL_BUTTON,
-    text::NORMAL as TEXT_COLOR,
-};
+
+use crate::keymap::{self, KeymapPath, KeymapPreset};
+use crate::gamepad::MenuGamepadInput;
+use crate::fonts::FontRole;
+use crate::localization::{keys, Localization, LocalizedText};
+use crate::settings::{CameraSettings, ControllerSettings, ControlsSettings, Settings, StickSettings};
+use crate::widgets::{spawn_setting_control, ControlKind, SettingRange};
+use crate::theme::{SettingsTheme, ThemeElement};
 
 /// State for managing controls settings UI
 #[derive(Resource, Default)]
 pub struct ControlsSettingsState {
     pub focused_element: Option<Entity>,
+    /// Preset last applied, `None` once the keymap has been customised or imported
+    pub active_preset: Option<KeymapPreset>,
+    /// Result of the last preset/import/export action
+    pub keymap_status: Option<LocalizedText>,
+}
+
+/// Button that applies a keymap preset to the current keybinds
+#[derive(Component, Clone, Copy)]
+pub struct KeymapPresetButton(pub KeymapPreset);
+
+/// Buttons for moving the player's keymap in and out of a standalone file
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapFileButton {
+    Import,
+    Export,
+}
+
+/// Text showing the outcome of keymap actions
+#[derive(Component)]
+pub struct KeymapStatusText;
+
+/// Size of the stick visualizer plots in pixels
+const STICK_PLOT_SIZE: f32 = 120.0;
+const STICK_DOT_SIZE: f32 = 10.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stick {
+    Left,
+    Right,
+}
+
+/// Dot plotting a stick position, either straight from the gamepad or after processing
+#[derive(Component, Clone, Copy)]
+pub struct StickPlotDot {
+    pub stick: Stick,
+    pub processed: bool,
+}
+
+/// Ring marking a stick's inner or outer dead zone in the visualizer
+#[derive(Component, Clone, Copy)]
+pub struct StickDeadZoneRing {
+    pub stick: Stick,
+    pub outer: bool,
 }
 
 #[derive(Default)]
@@ -20,10 +67,15 @@ pub struct ControlsPlugin;
 impl Plugin for ControlsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ControlsSettingsState>()
+            .init_resource::<KeymapPath>()
             .add_systems(Update, (
                 handle_controls_settings_keyboard_navigation,
                 update_focus_visuals,
-                handle_interaction_feedback
+                handle_interaction_feedback,
+                handle_keymap_preset_buttons,
+                handle_keymap_file_buttons,
+                update_keymap_status_text,
+                update_stick_visualizer,
             ));
     }
 }
@@ -42,63 +94,380 @@ fn handle_controls_settings_keyboard_navigation(
 
 /// Update focus visuals for controls settings
 fn update_focus_visuals(
+    theme: Res<SettingsTheme>,
     mut query: Query<(&Focusable, &mut BackgroundColor), Changed<Focusable>>,
 ) {
-    use sf_ui_common::colors::{
-        button::{NORMAL, HOVERED, PRESSED},
-        focus::HIGHLIGHT,
-    };
-
+    let colors = &theme.colors;
     for (focusable, mut background_color) in &mut query {
         *background_color = match focusable.state {
-            FocusState::Focused => HIGHLIGHT.into(),
-            FocusState::Active => PRESSED.into(),
-            FocusState::NotFocused => NORMAL.into(),
+            FocusState::Focused => colors.focused.into(),
+            FocusState::Active => colors.button_pressed.into(),
+            FocusState::NotFocused => colors.button.into(),
         };
     }
 }
 
 /// Handle interaction feedback for controls settings
 fn handle_interaction_feedback(
+    theme: Res<SettingsTheme>,
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
 ) {
-    use sf_ui_common::colors::button::{NORMAL, HOVERED, PRESSED};
-
     for (interaction, mut color) in &mut interaction_query {
-        *color = match interaction {
-            Interaction::Pressed => PRESSED.into(),
-            Interaction::Hovered => HOVERED.into(),
-            Interaction::None => NORMAL.into(),
+        *color = theme.button_background(*interaction);
+    }
+}
+
+/// Apply the selected keymap preset to the player's keybinds
+fn handle_keymap_preset_buttons(
+    mut state: ResMut<ControlsSettingsState>,
+    mut settings: ResMut<Settings>,
+    query: Query<(&Interaction, &KeymapPresetButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let preset = button.0;
+        match preset.keybinds() {
+            Ok(import) => {
+                settings.controls.keybinds = import.keybinds;
+                state.active_preset = Some(preset);
+                state.keymap_status = Some(LocalizedText::new(keys::KEYMAP_APPLIED).with_arg("preset", preset_name(preset)));
+            }
+            Err(err) => {
+                error!("Failed to load keymap preset {:?}: {}", preset, err);
+                state.keymap_status =
+                    Some(LocalizedText::new(keys::KEYMAP_PRESET_FAILED).with_arg("preset", preset_name(preset)));
+            }
+        }
+    }
+}
+
+/// Import or export the player's keymap
+fn handle_keymap_file_buttons(
+    mut state: ResMut<ControlsSettingsState>,
+    mut settings: ResMut<Settings>,
+    path: Res<KeymapPath>,
+    query: Query<(&Interaction, &KeymapFileButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            KeymapFileButton::Import => {
+                match keymap::import_keymap(&path.0, &settings.controls.keybinds) {
+                    Ok(import) => {
+                        if !import.is_clean() {
+                            warn!("Keymap {:?} imported with problems: {}", path.0, import.report());
+                        }
+                        let ignored = import.unknown_actions.len() + import.unknown_keys.len();
+                        state.keymap_status = Some(
+                            LocalizedText::new(keys::KEYMAP_IMPORTED)
+                                .with_arg("name", import.name.as_str())
+                                .with_arg("ignored", ignored)
+                                .with_fallback(import.report()),
+                        );
+                        state.active_preset = None;
+                        settings.controls.keybinds = import.keybinds;
+                    }
+                    Err(err) => {
+                        warn!("Failed to import keymap from {:?}: {}", path.0, err);
+                        state.keymap_status =
+                            Some(LocalizedText::new(keys::KEYMAP_IMPORT_FAILED).with_arg("error", err.to_string()));
+                    }
+                }
+            }
+            KeymapFileButton::Export => {
+                let name = state
+                    .active_preset
+                    .map(|preset| preset.label())
+                    .unwrap_or("Custom");
+                state.keymap_status = Some(
+                    match keymap::export_keymap(&path.0, name, &settings.controls.keybinds) {
+                        Ok(()) => LocalizedText::new(keys::KEYMAP_EXPORTED).with_arg("path", path.0.display().to_string()),
+                        Err(err) => {
+                            warn!("Failed to export keymap to {:?}: {}", path.0, err);
+                            LocalizedText::new(keys::KEYMAP_EXPORT_FAILED).with_arg("error", err.to_string())
+                        }
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Mirror the last keymap action in the status line
+fn update_keymap_status_text(
+    state: Res<ControlsSettingsState>,
+    localization: Res<Localization>,
+    mut query: Query<&mut Text, With<KeymapStatusText>>,
+) {
+    if !state.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = state
+            .keymap_status
+            .as_ref()
+            .map(|status| localization.text(status))
+            .unwrap_or_default();
+    }
+}
+
+/// Name of a keymap preset in the current language
+fn preset_name(preset: KeymapPreset) -> LocalizedText {
+    LocalizedText::variant(&preset).with_fallback(preset.label())
+}
+
+/// Plot raw vs processed stick positions and the configured dead zones
+fn update_stick_visualizer(
+    input: Res<MenuGamepadInput>,
+    settings: Res<Settings>,
+    mut dots: Query<(&StickPlotDot, &mut Style)>,
+    mut rings: Query<(Ref<StickDeadZoneRing>, &mut Style), Without<StickPlotDot>>,
+) {
+    for (dot, mut style) in &mut dots {
+        let position = match (dot.stick, dot.processed) {
+            (Stick::Left, false) => input.0.raw_left_stick,
+            (Stick::Left, true) => input.0.left_stick,
+            (Stick::Right, false) => input.0.raw_right_stick,
+            (Stick::Right, true) => input.0.right_stick,
         };
+        style.left = Val::Percent(50.0 + position.x.clamp(-1.0, 1.0) * 50.0);
+        style.top = Val::Percent(50.0 - position.y.clamp(-1.0, 1.0) * 50.0);
+    }
+
+    let controller = &settings.controls.controller;
+    for (ring, mut style) in &mut rings {
+        if !settings.is_changed() && !ring.is_added() {
+            continue;
+        }
+
+        let stick = match ring.stick {
+            Stick::Left => &controller.left_stick,
+            Stick::Right => &controller.right_stick,
+        };
+        let radius = if ring.outer { stick.outer_dead_zone } else { stick.inner_dead_zone };
+        let radius = radius.clamp(0.0, 1.0);
+        style.width = Val::Percent(radius * 100.0);
+        style.height = Val::Percent(radius * 100.0);
+        style.left = Val::Percent(50.0 - radius * 50.0);
+        style.top = Val::Percent(50.0 - radius * 50.0);
     }
 }
 
-pub fn spawn_controls_settings(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
-    parent.spawn(TextBundle::from_section(
-        "Controls Settings", 
-        TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-            font_size: 24.0,
-            color: TEXT_COLOR,
-        })
-    );
+pub fn spawn_controls_settings(parent: &mut ChildBuilder, _asset_server: &Res<AssetServer>) {
+    parent.spawn((
+        TextBundle::from_section("Controls Settings", TextStyle::default()),
+        ThemeElement::Heading,
+        LocalizedText::new(keys::CONTROLS_TITLE).with_fallback("Controls Settings"),
+        FontRole::Bold,
+    ));
+
+    parent.spawn((ButtonBundle::default(), ThemeElement::ActionButton)).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Test", TextStyle::default()),
+            ThemeElement::ActionLabel,
+            LocalizedText::new(keys::TEST_BUTTON).with_fallback("Test"),
+            FontRole::Bold,
+        ));
+    });
+
+    // Mouse and camera
+    spawn_setting_control(parent, "Mouse sensitivity", "controls.mouse_sensitivity",
+        stepper::<ControlsSettings>("mouse_sensitivity"));
+    spawn_setting_control(parent, "Invert Y", "controls.invert_y", ControlKind::Toggle);
+    spawn_setting_control(parent, "Mouse dead zone", "controls.mouse_dead_zone",
+        stepper::<ControlsSettings>("mouse_dead_zone"));
+    spawn_setting_control(parent, "Edge scrolling", "controls.camera.edge_scroll", ControlKind::Toggle);
+    spawn_setting_control(parent, "Edge scroll margin", "controls.camera.edge_scroll_margin",
+        stepper::<CameraSettings>("edge_scroll_margin"));
+    spawn_setting_control(parent, "Edge scroll speed", "controls.camera.edge_scroll_speed",
+        stepper::<CameraSettings>("edge_scroll_speed"));
+    spawn_setting_control(parent, "Keyboard pan speed", "controls.camera.keyboard_pan_speed",
+        stepper::<CameraSettings>("keyboard_pan_speed"));
+    spawn_setting_control(parent, "Zoom speed", "controls.camera.zoom_speed",
+        stepper::<CameraSettings>("zoom_speed"));
+    spawn_setting_control(parent, "Minimum zoom", "controls.camera.min_zoom",
+        stepper::<CameraSettings>("min_zoom"));
+    spawn_setting_control(parent, "Maximum zoom", "controls.camera.max_zoom",
+        stepper::<CameraSettings>("max_zoom"));
+    spawn_setting_control(parent, "Middle mouse drag pan", "controls.camera.middle_mouse_pan", ControlKind::Toggle);
+    spawn_setting_control(parent, "Lock cursor to window", "controls.camera.lock_cursor", ControlKind::Toggle);
+
+    // Controller
+    for (name, path) in [("Left stick", "controls.controller.left_stick"), ("Right stick", "controls.controller.right_stick")] {
+        spawn_setting_control(parent, &format!("{name} inner dead zone"), &format!("{path}.inner_dead_zone"),
+            stepper::<StickSettings>("inner_dead_zone"));
+        spawn_setting_control(parent, &format!("{name} outer dead zone"), &format!("{path}.outer_dead_zone"),
+            stepper::<StickSettings>("outer_dead_zone"));
+        spawn_setting_control(parent, &format!("{name} response curve"), &format!("{path}.response_curve"),
+            ControlKind::Cycle);
+        spawn_setting_control(parent, &format!("{name} custom curve exponent"), &format!("{path}.custom_exponent"),
+            stepper::<StickSettings>("custom_exponent"));
+    }
+    spawn_setting_control(parent, "Left trigger threshold", "controls.controller.left_trigger_threshold",
+        stepper::<ControllerSettings>("left_trigger_threshold"));
+    spawn_setting_control(parent, "Right trigger threshold", "controls.controller.right_trigger_threshold",
+        stepper::<ControllerSettings>("right_trigger_threshold"));
+    spawn_setting_control(parent, "Gamepad cursor speed", "controls.controller.cursor_speed",
+        stepper::<ControllerSettings>("cursor_speed"));
+    spawn_setting_control(parent, "Rumble strength", "controls.controller.rumble_strength",
+        stepper::<ControllerSettings>("rumble_strength"));
+
+    // Live stick visualizer
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ..default()
+        },
+        ThemeElement::Group,
+    )).with_children(|parent| {
+        spawn_stick_plot(parent, Stick::Left);
+        spawn_stick_plot(parent, Stick::Right);
+    });
+
+    // Keymap preset selector
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        ThemeElement::Group,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Keymap preset", TextStyle::default()),
+            ThemeElement::Label,
+            LocalizedText::new(keys::KEYMAP_PRESET).with_fallback("Keymap preset"),
+            FontRole::Bold,
+        ));
+
+        for preset in KeymapPreset::ALL {
+            spawn_keymap_button(parent, preset_name(preset), KeymapPresetButton(preset));
+        }
+    });
 
-    parent.spawn(ButtonBundle {
+    // Import/export of the player's own keymap
+    parent.spawn(NodeBundle {
         style: Style {
-            width: Val::Px(150.0),
-            height: Val::Px(65.0),
-            margin: UiRect::all(Val::Px(10.0)),
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
             ..default()
         },
-        background_color: NORMAL_BUTTON.into(),
         ..default()
     }).with_children(|parent| {
-        parent.spawn(TextBundle::from_section("Test", 
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 20.0,
-                color: TEXT_COLOR,
-            })
-        );
+        spawn_keymap_button(parent, LocalizedText::new(keys::KEYMAP_IMPORT).with_fallback("Import"),
+            KeymapFileButton::Import);
+        spawn_keymap_button(parent, LocalizedText::new(keys::KEYMAP_EXPORT).with_fallback("Export"),
+            KeymapFileButton::Export);
     });
-}
\ No newline at end of file
+
+    parent.spawn((
+        TextBundle::from_section("", TextStyle::default()),
+        ThemeElement::SmallText,
+        KeymapStatusText,
+        FontRole::Regular,
+    ));
+}
+
+/// Stepper using the [`SettingRange`] declared on `field` of `T`
+fn stepper<T: bevy::reflect::Typed>(field: &str) -> ControlKind {
+    SettingRange::of::<T>(field).unwrap_or_default().into()
+}
+
+fn spawn_keymap_button(
+    parent: &mut ChildBuilder,
+    label: LocalizedText,
+    marker: impl Component,
+) {
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        ThemeElement::SmallButton,
+        marker,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(label.fallback.clone(), TextStyle::default()),
+            ThemeElement::SmallText,
+            label,
+            FontRole::Bold,
+        ));
+    });
+}
+
+/// Square plot showing a stick's raw (grey) and processed (green) position
+fn spawn_stick_plot(parent: &mut ChildBuilder, stick: Stick) {
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Px(STICK_PLOT_SIZE),
+                height: Val::Px(STICK_PLOT_SIZE),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        ThemeElement::StickPlot,
+        Name::new(format!("{:?}StickVisualizer", stick)),
+    )).with_children(|parent| {
+        // Sized from the current settings by update_stick_visualizer
+        for outer in [false, true] {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                ThemeElement::DeadZoneRing,
+                StickDeadZoneRing { stick, outer },
+            ));
+        }
+
+        for processed in [false, true] {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(STICK_DOT_SIZE),
+                        height: Val::Px(STICK_DOT_SIZE),
+                        left: Val::Percent(50.0),
+                        top: Val::Percent(50.0),
+                        // Centre the dot on its position
+                        margin: UiRect {
+                            left: Val::Px(-STICK_DOT_SIZE / 2.0),
+                            top: Val::Px(-STICK_DOT_SIZE / 2.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                ThemeElement::StickDot { processed },
+                StickPlotDot { stick, processed },
+            ));
+        }
+    });
+}
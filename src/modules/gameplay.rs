@@ -3,10 +3,14 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::ButtonInput;
 use bevy::ui::{Interaction, BackgroundColor};
 use sf_ui_common::components::{Focusable, FocusState};
-use sf_ui_common::colors::{
-    button::NORMAL as NORMAL_BUTTON,
-    text::NORMAL as TEXT_COLOR,
-};
+
+use crate::difficulty::{DifficultyModifiers, MatchInProgress};
+use crate::fonts::FontRole;
+use crate::localization::{keys, LocalizedText};
+use crate::settings::{AutosaveSettings, GameplaySettings, Settings, SubtitleStyle};
+use crate::tutorials::SeenTutorialHints;
+use crate::widgets::{spawn_setting_control, ControlKind, SettingRange};
+use crate::theme::{SettingsTheme, ThemeElement};
 
 /// State for managing gameplay settings UI
 #[derive(Resource, Default)]
@@ -14,6 +18,14 @@ pub struct GameplaySettingsState {
     pub focused_element: Option<Entity>,
 }
 
+/// Note under the difficulty settings shown while they are locked
+#[derive(Component)]
+pub struct DifficultyLockedHint;
+
+/// Button that clears the seen tutorial hints so they are shown again
+#[derive(Component)]
+pub struct ResetSeenHintsButton;
+
 #[derive(Default)]
 pub struct GameplayPlugin;
 
@@ -23,7 +35,9 @@ impl Plugin for GameplayPlugin {
             .add_systems(Update, (
                 handle_gameplay_settings_keyboard_navigation,
                 update_focus_visuals,
-                handle_interaction_feedback
+                handle_interaction_feedback,
+                handle_reset_seen_hints_button,
+                show_difficulty_locked_hint,
             ));
     }
 }
@@ -44,63 +58,139 @@ fn handle_gameplay_settings_keyboard_navigation(
 
 /// Update focus visuals for gameplay settings
 fn update_focus_visuals(
+    theme: Res<SettingsTheme>,
     mut query: Query<(&Focusable, &mut BackgroundColor), Changed<Focusable>>,
 ) {
-    use sf_ui_common::colors::{
-        button::{NORMAL, HOVERED, PRESSED},
-        focus::HIGHLIGHT,
-    };
-
+    let colors = &theme.colors;
     for (focusable, mut background_color) in &mut query {
         *background_color = match focusable.state {
-            FocusState::Focused => HIGHLIGHT.into(),
-            FocusState::Active => PRESSED.into(),
-            FocusState::NotFocused => NORMAL.into(),
+            FocusState::Focused => colors.focused.into(),
+            FocusState::Active => colors.button_pressed.into(),
+            FocusState::NotFocused => colors.button.into(),
         };
     }
 }
 
 /// Handle interaction feedback for gameplay settings
 fn handle_interaction_feedback(
+    theme: Res<SettingsTheme>,
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
 ) {
-    use sf_ui_common::colors::button::{NORMAL, HOVERED, PRESSED};
-
     for (interaction, mut color) in &mut interaction_query {
-        *color = match interaction {
-            Interaction::Pressed => PRESSED.into(),
-            Interaction::Hovered => HOVERED.into(),
-            Interaction::None => NORMAL.into(),
-        };
+        *color = theme.button_background(*interaction);
+    }
+}
+
+/// Forget which tutorial hints were shown
+fn handle_reset_seen_hints_button(
+    mut seen: ResMut<SeenTutorialHints>,
+    query: Query<&Interaction, (Changed<Interaction>, With<ResetSeenHintsButton>)>,
+) {
+    let pressed = query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if pressed && !seen.ids.is_empty() {
+        seen.ids.clear();
+    }
+}
+
+fn show_difficulty_locked_hint(
+    in_progress: Res<MatchInProgress>,
+    mut hints: Query<&mut Style, With<DifficultyLockedHint>>,
+) {
+    let display = if in_progress.0 { Display::Flex } else { Display::None };
+    for mut style in &mut hints {
+        if style.display != display {
+            style.display = display;
+        }
     }
 }
 
-pub fn spawn_gameplay_settings(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
-    parent.spawn(TextBundle::from_section(
-        "Gameplay Settings", 
-        TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-            font_size: 24.0,
-            color: TEXT_COLOR,
-        })
-    );
-
-    parent.spawn(ButtonBundle {
-        style: Style {
-            width: Val::Px(150.0),
-            height: Val::Px(65.0),
-            margin: UiRect::all(Val::Px(10.0)),
+/// Stepper using the [`SettingRange`] declared on `field` of [`DifficultyModifiers`]
+fn modifier_stepper(field: &str) -> ControlKind {
+    SettingRange::of::<DifficultyModifiers>(field).unwrap_or_default().into()
+}
+
+pub fn spawn_gameplay_settings(parent: &mut ChildBuilder, _asset_server: &Res<AssetServer>) {
+    parent.spawn((
+        TextBundle::from_section("Gameplay Settings", TextStyle::default()),
+        ThemeElement::Heading,
+        LocalizedText::new(keys::GAMEPLAY_TITLE).with_fallback("Gameplay Settings"),
+        FontRole::Bold,
+    ));
+
+    parent.spawn((ButtonBundle::default(), ThemeElement::ActionButton)).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Test", TextStyle::default()),
+            ThemeElement::ActionLabel,
+            LocalizedText::new(keys::TEST_BUTTON).with_fallback("Test"),
+            FontRole::Bold,
+        ));
+    });
+
+    spawn_setting_control(parent, "Difficulty", "gameplay.difficulty", ControlKind::Cycle);
+    parent.spawn((
+        TextBundle::from_section("Custom difficulty", TextStyle::default()),
+        ThemeElement::SectionHeading,
+        LocalizedText::new(keys::DIFFICULTY_CUSTOM_HEADING).with_fallback("Custom difficulty"),
+        FontRole::Bold,
+    ));
+    spawn_setting_control(parent, "AI resource bonus", "gameplay.custom_difficulty.ai_resource_bonus",
+        modifier_stepper("ai_resource_bonus"));
+    spawn_setting_control(parent, "AI reaction delay", "gameplay.custom_difficulty.ai_reaction_delay",
+        modifier_stepper("ai_reaction_delay"));
+    spawn_setting_control(parent, "Player damage", "gameplay.custom_difficulty.player_damage_multiplier",
+        modifier_stepper("player_damage_multiplier"));
+    spawn_setting_control(parent, "AI damage", "gameplay.custom_difficulty.ai_damage_multiplier",
+        modifier_stepper("ai_damage_multiplier"));
+    spawn_setting_control(parent, "AI sees through fog", "gameplay.custom_difficulty.ai_ignores_fog", ControlKind::Toggle);
+    parent.spawn((
+        TextBundle::from_section("Difficulty can't be changed during a match", TextStyle::default()).with_style(Style {
+            display: Display::None,
+            ..default()
+        }),
+        ThemeElement::Hint,
+        LocalizedText::new(keys::DIFFICULTY_LOCKED).with_fallback("Difficulty can't be changed during a match"),
+        DifficultyLockedHint,
+        FontRole::Regular,
+    ));
+
+    spawn_setting_control(parent, "Game speed", "gameplay.game_speed",
+        SettingRange::of::<GameplaySettings>("game_speed").unwrap_or_default().into());
+    spawn_setting_control(parent, "Autosave every", "gameplay.autosave.interval_minutes",
+        SettingRange::of::<AutosaveSettings>("interval_minutes").unwrap_or_default().into());
+    spawn_setting_control(parent, "Autosave slots", "gameplay.autosave.slots",
+        SettingRange::of::<AutosaveSettings>("slots").unwrap_or_default().into());
+    spawn_setting_control(parent, "Under attack alerts", "gameplay.alerts.under_attack", ControlKind::Toggle);
+    spawn_setting_control(parent, "Idle worker alerts", "gameplay.alerts.idle_workers", ControlKind::Toggle);
+    spawn_setting_control(parent, "Research complete alerts", "gameplay.alerts.research_complete", ControlKind::Toggle);
+    spawn_setting_control(parent, "Health bars", "gameplay.health_bars", ControlKind::Cycle);
+    spawn_setting_control(parent, "Waypoints", "gameplay.waypoints", ControlKind::Cycle);
+
+    spawn_setting_control(parent, "Show tutorials", "gameplay.show_tutorials", ControlKind::Toggle);
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
             ..default()
         },
-        background_color: NORMAL_BUTTON.into(),
-        ..default()
-    }).with_children(|parent| {
-        parent.spawn(TextBundle::from_section("Test", 
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 20.0,
-                color: TEXT_COLOR,
-            })
-        );
+        ThemeElement::SmallButton,
+        ResetSeenHintsButton,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Reset seen hints", TextStyle::default()),
+            ThemeElement::SmallText,
+            LocalizedText::new(keys::TUTORIAL_RESET).with_fallback("Reset seen hints"),
+            FontRole::Bold,
+        ));
     });
-}
\ No newline at end of file
+
+    spawn_setting_control(parent, "Subtitles", "gameplay.subtitles", ControlKind::Toggle);
+    spawn_setting_control(parent, "Sound effect captions", "gameplay.closed_captions", ControlKind::Toggle);
+    spawn_setting_control(parent, "Subtitle size", "gameplay.subtitle_style.size", ControlKind::Cycle);
+    spawn_setting_control(parent, "Subtitle background", "gameplay.subtitle_style.background_opacity",
+        SettingRange::of::<SubtitleStyle>("background_opacity").unwrap_or_default().into());
+    spawn_setting_control(parent, "Speaker names", "gameplay.subtitle_style.speaker_names", ControlKind::Toggle);
+    spawn_setting_control(parent, "Speaker colours", "gameplay.subtitle_style.speaker_colors", ControlKind::Toggle);
+}
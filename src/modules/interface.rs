@@ -3,10 +3,14 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::ButtonInput;
 use bevy::ui::{Interaction, BackgroundColor};
 use sf_ui_common::components::{Focusable, FocusState};
-use sf_ui_common::colors::{
-    button::NORMAL as NORMAL_BUTTON,
-    text::NORMAL as TEXT_COLOR,
-};
+
+use crate::display::KeepsBackground;
+use crate::fonts::FontRole;
+use crate::localization::{keys, LocalizedText};
+use crate::hud::HudLayout;
+use crate::settings::{HudSettings, InterfaceSettings, Settings};
+use crate::widgets::{spawn_setting_control, ControlKind, SettingRange};
+use crate::theme::{SettingsTheme, ThemeElement};
 
 /// State for managing interface settings UI
 #[derive(Resource, Default)]
@@ -14,6 +18,20 @@ pub struct InterfaceSettingsState {
     pub focused_element: Option<Entity>,
 }
 
+/// Size of the HUD preview in pixels, with the aspect of a 16:9 screen
+const HUD_PREVIEW_WIDTH: f32 = 256.0;
+const HUD_PREVIEW_HEIGHT: f32 = 144.0;
+
+/// Element of the HUD preview, placed from [`HudLayout`]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HudPreviewPart {
+    Minimap,
+    CommandCard,
+    ResourceBar,
+    Chat,
+    PerformanceOverlay,
+}
+
 #[derive(Default)]
 pub struct InterfacePlugin;
 
@@ -23,7 +41,8 @@ impl Plugin for InterfacePlugin {
             .add_systems(Update, (
                 handle_interface_settings_keyboard_navigation,
                 update_focus_visuals,
-                handle_interaction_feedback
+                handle_interaction_feedback,
+                update_hud_preview,
             ));
     }
 }
@@ -42,63 +61,165 @@ fn handle_interface_settings_keyboard_navigation(
 
 /// Update focus visuals for interface settings
 fn update_focus_visuals(
+    theme: Res<SettingsTheme>,
     mut query: Query<(&Focusable, &mut BackgroundColor), Changed<Focusable>>,
 ) {
-    use sf_ui_common::colors::{
-        button::{NORMAL, HOVERED, PRESSED},
-        focus::HIGHLIGHT,
-    };
-
+    let colors = &theme.colors;
     for (focusable, mut background_color) in &mut query {
         *background_color = match focusable.state {
-            FocusState::Focused => HIGHLIGHT.into(),
-            FocusState::Active => PRESSED.into(),
-            FocusState::NotFocused => NORMAL.into(),
+            FocusState::Focused => colors.focused.into(),
+            FocusState::Active => colors.button_pressed.into(),
+            FocusState::NotFocused => colors.button.into(),
         };
     }
 }
 
 /// Handle interaction feedback for interface settings
 fn handle_interaction_feedback(
+    theme: Res<SettingsTheme>,
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
 ) {
-    use sf_ui_common::colors::button::{NORMAL, HOVERED, PRESSED};
-
     for (interaction, mut color) in &mut interaction_query {
-        *color = match interaction {
-            Interaction::Pressed => PRESSED.into(),
-            Interaction::Hovered => HOVERED.into(),
-            Interaction::None => NORMAL.into(),
+        *color = theme.button_background(*interaction);
+    }
+}
+
+/// Move the HUD preview elements to where the HUD settings put them
+fn update_hud_preview(
+    settings: Res<Settings>,
+    theme: Res<SettingsTheme>,
+    mut parts: Query<(Ref<HudPreviewPart>, &mut Style, &mut BackgroundColor)>,
+) {
+    let refresh = settings.is_changed() || theme.is_changed();
+    let hud = &settings.interface.hud;
+    let layout = HudLayout::new(hud, HUD_PREVIEW_WIDTH / HUD_PREVIEW_HEIGHT);
+    for (part, mut style, mut background) in &mut parts {
+        if !refresh && !part.is_added() {
+            continue;
+        }
+
+        let rect = match *part {
+            HudPreviewPart::Minimap => Some(layout.minimap),
+            HudPreviewPart::CommandCard => Some(layout.command_card),
+            HudPreviewPart::ResourceBar => Some(layout.resource_bar),
+            HudPreviewPart::Chat => Some(layout.chat),
+            HudPreviewPart::PerformanceOverlay => layout.performance_overlay,
+        };
+        let Some(rect) = rect else {
+            style.display = Display::None;
+            continue;
         };
+        style.display = Display::Flex;
+        style.left = Val::Percent(rect.min.x * 100.0);
+        style.top = Val::Percent(rect.min.y * 100.0);
+        style.width = Val::Percent(rect.width() * 100.0);
+        style.height = Val::Percent(rect.height() * 100.0);
+
+        // Each part keeps its own colour, in high contrast too, and only the
+        // chat fades with its opacity
+        let colors = &theme.colors;
+        let (color, opacity) = match *part {
+            HudPreviewPart::Minimap => (colors.hud_minimap.0, 1.0),
+            HudPreviewPart::CommandCard => (colors.hud_command_card.0, 1.0),
+            HudPreviewPart::ResourceBar => (colors.hud_resource_bar.0, 1.0),
+            HudPreviewPart::Chat => (colors.hud_chat.0, hud.chat_opacity),
+            HudPreviewPart::PerformanceOverlay => (colors.hud_performance_overlay.0, 1.0),
+        };
+        background.0 = color.with_alpha(color.alpha() * opacity);
     }
 }
 
-pub fn spawn_interface_settings(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
-    parent.spawn(TextBundle::from_section(
-        "Interface Settings", 
-        TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-            font_size: 24.0,
-            color: TEXT_COLOR,
-        })
-    );
-
-    parent.spawn(ButtonBundle {
-        style: Style {
-            width: Val::Px(150.0),
-            height: Val::Px(65.0),
-            margin: UiRect::all(Val::Px(10.0)),
+/// Stepper using the [`SettingRange`] declared on `field` of [`HudSettings`]
+fn hud_stepper(field: &str) -> ControlKind {
+    SettingRange::of::<HudSettings>(field).unwrap_or_default().into()
+}
+
+fn spawn_hud_preview(parent: &mut ChildBuilder) {
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Px(HUD_PREVIEW_WIDTH),
+                height: Val::Px(HUD_PREVIEW_HEIGHT),
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
             ..default()
         },
-        background_color: NORMAL_BUTTON.into(),
-        ..default()
-    }).with_children(|parent| {
-        parent.spawn(TextBundle::from_section("Test", 
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 20.0,
-                color: TEXT_COLOR,
-            })
-        );
+        ThemeElement::HudPreview,
+        Name::new("HudPreview"),
+    )).with_children(|parent| {
+        // Placed and coloured by update_hud_preview
+        for part in [
+            HudPreviewPart::Minimap,
+            HudPreviewPart::CommandCard,
+            HudPreviewPart::ResourceBar,
+            HudPreviewPart::Chat,
+            HudPreviewPart::PerformanceOverlay,
+        ] {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeElement::HudPreviewElement,
+                KeepsBackground,
+                part,
+                Name::new(format!("{:?}Preview", part)),
+            ));
+        }
+    });
+}
+
+pub fn spawn_interface_settings(parent: &mut ChildBuilder, _asset_server: &Res<AssetServer>) {
+    parent.spawn((
+        TextBundle::from_section("Interface Settings", TextStyle::default()),
+        ThemeElement::Heading,
+        LocalizedText::new(keys::INTERFACE_TITLE).with_fallback("Interface Settings"),
+        FontRole::Bold,
+    ));
+
+    parent.spawn((ButtonBundle::default(), ThemeElement::ActionButton)).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Test", TextStyle::default()),
+            ThemeElement::ActionLabel,
+            LocalizedText::new(keys::TEST_BUTTON).with_fallback("Test"),
+            FontRole::Bold,
+        ));
     });
-}
\ No newline at end of file
+
+    spawn_setting_control(parent, "Language", "interface.language", ControlKind::Cycle);
+    spawn_setting_control(parent, "UI scale", "interface.ui_scale",
+        SettingRange::of::<InterfaceSettings>("ui_scale").unwrap_or_default().into());
+    spawn_setting_control(parent, "Colour-blind mode", "interface.colorblind_mode", ControlKind::Cycle);
+    spawn_setting_control(parent, "Colour-blind strength", "interface.colorblind_strength",
+        SettingRange::of::<InterfaceSettings>("colorblind_strength").unwrap_or_default().into());
+    spawn_setting_control(parent, "Text size", "interface.text_scale",
+        SettingRange::of::<InterfaceSettings>("text_scale").unwrap_or_default().into());
+    spawn_setting_control(parent, "High contrast", "interface.high_contrast", ControlKind::Toggle);
+    spawn_setting_control(parent, "Reduce motion", "interface.reduce_motion", ControlKind::Toggle);
+    spawn_setting_control(parent, "Dyslexia-friendly font", "interface.dyslexia_font", ControlKind::Toggle);
+    spawn_setting_control(parent, "Cursor size", "interface.cursor_size", ControlKind::Cycle);
+
+    // In-match HUD
+    parent.spawn((
+        TextBundle::from_section("HUD layout", TextStyle::default()),
+        ThemeElement::SectionHeading,
+        LocalizedText::new(keys::HUD_HEADING).with_fallback("HUD layout"),
+        FontRole::Bold,
+    ));
+    spawn_hud_preview(parent);
+    spawn_setting_control(parent, "Minimap position", "interface.hud.minimap_position", ControlKind::Cycle);
+    spawn_setting_control(parent, "Minimap size", "interface.hud.minimap_size", hud_stepper("minimap_size"));
+    spawn_setting_control(parent, "Minimap rotation", "interface.hud.minimap_rotation", ControlKind::Cycle);
+    spawn_setting_control(parent, "Command card layout", "interface.hud.command_card_layout", ControlKind::Cycle);
+    spawn_setting_control(parent, "Resource bar position", "interface.hud.resource_bar_position", ControlKind::Cycle);
+    spawn_setting_control(parent, "Chat opacity", "interface.hud.chat_opacity", hud_stepper("chat_opacity"));
+    spawn_setting_control(parent, "Chat fade time", "interface.hud.chat_fade_time", hud_stepper("chat_fade_time"));
+    spawn_setting_control(parent, "Tooltip delay", "interface.hud.tooltip_delay", hud_stepper("tooltip_delay"));
+    spawn_setting_control(parent, "Tooltip detail", "interface.hud.tooltip_detail", ControlKind::Cycle);
+    spawn_setting_control(parent, "FPS and ping", "interface.hud.performance_overlay", ControlKind::Toggle);
+}
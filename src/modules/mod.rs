@@ -6,11 +6,6 @@ pub mod gameplay;
 pub mod interface;
 pub mod video;
 
-use bevy::prelude::*;
-use sf_ui_common::components::*;
-use sf_ui_common::types::{SettingsTab, SliderType, CheckboxType, WindowMode};
-use sf_ui_common::accessibility::{AccessibilityNode, Role};
-
 // Re-exports
 pub use audio::AudioPlugin;
 pub use controls::ControlsPlugin;
@@ -3,10 +3,10 @@ use bevy::input::keyboard::KeyCode;
 use bevy::input::ButtonInput;
 use bevy::ui::{Interaction, BackgroundColor};
 use sf_ui_common::components::{Focusable, FocusState};
-use sf_ui_common::colors::{
-    button::NORMAL as NORMAL_BUTTON,
-    text::NORMAL as TEXT_COLOR,
-};
+
+use crate::fonts::FontRole;
+use crate::localization::{keys, LocalizedText};
+use crate::theme::{SettingsTheme, ThemeElement};
 
 #[derive(Default)]
 pub struct VideoPlugin;
@@ -36,34 +36,26 @@ fn handle_video_settings_keyboard_navigation(
 
 /// Update focus visuals for video settings
 fn update_focus_visuals(
+    theme: Res<SettingsTheme>,
     mut query: Query<(&Focusable, &mut BackgroundColor), Changed<Focusable>>,
 ) {
-    use sf_ui_common::colors::{
-        button::{NORMAL, HOVERED, PRESSED},
-        focus::HIGHLIGHT,
-    };
-
+    let colors = &theme.colors;
     for (focusable, mut background_color) in &mut query {
         *background_color = match focusable.state {
-            FocusState::Focused => HIGHLIGHT.into(),
-            FocusState::Active => PRESSED.into(),
-            FocusState::NotFocused => NORMAL.into(),
+            FocusState::Focused => colors.focused.into(),
+            FocusState::Active => colors.button_pressed.into(),
+            FocusState::NotFocused => colors.button.into(),
         };
     }
 }
 
 /// Handle interaction feedback for video settings
 fn handle_interaction_feedback(
+    theme: Res<SettingsTheme>,
     mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
 ) {
-    use sf_ui_common::colors::button::{NORMAL, HOVERED, PRESSED};
-
     for (interaction, mut color) in &mut interaction_query {
-        *color = match interaction {
-            Interaction::Pressed => PRESSED.into(),
-            Interaction::Hovered => HOVERED.into(),
-            Interaction::None => NORMAL.into(),
-        };
+        *color = theme.button_background(*interaction);
     }
 }
 
@@ -72,34 +64,22 @@ pub struct VideoSettingsState {
     pub test_active: bool,
 }
 
-pub fn spawn_video_settings(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>) {
+pub fn spawn_video_settings(parent: &mut ChildBuilder, _asset_server: &Res<AssetServer>) {
     // Title
-    parent.spawn(TextBundle::from_section(
-        "Video Settings", 
-        TextStyle {
-            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-            font_size: 24.0,
-            color: TEXT_COLOR,
-        })
-    );
+    parent.spawn((
+        TextBundle::from_section("Video Settings", TextStyle::default()),
+        ThemeElement::Heading,
+        LocalizedText::new(keys::VIDEO_TITLE).with_fallback("Video Settings"),
+        FontRole::Bold,
+    ));
 
     // Test button
-    parent.spawn(ButtonBundle {
-        style: Style {
-            width: Val::Px(150.0),
-            height: Val::Px(65.0),
-            margin: UiRect::all(Val::Px(10.0)),
-            ..default()
-        },
-        background_color: NORMAL_BUTTON.into(),
-        ..default()
-    }).with_children(|parent| {
-        parent.spawn(TextBundle::from_section("Test", 
-            TextStyle {
-                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                font_size: 20.0,
-                color: TEXT_COLOR,
-            })
-        );
+    parent.spawn((ButtonBundle::default(), ThemeElement::ActionButton)).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section("Test", TextStyle::default()),
+            ThemeElement::ActionLabel,
+            LocalizedText::new(keys::TEST_BUTTON).with_fallback("Test"),
+            FontRole::Bold,
+        ));
     });
 }
\ No newline at end of file
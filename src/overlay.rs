@@ -0,0 +1,245 @@
+//! In-game settings overlay
+//!
+//! The standalone settings panel is shown while [`SettingsState::is_visible`] is
+//! set. The toggle key from [`SettingsMenuConfig`] (Escape by default) flips it,
+//! and hosts can set it directly, e.g. from a pause menu button.
+//!
+//! While the overlay is open:
+//! - the game is paused or kept running according to [`OverlayPausePolicy`]
+//! - [`CameraInput`] and [`GamepadInput`] read as idle and the cursor is released;
+//!   game systems that read input directly should use [`settings_overlay_closed`]
+//! - whatever [`Focusable`] had focus is unfocused, and refocused on close
+//!
+//! The panel slides in when it opens, unless [`UiAccessibility::reduce_motion`]
+//! is set.
+//!
+//! Both the toggle key and the pause policy are read from the
+//! [`SettingsMenuConfig`] resource, so hosts can change them mid-game, for
+//! example to stop pausing in multiplayer matches.
+
+use bevy::prelude::*;
+use sf_ui_common::components::{FocusState, Focusable};
+
+use crate::camera_input::{CameraInput, CameraInputSet};
+use crate::display::UiAccessibility;
+use crate::gamepad::{GamepadInput, GamepadInputSet};
+use crate::settings::SettingsState;
+use crate::SettingsMenuConfig;
+
+/// Whether opening the overlay pauses the game
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverlayPausePolicy {
+    /// Keep the game running, e.g. in multiplayer matches
+    #[default]
+    KeepRunning,
+    /// Pause `Time<Virtual>` while the overlay is open
+    PauseVirtualTime,
+}
+
+/// Length of the panel's slide-in
+const PANEL_TRANSITION_SECS: f32 = 0.15;
+
+/// How far below its place the panel starts sliding in, in percent of the window height
+const PANEL_SLIDE_PERCENT: f32 = 3.0;
+
+/// The standalone settings panel
+#[derive(Component)]
+pub struct SettingsPanel;
+
+/// Slide-in of the panel, removed once it's in place
+#[derive(Component)]
+struct PanelTransition {
+    /// Where the panel ends up
+    top: Val,
+    timer: Timer,
+}
+
+/// Sent when the overlay opens or closes, for hosts with their own pause handling
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingsOverlayToggled {
+    pub open: bool,
+}
+
+/// What the overlay changed when it opened, undone when it closes
+#[derive(Resource, Default)]
+struct OverlaySession {
+    open: bool,
+    /// Whether the overlay paused virtual time, so it doesn't unpause a game the
+    /// host had paused itself
+    paused_time: bool,
+    previous_focus: Vec<Entity>,
+}
+
+pub struct SettingsOverlayPlugin;
+
+impl Plugin for SettingsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<OverlaySession>()
+            .add_event::<SettingsOverlayToggled>()
+            .add_systems(
+                PreUpdate,
+                block_game_input
+                    .after(CameraInputSet)
+                    .after(GamepadInputSet)
+                    .run_if(settings_overlay_open),
+            )
+            .add_systems(Update, (
+                toggle_settings_overlay,
+                apply_overlay_transition.after(toggle_settings_overlay),
+                sync_panel_visibility.after(toggle_settings_overlay),
+                animate_panel_transition.after(sync_panel_visibility),
+            ));
+    }
+}
+
+/// Run condition: the settings overlay is open
+pub fn settings_overlay_open(state: Option<Res<SettingsState>>) -> bool {
+    state.is_some_and(|state| state.is_visible)
+}
+
+/// Settings menu content embedded in the host menu by `MenuItemPlugin::on_selected`
+#[derive(Component)]
+pub struct EmbeddedSettingsMenu;
+
+/// Run condition: the overlay is open or the menu is embedded in the host menu
+pub fn settings_menu_shown(
+    state: Option<Res<SettingsState>>,
+    embedded: Query<(), With<EmbeddedSettingsMenu>>,
+) -> bool {
+    settings_overlay_open(state) || !embedded.is_empty()
+}
+
+/// Run condition for game systems that must not see input while the overlay is open
+pub fn settings_overlay_closed(state: Option<Res<SettingsState>>) -> bool {
+    !settings_overlay_open(state)
+}
+
+fn toggle_settings_overlay(
+    config: Res<SettingsMenuConfig>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<SettingsState>,
+) {
+    let Some(key) = config.toggle_key else {
+        return;
+    };
+    if keys.just_pressed(key) {
+        state.is_visible = !state.is_visible;
+    }
+}
+
+/// Pause, unfocus and notify when the overlay opens, and undo it when it closes
+fn apply_overlay_transition(
+    config: Res<SettingsMenuConfig>,
+    state: Res<SettingsState>,
+    mut session: ResMut<OverlaySession>,
+    mut time: ResMut<Time<Virtual>>,
+    mut focusables: Query<(Entity, &mut Focusable)>,
+    mut toggled: EventWriter<SettingsOverlayToggled>,
+) {
+    if state.is_visible == session.open {
+        return;
+    }
+    session.open = state.is_visible;
+    toggled.send(SettingsOverlayToggled { open: session.open });
+
+    if session.open {
+        if config.pause_policy == OverlayPausePolicy::PauseVirtualTime && !time.is_paused() {
+            time.pause();
+            session.paused_time = true;
+        }
+
+        session.previous_focus.clear();
+        for (entity, mut focusable) in &mut focusables {
+            if focusable.state == FocusState::Focused {
+                focusable.state = FocusState::NotFocused;
+                session.previous_focus.push(entity);
+            }
+        }
+    } else {
+        if std::mem::take(&mut session.paused_time) {
+            time.unpause();
+        }
+
+        // Everything else was unfocused on open, so anything focused now is in the panel
+        for (_, mut focusable) in &mut focusables {
+            if focusable.state == FocusState::Focused {
+                focusable.state = FocusState::NotFocused;
+            }
+        }
+        for entity in std::mem::take(&mut session.previous_focus) {
+            if let Ok((_, mut focusable)) = focusables.get_mut(entity) {
+                focusable.state = FocusState::Focused;
+            }
+        }
+    }
+}
+
+fn sync_panel_visibility(
+    mut commands: Commands,
+    state: Res<SettingsState>,
+    options: Option<Res<UiAccessibility>>,
+    mut panels: Query<(Entity, Ref<SettingsPanel>, &mut Visibility, &Style)>,
+    transitions: Query<(), With<PanelTransition>>,
+) {
+    let target = if state.is_visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    let reduce_motion = options.is_some_and(|options| options.reduce_motion);
+
+    for (entity, panel, mut visibility, style) in &mut panels {
+        if !(state.is_changed() || panel.is_added()) || *visibility == target {
+            continue;
+        }
+        *visibility = target;
+
+        if target == Visibility::Inherited && !reduce_motion && !panel.is_added() && !transitions.contains(entity) {
+            commands.entity(entity).insert(PanelTransition {
+                top: style.top,
+                timer: Timer::from_seconds(PANEL_TRANSITION_SECS, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// Slide the panel into place, in real time so it also runs while the game is paused
+fn animate_panel_transition(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    options: Option<Res<UiAccessibility>>,
+    mut panels: Query<(Entity, &mut Style, &mut PanelTransition)>,
+) {
+    let reduce_motion = options.is_some_and(|options| options.reduce_motion);
+    for (entity, mut style, mut transition) in &mut panels {
+        transition.timer.tick(time.delta());
+        if reduce_motion || transition.timer.finished() {
+            style.top = transition.top;
+            commands.entity(entity).remove::<PanelTransition>();
+            continue;
+        }
+
+        // Ease out, fast at first and settling into place
+        let remaining = (1.0 - transition.timer.fraction()).powi(2);
+        style.top = match transition.top {
+            Val::Percent(top) => Val::Percent(top + PANEL_SLIDE_PERCENT * remaining),
+            top => top,
+        };
+    }
+}
+
+/// Make the processed camera and gamepad input read as idle for game systems
+///
+/// The menu reads the gamepad from [`MenuGamepadInput`](crate::gamepad::MenuGamepadInput),
+/// so the stick preview keeps moving.
+fn block_game_input(mut camera: ResMut<CameraInput>, mut gamepad: ResMut<GamepadInput>) {
+    camera.pan = Vec2::ZERO;
+    camera.rotate = Vec2::ZERO;
+    camera.zoom = 0.0;
+    camera.scroll_velocity = Vec2::ZERO;
+
+    *gamepad = GamepadInput {
+        gamepad: gamepad.gamepad,
+        ..default()
+    };
+}
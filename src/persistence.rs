@@ -0,0 +1,538 @@
+//! Loading and saving settings
+//!
+//! Settings are stored in a single RON file as a map from section id to the
+//! reflected value. The built-in [`Settings`] live under `"settings"` and every
+//! registered plugin section under its own id:
+//!
+//! ```ron
+//! {
+//!     "settings": (video: (...), audio: (...), ...),
+//!     "minimap": (zoom: 1.0, show_pings: true),
+//! }
+//! ```
+//!
+//! Every setting is read on its own: missing fields keep their defaults, and
+//! unknown sections and fields are skipped, as are values that don't parse,
+//! such as a variant renamed since the file was written or the end of a
+//! truncated file. Files written by older or newer builds therefore still
+//! load. Out of range values are repaired and listed in [`SettingsRepairReport`].
+//!
+//! Edits are saved once the settings have been left alone for [`SAVE_DELAY`],
+//! or right away when the overlay closes or the app exits. The file is written
+//! next to the old one and renamed over it, so it is never left half written.
+//! A file that can't be read at all is left untouched for the rest of the
+//! session instead of being overwritten with defaults.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy::reflect::{ApplyError, DynamicStruct, TypeInfo, TypeRegistration, TypeRegistry};
+use serde::de::DeserializeSeed;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::overlay::SettingsOverlayToggled;
+use crate::registry::SettingsSections;
+use crate::settings::Settings;
+use crate::validation::{self, SettingsRepairReport};
+use crate::widgets::format_value;
+
+/// Default settings file, relative to the working directory
+pub const DEFAULT_SETTINGS_PATH: &str = "settings.ron";
+
+/// Key of the built-in [`Settings`] in the settings file
+pub const CORE_SETTINGS_KEY: &str = "settings";
+
+/// Edits are saved once no setting has changed for this long
+pub const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// Reflected settings values keyed by section id
+pub type SettingsValues = Vec<(String, Box<dyn Reflect>)>;
+
+/// A setting in the settings file that was left out when loading it
+#[derive(Clone, Debug)]
+pub struct SkippedSetting {
+    /// Setting path, e.g. `"audio.music_volume"` or `"minimap"`
+    pub path: String,
+    pub reason: String,
+}
+
+/// Location of the settings file
+#[derive(Resource, Clone)]
+pub struct SettingsPath(pub PathBuf);
+
+impl Default for SettingsPath {
+    fn default() -> Self {
+        Self(PathBuf::from(DEFAULT_SETTINGS_PATH))
+    }
+}
+
+#[derive(Debug)]
+pub enum SettingsFileError {
+    Io(io::Error),
+    Ron(String),
+}
+
+impl fmt::Display for SettingsFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsFileError::Io(err) => write!(f, "settings file error: {err}"),
+            SettingsFileError::Ron(err) => write!(f, "invalid settings file: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SettingsFileError {}
+
+impl From<io::Error> for SettingsFileError {
+    fn from(err: io::Error) -> Self {
+        SettingsFileError::Io(err)
+    }
+}
+
+/// Settings edits not written to the file yet
+#[derive(Resource, Default)]
+struct PendingSave {
+    /// Real time of the latest unsaved edit
+    last_edit: Option<Duration>,
+    /// Save now instead of waiting for [`SAVE_DELAY`]
+    flush: bool,
+    /// The settings file couldn't be read, so it must not be overwritten
+    load_failed: bool,
+}
+
+pub struct SettingsPersistencePlugin;
+
+impl Plugin for SettingsPersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsPath>()
+            .init_resource::<PendingSave>()
+            .add_event::<SettingsOverlayToggled>()
+            .add_systems(PreStartup, load_settings)
+            .add_systems(Last, (flush_on_close, save_changed_settings).chain());
+    }
+}
+
+/// Load the settings file into [`Settings`] and every registered section
+pub fn load_settings(world: &mut World) {
+    let path = world.resource::<SettingsPath>().0.clone();
+    if !path.exists() {
+        info!("No settings file at {:?}, using defaults", path);
+        return;
+    }
+
+    let (values, skipped) = match read_settings_file(world, &path) {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            warn!("Failed to load settings from {:?}, it won't be saved over this session: {}", path, err);
+            world.resource_mut::<PendingSave>().load_failed = true;
+            return;
+        }
+    };
+
+    for setting in skipped {
+        warn!("Ignoring setting {:?} from {:?}: {}", setting.path, path, setting.reason);
+    }
+    for (key, err) in apply_settings(world, values) {
+        warn!("Ignoring settings section {:?} from {:?}: {}", key, path, err);
+    }
+
+    let repairs = validation::repair_settings(world);
+    for repair in &repairs {
+        warn!(
+            "Repaired setting {:?} from {:?}: {}, now {}",
+            repair.path,
+            path,
+            repair.message,
+            format_value(repair.repaired.as_ref()),
+        );
+    }
+    world.insert_resource(SettingsRepairReport { repairs });
+}
+
+/// Apply deserialized settings to [`Settings`] and the registered sections
+///
+/// Returns the sections whose values didn't match the current type.
+pub fn apply_settings(world: &mut World, values: SettingsValues) -> Vec<(String, ApplyError)> {
+    let sections = world.get_resource::<SettingsSections>().cloned().unwrap_or_default();
+    let mut errors = Vec::new();
+    for (key, value) in values {
+        let result = if key == CORE_SETTINGS_KEY {
+            world.resource_mut::<Settings>().try_apply(value.as_ref())
+        } else if let Some(section) = sections.get(&key) {
+            let mut result = Ok(());
+            section.reflect_mut(world, &mut |target| result = target.try_apply(value.as_ref()));
+            result
+        } else {
+            continue;
+        };
+
+        if let Err(err) = result {
+            errors.push((key, err));
+        }
+    }
+    errors
+}
+
+/// Save without waiting once the overlay closes or the app is exiting
+fn flush_on_close(
+    mut pending: ResMut<PendingSave>,
+    mut toggled: EventReader<SettingsOverlayToggled>,
+    mut exit: EventReader<AppExit>,
+) {
+    let closed = toggled.read().any(|toggled| !toggled.open);
+    let exiting = exit.read().count() > 0;
+    if closed || exiting {
+        pending.flush = true;
+    }
+}
+
+/// Write the settings file once [`Settings`] and the registered sections have
+/// been left alone for [`SAVE_DELAY`], or right away when flushed
+fn save_changed_settings(world: &mut World, mut started: Local<bool>) {
+    let sections = world.get_resource::<SettingsSections>().cloned().unwrap_or_default();
+    let changed = world.is_resource_changed::<Settings>()
+        || sections.iter().any(|section| section.is_changed(world));
+    // The first run sees the values loaded at startup as changed
+    let changed = changed && *started;
+    *started = true;
+
+    let now = world.get_resource::<Time<Real>>().map(|time| time.elapsed());
+    let mut pending = world.resource_mut::<PendingSave>();
+    if changed {
+        pending.last_edit = Some(now.unwrap_or_default());
+    }
+    let flush = std::mem::take(&mut pending.flush);
+    let due = match (pending.last_edit, now) {
+        (None, _) => false,
+        (Some(last_edit), Some(now)) => flush || now.saturating_sub(last_edit) >= SAVE_DELAY,
+        (Some(_), None) => true,
+    };
+    if due {
+        pending.last_edit = None;
+        save_settings(world);
+    }
+}
+
+/// Save edits still waiting for [`SAVE_DELAY`], e.g. before the plugin is unloaded
+pub fn save_pending_settings(world: &mut World) {
+    let pending = world.get_resource_mut::<PendingSave>().and_then(|mut pending| pending.last_edit.take());
+    if pending.is_some() {
+        save_settings(world);
+    }
+}
+
+/// Write [`Settings`] and every registered section to the settings file
+///
+/// Does nothing when the settings file failed to load, so it isn't replaced
+/// with defaults.
+pub fn save_settings(world: &mut World) {
+    if world.get_resource::<PendingSave>().is_some_and(|pending| pending.load_failed) {
+        return;
+    }
+    let path = world.resource::<SettingsPath>().0.clone();
+    if let Err(err) = write_settings_file(world, &path) {
+        warn!("Failed to save settings to {:?}: {}", path, err);
+    }
+}
+
+/// Serialize the current settings to RON
+pub fn serialize_settings(world: &World) -> Result<String, SettingsFileError> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    let mut entries: Vec<(&str, &dyn Reflect)> = vec![(CORE_SETTINGS_KEY, world.resource::<Settings>().as_reflect())];
+    if let Some(sections) = world.get_resource::<SettingsSections>() {
+        for section in sections.iter() {
+            if let Some(value) = section.reflect(world) {
+                entries.push((section.id, value));
+            }
+        }
+    }
+
+    let file = SettingsFileSerializer {
+        entries,
+        registry: &registry,
+    };
+    ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())
+        .map_err(|err| SettingsFileError::Ron(err.to_string()))
+}
+
+/// Parse a settings file into reflected values keyed by section id, along with
+/// the settings that were left out
+///
+/// Sections and fields that aren't known to this build are skipped, and so is
+/// every value that fails to parse, without affecting the others. Only a file
+/// that isn't a map of sections at all is an error.
+pub fn deserialize_settings(world: &World, source: &str) -> Result<(SettingsValues, Vec<SkippedSetting>), SettingsFileError> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    let mut registrations: HashMap<String, &TypeRegistration> = HashMap::new();
+    if let Some(registration) = registry.get(std::any::TypeId::of::<Settings>()) {
+        registrations.insert(CORE_SETTINGS_KEY.to_string(), registration);
+    }
+    if let Some(sections) = world.get_resource::<SettingsSections>() {
+        for section in sections.iter() {
+            if let Some(registration) = registry.get(section.type_info.type_id()) {
+                registrations.insert(section.id.to_string(), registration);
+            }
+        }
+    }
+
+    let entries = split_entries(source)
+        .ok_or_else(|| SettingsFileError::Ron("expected a map of settings sections".to_string()))?;
+    let mut values = Vec::new();
+    let mut skipped = Vec::new();
+    for (key, source) in entries {
+        let Some(registration) = registrations.get(key) else {
+            continue;
+        };
+        if let Some(value) = deserialize_lenient(key, source, registration, &registry, &mut skipped) {
+            values.push((key.to_string(), value));
+        }
+    }
+    Ok((values, skipped))
+}
+
+/// Deserialize `source` as the type of `registration`
+///
+/// Structs are read field by field, leaving unknown fields and fields that fail
+/// to parse out of the returned partial value and adding them to `skipped`.
+/// `path` is the setting path of the value, for the skipped entries.
+fn deserialize_lenient(
+    path: &str,
+    source: &str,
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+    skipped: &mut Vec<SkippedSetting>,
+) -> Option<Box<dyn Reflect>> {
+    let mut skip = |reason: String| {
+        skipped.push(SkippedSetting {
+            path: path.to_string(),
+            reason,
+        });
+    };
+
+    let TypeInfo::Struct(info) = registration.type_info() else {
+        return deserialize_value(source, registration, registry).map_err(skip).ok();
+    };
+    let Some(entries) = split_entries(source) else {
+        skip("expected a struct".to_string());
+        return None;
+    };
+
+    let mut value = DynamicStruct::default();
+    value.set_represented_type(Some(registration.type_info()));
+    for (name, source) in entries {
+        let field_path = format!("{path}.{name}");
+        let Some(field_registration) = info.field(name).and_then(|field| registry.get(field.type_id())) else {
+            skipped.push(SkippedSetting {
+                path: field_path,
+                reason: "unknown setting".to_string(),
+            });
+            continue;
+        };
+        if let Some(field) = deserialize_lenient(&field_path, source, field_registration, registry, skipped) {
+            value.insert_boxed(name, field);
+        }
+    }
+    Some(Box::new(value))
+}
+
+/// Deserialize a whole value of the type of `registration` from `source`
+fn deserialize_value(source: &str, registration: &TypeRegistration, registry: &TypeRegistry) -> Result<Box<dyn Reflect>, String> {
+    let mut deserializer = ron::Deserializer::from_str(source).map_err(|err| err.to_string())?;
+    let value = TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut deserializer)
+        .map_err(|err| err.to_string())?;
+    deserializer.end().map_err(|err| err.to_string())?;
+    Ok(value)
+}
+
+/// Keys and value sources of the entries of a RON map or struct, e.g.
+/// `[("zoom", "1.0"), ("show_pings", "true")]` for `(zoom: 1.0, show_pings: true)`
+///
+/// Values are only cut apart at the commas between entries, not parsed, so one
+/// that is malformed doesn't affect the others. When the source ends early the
+/// last value runs to its end and the entries before it are kept. Returns
+/// `None` if `source` isn't a map or struct.
+fn split_entries(source: &str) -> Option<Vec<(&str, &str)>> {
+    let bytes = source.as_bytes();
+    let mut i = skip_blank(bytes, 0);
+    // Struct name, when the file was written with them
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+        i += 1;
+    }
+    i = skip_blank(bytes, i);
+    if !matches!(bytes.get(i), Some(b'(' | b'{')) {
+        return None;
+    }
+    i += 1;
+
+    let mut entries = Vec::new();
+    loop {
+        i = skip_blank(bytes, i);
+        let key = match bytes.get(i) {
+            Some(b'"') => {
+                let end = skip_string(bytes, i);
+                let key = source.get(i + 1..end.saturating_sub(1))?;
+                i = end;
+                key
+            }
+            Some(byte) if byte.is_ascii_alphabetic() || *byte == b'_' => {
+                let start = i;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                &source[start..i]
+            }
+            _ => break,
+        };
+        i = skip_blank(bytes, i);
+        if bytes.get(i) != Some(&b':') {
+            break;
+        }
+
+        i += 1;
+        let start = i;
+        let mut depth = 0usize;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'"' => {
+                    i = skip_string(bytes, i);
+                    continue;
+                }
+                b'/' if matches!(bytes.get(i + 1), Some(b'/' | b'*')) => {
+                    i = skip_blank(bytes, i);
+                    continue;
+                }
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' if depth == 0 => break,
+                b')' | b']' | b'}' => depth -= 1,
+                b',' if depth == 0 => break,
+                _ => {}
+            }
+            i += 1;
+        }
+        entries.push((key, source[start..i].trim()));
+        if bytes.get(i) != Some(&b',') {
+            break;
+        }
+        i += 1;
+    }
+    Some(entries)
+}
+
+/// Index of the first byte from `i` that isn't whitespace or inside a comment
+fn skip_blank(bytes: &[u8], mut i: usize) -> usize {
+    loop {
+        match (bytes.get(i), bytes.get(i + 1)) {
+            (Some(byte), _) if byte.is_ascii_whitespace() => i += 1,
+            (Some(b'/'), Some(b'/')) => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            (Some(b'/'), Some(b'*')) => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i = (i + 2).min(bytes.len());
+            }
+            _ => return i,
+        }
+    }
+}
+
+/// Index just past the string literal starting at `i`
+fn skip_string(bytes: &[u8], mut i: usize) -> usize {
+    i += 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+fn read_settings_file(world: &World, path: &Path) -> Result<(SettingsValues, Vec<SkippedSetting>), SettingsFileError> {
+    let source = fs::read_to_string(path)?;
+    deserialize_settings(world, &source)
+}
+
+fn write_settings_file(world: &World, path: &Path) -> Result<(), SettingsFileError> {
+    let source = serialize_settings(world)?;
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    // Write next to the file and rename it over, so a crash can't truncate it
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, source)?;
+    fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+struct SettingsFileSerializer<'a> {
+    entries: Vec<(&'a str, &'a dyn Reflect)>,
+    registry: &'a TypeRegistry,
+}
+
+impl Serialize for SettingsFileSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (key, value) in &self.entries {
+            map.serialize_entry(key, &TypedReflectSerializer::new(*value, self.registry))?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_struct_fields() {
+        let source = "(zoom: 1.0, resolution: (1920, 1080), name: \"a, b\", curve: Linear(factor: 0.5))";
+        assert_eq!(
+            split_entries(source),
+            Some(vec![
+                ("zoom", "1.0"),
+                ("resolution", "(1920, 1080)"),
+                ("name", "\"a, b\""),
+                ("curve", "Linear(factor: 0.5)"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn splits_map_with_comments() {
+        let source = "{\n    // audio\n    \"settings\": (volume: 0.5), /* none */\n    \"minimap\": (),\n}";
+        assert_eq!(split_entries(source), Some(vec![("settings", "(volume: 0.5)"), ("minimap", "()")]));
+    }
+
+    #[test]
+    fn keeps_entries_before_truncation() {
+        assert_eq!(
+            split_entries("(a: 1, b: (c: 2, d"),
+            Some(vec![("a", "1"), ("b", "(c: 2, d")]),
+        );
+        assert_eq!(split_entries("(a: 1, b"), Some(vec![("a", "1")]));
+    }
+
+    #[test]
+    fn rejects_non_maps() {
+        assert_eq!(split_entries(""), None);
+        assert_eq!(split_entries("garbage"), None);
+        assert_eq!(split_entries("[1, 2]"), None);
+    }
+}
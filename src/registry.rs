@@ -0,0 +1,267 @@
+//! Settings sections registered by other StrategyForge plugins
+//!
+//! A plugin registers its own `Reflect` struct with
+//! [`RegisterSettingsSection::register_settings_section`]. The struct is inserted
+//! as a regular resource the plugin can read, persisted in the same file as
+//! [`Settings`], and rendered in the menu with controls generated from its
+//! reflected fields.
+//!
+//! Edits to a section send [`SettingChanged`](crate::changes::SettingChanged)
+//! events with paths prefixed by the section id and a typed
+//! [`SettingsSectionChanged<T>`] event per commit.
+//!
+//! State that should be saved but isn't something the player sets, such as
+//! which tutorial hints were shown, goes in a hidden section registered with
+//! [`RegisterSettingsSection::register_hidden_settings_section`]. It is saved
+//! like the others but isn't shown in the menu, sends no change events and
+//! so stays out of the undo history.
+//!
+//! ```ignore
+//! #[derive(Resource, Reflect, Default, Clone)]
+//! struct MinimapSettings {
+//!     #[reflect(@SettingRange::new(0.5, 2.0, 0.1))]
+//!     zoom: f32,
+//!     show_pings: bool,
+//! }
+//!
+//! app.register_settings_section::<MinimapSettings>("minimap", SettingsTab::Interface, "Minimap");
+//! ```
+
+use bevy::prelude::*;
+use bevy::reflect::{GetPath, GetTypeRegistration, NamedField, TypeInfo, Typed};
+
+use crate::changes::SettingsSectionChanged;
+use crate::settings::{Settings, SettingsTab};
+use crate::tabs::{SettingsTabInfo, SettingsTabs, SECTION_TAB_ORDER};
+use crate::widgets::LockedSettings;
+
+/// Runs a closure on the section resource
+type ReflectMutFn = fn(&mut World, &mut dyn FnMut(&mut dyn Reflect));
+
+/// Description of a registered settings section
+#[derive(Clone)]
+pub struct SettingsSectionInfo {
+    /// Unique id, also the first segment of the section's setting paths
+    pub id: &'static str,
+    /// Tab the section is shown on
+    pub tab: SettingsTab,
+    /// Heading shown above the section's controls
+    pub label: &'static str,
+    pub type_info: &'static TypeInfo,
+    /// Saved with the settings, but not shown and without change events
+    pub hidden: bool,
+    default_value: fn() -> Box<dyn Reflect>,
+    reflect: fn(&World) -> Option<&dyn Reflect>,
+    reflect_mut: ReflectMutFn,
+    is_changed: fn(&World) -> bool,
+    send_changed: fn(&mut World, &dyn Reflect, &dyn Reflect, Vec<String>),
+}
+
+impl SettingsSectionInfo {
+    /// A fresh default instance of the section type
+    pub fn default_value(&self) -> Box<dyn Reflect> {
+        (self.default_value)()
+    }
+
+    /// Current value of the section resource
+    pub fn reflect<'w>(&self, world: &'w World) -> Option<&'w dyn Reflect> {
+        (self.reflect)(world)
+    }
+
+    /// Edit the section resource in place, marking it changed
+    pub fn reflect_mut(&self, world: &mut World, f: &mut dyn FnMut(&mut dyn Reflect)) {
+        (self.reflect_mut)(world, f)
+    }
+
+    /// Whether the section resource changed since the calling system last ran
+    pub fn is_changed(&self, world: &World) -> bool {
+        (self.is_changed)(world)
+    }
+
+    /// Send the section's typed [`SettingsSectionChanged`] event
+    pub fn send_changed(&self, world: &mut World, previous: &dyn Reflect, current: &dyn Reflect, paths: Vec<String>) {
+        (self.send_changed)(world, previous, current, paths)
+    }
+}
+
+/// All settings sections registered by plugins, in registration order
+#[derive(Resource, Default, Clone)]
+pub struct SettingsSections {
+    sections: Vec<SettingsSectionInfo>,
+}
+
+impl SettingsSections {
+    pub fn iter(&self) -> impl Iterator<Item = &SettingsSectionInfo> {
+        self.sections.iter()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&SettingsSectionInfo> {
+        self.sections.iter().find(|section| section.id == id)
+    }
+
+    /// Sections shown on `tab`
+    pub fn for_tab<'a>(&'a self, tab: &'a SettingsTab) -> impl Iterator<Item = &'a SettingsSectionInfo> {
+        self.sections.iter().filter(move |section| !section.hidden && &section.tab == tab)
+    }
+}
+
+/// Extension trait for registering plugin settings sections on an [`App`]
+pub trait RegisterSettingsSection {
+    /// Register `T` as a settings section
+    ///
+    /// `id` must be unique and must not clash with a field of [`Settings`] since it
+    /// prefixes the section's setting paths (e.g. `"minimap.zoom"`).
+    fn register_settings_section<T>(&mut self, id: &'static str, tab: SettingsTab, label: &'static str) -> &mut Self
+    where
+        T: Resource + FromReflect + Typed + GetTypeRegistration + Default;
+
+    /// Register `T` as a hidden section, saved in the settings file under `id`
+    /// but not shown in the menu and without change events or undo history
+    fn register_hidden_settings_section<T>(&mut self, id: &'static str) -> &mut Self
+    where
+        T: Resource + FromReflect + Typed + GetTypeRegistration + Default;
+}
+
+impl RegisterSettingsSection for App {
+    fn register_settings_section<T>(&mut self, id: &'static str, tab: SettingsTab, label: &'static str) -> &mut Self
+    where
+        T: Resource + FromReflect + Typed + GetTypeRegistration + Default,
+    {
+        register_section::<T>(self, id, tab, label, false)
+    }
+
+    fn register_hidden_settings_section<T>(&mut self, id: &'static str) -> &mut Self
+    where
+        T: Resource + FromReflect + Typed + GetTypeRegistration + Default,
+    {
+        register_section::<T>(self, id, SettingsTab::Gameplay, id, true)
+    }
+}
+
+/// Shared by [`RegisterSettingsSection`]'s methods
+fn register_section<'a, T>(app: &'a mut App, id: &'static str, tab: SettingsTab, label: &'static str, hidden: bool) -> &'a mut App
+where
+    T: Resource + FromReflect + Typed + GetTypeRegistration + Default,
+{
+    if Settings::default().reflect_path(id).is_ok() {
+        panic!("Settings section id {id:?} clashes with a built-in settings field");
+    }
+
+    let mut sections = app
+        .world_mut()
+        .get_resource_or_insert_with(SettingsSections::default);
+    if sections.get(id).is_some() {
+        panic!("Settings section {id:?} registered twice");
+    }
+
+    sections.sections.push(SettingsSectionInfo {
+        id,
+        tab: tab.clone(),
+        label,
+        type_info: T::type_info(),
+        hidden,
+        default_value: || Box::new(T::default()),
+        reflect: |world| world.get_resource::<T>().map(|value| value.as_reflect()),
+        reflect_mut: |world, f| {
+            if let Some(mut value) = world.get_resource_mut::<T>() {
+                f(value.as_reflect_mut());
+            }
+        },
+        is_changed: |world| world.is_resource_changed::<T>(),
+        send_changed: |world, previous, current, paths| {
+            if let (Some(previous), Some(current)) = (T::from_reflect(previous), T::from_reflect(current)) {
+                world.send_event(SettingsSectionChanged { previous, current, paths });
+            }
+        },
+    });
+
+    // Give a new custom tab a place after the built-in ones
+    let mut tabs = app
+        .world_mut()
+        .get_resource_or_insert_with(SettingsTabs::default);
+    if !hidden && matches!(tab, SettingsTab::Custom(_)) && !tabs.contains(&tab) {
+        let order = SECTION_TAB_ORDER + tabs.len() as i32;
+        tabs.insert(SettingsTabInfo::new(tab.clone(), tab.label()).with_order(order));
+    }
+
+    app.register_type::<T>()
+        .init_resource::<T>()
+        .add_event::<SettingsSectionChanged<T>>()
+}
+
+/// Split a setting path into its section id (if any) and the path inside the section
+fn resolve<'p>(world: &World, path: &'p str) -> (Option<SettingsSectionInfo>, &'p str) {
+    let (head, rest) = path.split_once('.').unwrap_or((path, ""));
+    let section = world
+        .get_resource::<SettingsSections>()
+        .and_then(|sections| sections.get(head))
+        .cloned();
+
+    match section {
+        Some(section) => (Some(section), rest),
+        None => (None, path),
+    }
+}
+
+/// Read the setting at `path`, looking in plugin sections before the built-in [`Settings`]
+pub fn read_setting<R>(world: &World, path: &str, f: impl FnOnce(&dyn Reflect) -> R) -> Option<R> {
+    let (section, field_path) = resolve(world, path);
+    let root = match section {
+        Some(section) => section.reflect(world)?,
+        None => world.get_resource::<Settings>()?.as_reflect(),
+    };
+
+    if field_path.is_empty() {
+        return Some(f(root));
+    }
+    root.reflect_path(field_path).ok().map(f)
+}
+
+/// Edit the setting at `path`, returning whether the path resolved
+///
+/// Settings in [`LockedSettings`] are left alone and also return `false`.
+pub fn edit_setting(world: &mut World, path: &str, f: impl FnOnce(&mut dyn Reflect)) -> bool {
+    if is_locked(world, path) {
+        return false;
+    }
+    let (section, field_path) = resolve(world, path);
+    let mut f = Some(f);
+    let mut found = false;
+    let mut edit = |root: &mut dyn Reflect| {
+        let field = if field_path.is_empty() {
+            Some(root)
+        } else {
+            root.reflect_path_mut(field_path).ok()
+        };
+        if let (Some(field), Some(f)) = (field, f.take()) {
+            f(field);
+            found = true;
+        }
+    };
+
+    match section {
+        Some(section) => section.reflect_mut(world, &mut edit),
+        None => {
+            if let Some(mut settings) = world.get_resource_mut::<Settings>() {
+                edit(settings.as_reflect_mut());
+            }
+        }
+    }
+    found
+}
+
+/// Whether the setting at `path` is in [`LockedSettings`]
+pub fn is_locked(world: &World, path: &str) -> bool {
+    world.get_resource::<LockedSettings>().is_some_and(|locks| locks.is_locked(path))
+}
+
+/// Run `f` on the reflected field declaration of the setting at `path`, for its
+/// attributes and doc comment
+pub fn read_field_info<R>(world: &World, path: &str, f: impl FnOnce(&NamedField) -> R) -> Option<R> {
+    let (parent, name) = path.rsplit_once('.')?;
+    read_setting(world, parent, |value| match value.get_represented_type_info() {
+        Some(TypeInfo::Struct(info)) => info.field(name).map(f),
+        _ => None,
+    })
+    .flatten()
+}
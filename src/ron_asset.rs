@@ -0,0 +1,144 @@
+//! Data files the settings menu loads as RON assets
+//!
+//! The theme, the font manifest and the difficulty table are each a
+//! [`RonAsset`]: a resource holding the values in use, loaded from the asset
+//! at its [`RonAssetPath`]. Until that file has loaded, or when the game has
+//! none, the resource keeps its default, the copy embedded in the plugin.
+//! Pointing the path at another file loads it, and edits to the loaded file
+//! are picked up while the game runs.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AssetLoadFailedEvent, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+
+/// A resource loaded from a RON file in the game's assets
+pub trait RonAsset: Asset + Resource + DeserializeOwned + Default + Clone + PartialEq {
+    /// File extension the loader claims, e.g. `theme.ron`
+    const EXTENSION: &'static str;
+    /// File loaded unless the host sets another [`RonAssetPath`]
+    const DEFAULT_PATH: &'static str;
+    /// What the file holds, for log messages
+    const DESCRIPTION: &'static str;
+}
+
+#[derive(Debug)]
+pub enum RonAssetError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for RonAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonAssetError::Io(err) => write!(f, "file error: {err}"),
+            RonAssetError::Parse(err) => write!(f, "invalid RON: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RonAssetError {}
+
+struct RonAssetLoader<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonAssetLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: RonAsset> AssetLoader for RonAssetLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = RonAssetError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<T, RonAssetError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await.map_err(RonAssetError::Io)?;
+        ron::from_str(&source).map_err(|err| RonAssetError::Parse(err.to_string()))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        std::slice::from_ref(&T::EXTENSION)
+    }
+}
+
+/// File the [`RonAsset`] `T` is loaded from, [`RonAsset::DEFAULT_PATH`] unless
+/// the host sets another
+#[derive(Resource, Clone, Debug)]
+pub struct RonAssetPath<T>(pub String, PhantomData<fn() -> T>);
+
+impl<T> RonAssetPath<T> {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self(path.into(), PhantomData)
+    }
+}
+
+impl<T: RonAsset> Default for RonAssetPath<T> {
+    fn default() -> Self {
+        Self::new(T::DEFAULT_PATH)
+    }
+}
+
+/// Systems taking loaded and edited [`RonAsset`]s into use, in `PreUpdate`
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RonAssetSet;
+
+/// Keeps the resource `T` in step with the file at its [`RonAssetPath`]
+pub struct RonAssetPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for RonAssetPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: RonAsset> Plugin for RonAssetPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<T>().init_resource::<RonAssetPath<T>>();
+
+        // Headless apps without assets keep the embedded copy
+        if app.world().contains_resource::<AssetServer>() {
+            app.init_asset::<T>()
+                .register_asset_loader(RonAssetLoader::<T>::default())
+                .add_systems(PreUpdate, reload_ron_asset::<T>.in_set(RonAssetSet));
+        }
+    }
+}
+
+/// Load the file at [`RonAssetPath`] whenever the path changes, and take it
+/// into use once it has loaded or been edited
+fn reload_ron_asset<T: RonAsset>(
+    mut events: EventReader<AssetEvent<T>>,
+    mut failures: EventReader<AssetLoadFailedEvent<T>>,
+    path: Res<RonAssetPath<T>>,
+    assets: Res<Assets<T>>,
+    asset_server: Res<AssetServer>,
+    mut current: ResMut<T>,
+    mut handle: Local<Handle<T>>,
+) {
+    for failure in failures.read() {
+        warn!("Keeping the current {}, {:?} didn't load: {}", T::DESCRIPTION, failure.path, failure.error);
+    }
+
+    let mut changed = false;
+    if path.is_changed() {
+        *handle = asset_server.load(path.0.clone());
+        // A file loaded before, e.g. when switching back, sends no new event
+        changed = true;
+    }
+    changed |= events
+        .read()
+        .any(|event| event.is_loaded_with_dependencies(&*handle) || event.is_modified(&*handle));
+    if let (true, Some(loaded)) = (changed, assets.get(&*handle)) {
+        current.set_if_neq(loaded.clone());
+    }
+}
@@ -1,43 +1,40 @@
 use bevy::prelude::*;
-use sf_plugin_template::MenuItemPlugin;
-
-// Module declarations
-#[path = "modules/video.rs"]
-pub mod video;
-#[path = "modules/audio.rs"]
-pub mod audio;
-#[path = "modules/gameplay.rs"]
-pub mod gameplay;
-#[path = "modules/controls.rs"]
-pub mod controls;
-#[path = "modules/interface.rs"]
-pub mod interface;
-// Re-exports
-pub use video::*;
-pub use audio::*;
-pub use gameplay::*;
-pub use controls::*;
-pub use interface::*;
-
-// Re-export plugin types
-pub use audio::AudioPlugin;
-pub use controls::ControlsPlugin;
-pub use gameplay::GameplayPlugin;
-pub use interface::InterfacePlugin;
-pub use video::VideoPlugin;
+
+use crate::colorblind::ColorblindMode;
+use crate::difficulty::DifficultyModifiers;
+use crate::display::CursorSize;
+use crate::localization::Language;
+use crate::subtitles::SubtitleSize;
+use crate::widgets::SettingRange;
 
 // Main settings components
-#[derive(Component, Reflect, Default, Clone)]
+#[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct VideoSettings {
     pub display_mode: DisplayMode,
+    /// One of [`SUPPORTED_RESOLUTIONS`](crate::validation::SUPPORTED_RESOLUTIONS)
     pub resolution: (u32, u32),
     pub graphics_quality: GraphicsQuality,
     pub vsync: bool,
+    #[reflect(@SettingRange::new(30.0, 360.0, 10.0))]
     pub fps_limit: Option<u32>,
+    #[reflect(@SettingRange::new(0.5, 2.0, 0.1))]
     pub ui_scale: f32,
 }
 
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            display_mode: DisplayMode::default(),
+            resolution: (1920, 1080),
+            graphics_quality: GraphicsQuality::default(),
+            vsync: true,
+            fps_limit: None,
+            ui_scale: 1.0,
+        }
+    }
+}
+
 #[derive(Reflect, Default, Clone)]
 pub enum DisplayMode {
     #[default]
@@ -56,24 +53,158 @@ pub enum GraphicsQuality {
 }
 
 /// Audio settings component
-#[derive(Component, Reflect, Default, Clone)]
+#[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct AudioSettings {
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.05))]
     pub master_volume: f32,
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.05))]
     pub music_volume: f32,
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.05))]
     pub sfx_volume: f32,
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.05))]
     pub voice_volume: f32,
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.05))]
     pub ambient_volume: f32,
     pub mute_when_inactive: bool,
 }
 
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            music_volume: 0.7,
+            sfx_volume: 1.0,
+            voice_volume: 1.0,
+            ambient_volume: 0.8,
+            mute_when_inactive: true,
+        }
+    }
+}
+
 /// Game settings component
-#[derive(Component, Reflect, Default, Clone)]
+#[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct GameplaySettings {
     pub difficulty: Difficulty,
+    /// Modifiers used when `difficulty` is Custom
+    pub custom_difficulty: DifficultyModifiers,
     pub show_tutorials: bool,
+    /// Show spoken lines as text at the bottom of the screen
     pub subtitles: bool,
+    /// Also describe important sound effects, e.g. "[Explosion]"
+    pub closed_captions: bool,
+    pub subtitle_style: SubtitleStyle,
+    /// Speed of single player games; multiplayer games always run at normal speed
+    #[reflect(@SettingRange::new(0.5, 2.0, 0.25))]
+    pub game_speed: f32,
+    pub autosave: AutosaveSettings,
+    pub alerts: AlertSettings,
+    /// Which units show a health bar
+    pub health_bars: HealthBarVisibility,
+    /// When the waypoints of queued commands are drawn
+    pub waypoints: WaypointDisplay,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::default(),
+            custom_difficulty: DifficultyModifiers::default(),
+            show_tutorials: true,
+            subtitles: false,
+            closed_captions: false,
+            subtitle_style: SubtitleStyle::default(),
+            game_speed: 1.0,
+            autosave: AutosaveSettings::default(),
+            alerts: AlertSettings::default(),
+            health_bars: HealthBarVisibility::default(),
+            waypoints: WaypointDisplay::default(),
+        }
+    }
+}
+
+#[derive(Reflect, Clone)]
+pub struct SubtitleStyle {
+    pub size: SubtitleSize,
+    /// Opacity of the box behind each line
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.1))]
+    pub background_opacity: f32,
+    /// Start each line with the name of who is speaking
+    pub speaker_names: bool,
+    /// Show each speaker's name in its own colour
+    pub speaker_colors: bool,
+}
+
+impl Default for SubtitleStyle {
+    fn default() -> Self {
+        Self {
+            size: SubtitleSize::Medium,
+            background_opacity: 0.6,
+            speaker_names: true,
+            speaker_colors: true,
+        }
+    }
+}
+
+#[derive(Reflect, Clone)]
+pub struct AutosaveSettings {
+    /// Minutes between autosaves, never when unset
+    #[reflect(@SettingRange::new(5.0, 60.0, 5.0))]
+    pub interval_minutes: Option<u32>,
+    /// Autosaves kept before the oldest is overwritten
+    #[reflect(@SettingRange::new(1.0, 10.0, 1.0))]
+    pub slots: u32,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            interval_minutes: Some(10),
+            slots: 3,
+        }
+    }
+}
+
+/// Events the game alerts the player to
+#[derive(Reflect, Clone, PartialEq, Debug)]
+pub struct AlertSettings {
+    /// Your units or buildings are under attack
+    pub under_attack: bool,
+    /// A worker has nothing to do
+    pub idle_workers: bool,
+    /// An upgrade finished researching
+    pub research_complete: bool,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            under_attack: true,
+            idle_workers: true,
+            research_complete: true,
+        }
+    }
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HealthBarVisibility {
+    Always,
+    /// Only for selected units
+    Selected,
+    /// For units below full health, and selected ones
+    #[default]
+    Damaged,
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WaypointDisplay {
+    /// For the selected units
+    #[default]
+    Selected,
+    /// Only while the queue modifier is held
+    Queuing,
+    Never,
 }
 
 #[derive(Reflect, Default, Clone)]
@@ -82,21 +213,292 @@ pub enum Difficulty {
     #[default]
     Normal,
     Hard,
+    /// Modifiers set one by one in the Gameplay tab
+    Custom,
 }
 
-#[derive(Component, Reflect, Default, Clone)]
+#[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct ControlsSettings {
+    #[reflect(@SettingRange::new(0.1, 5.0, 0.1))]
     pub mouse_sensitivity: f32,
     pub invert_y: bool,
+    /// Extra speed applied to fast mouse movements
+    pub mouse_acceleration: AccelerationCurve,
+    /// Mouse motion (in pixels per frame) ignored by the camera
+    #[reflect(@SettingRange::new(0.0, 10.0, 0.5))]
+    pub mouse_dead_zone: f32,
+    pub camera: CameraSettings,
+    pub controller: ControllerSettings,
     pub keybinds: KeybindSettings,
 }
 
-#[derive(Component, Reflect, Default, Clone)]
+impl Default for ControlsSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 1.0,
+            invert_y: false,
+            mouse_acceleration: AccelerationCurve::Off,
+            mouse_dead_zone: 0.0,
+            camera: CameraSettings::default(),
+            controller: ControllerSettings::default(),
+            keybinds: KeybindSettings::default(),
+        }
+    }
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Debug)]
+pub enum AccelerationCurve {
+    #[default]
+    Off,
+    /// Multiplier grows linearly with pointer speed
+    Linear {
+        #[reflect(@SettingRange::new(0.0, 5.0, 0.1))]
+        factor: f32,
+    },
+    /// Multiplier grows with the square of pointer speed
+    Quadratic {
+        #[reflect(@SettingRange::new(0.0, 5.0, 0.1))]
+        factor: f32,
+    },
+}
+
+/// RTS camera movement settings
+#[derive(Reflect, Clone)]
+pub struct CameraSettings {
+    /// Pan when the cursor touches the window edge
+    pub edge_scroll: bool,
+    /// Distance from the window edge, in logical pixels, that triggers edge scrolling
+    #[reflect(@SettingRange::new(1.0, 100.0, 1.0))]
+    pub edge_scroll_margin: f32,
+    /// Edge scroll speed in screen units per second
+    #[reflect(@SettingRange::new(100.0, 3000.0, 50.0))]
+    pub edge_scroll_speed: f32,
+    /// Pan key speed in screen units per second
+    #[reflect(@SettingRange::new(100.0, 3000.0, 50.0))]
+    pub keyboard_pan_speed: f32,
+    /// Zoom per scroll wheel line
+    #[reflect(@SettingRange::new(0.1, 5.0, 0.1))]
+    pub zoom_speed: f32,
+    /// At most `max_zoom`
+    #[reflect(@SettingRange::new(0.1, 10.0, 0.1))]
+    pub min_zoom: f32,
+    #[reflect(@SettingRange::new(0.1, 10.0, 0.1))]
+    pub max_zoom: f32,
+    /// Pan by dragging with the middle mouse button
+    pub middle_mouse_pan: bool,
+    /// Confine the cursor to the game window
+    pub lock_cursor: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            edge_scroll: true,
+            edge_scroll_margin: 10.0,
+            edge_scroll_speed: 800.0,
+            keyboard_pan_speed: 800.0,
+            zoom_speed: 1.0,
+            min_zoom: 0.5,
+            max_zoom: 3.0,
+            middle_mouse_pan: true,
+            lock_cursor: false,
+        }
+    }
+}
+
+/// Gamepad settings
+#[derive(Reflect, Clone)]
+pub struct ControllerSettings {
+    pub left_stick: StickSettings,
+    pub right_stick: StickSettings,
+    /// Trigger travel (0-1) at which a trigger counts as pressed
+    #[reflect(@SettingRange::new(0.05, 1.0, 0.05))]
+    pub left_trigger_threshold: f32,
+    #[reflect(@SettingRange::new(0.05, 1.0, 0.05))]
+    pub right_trigger_threshold: f32,
+    /// Virtual cursor speed at full deflection, in logical pixels per second
+    #[reflect(@SettingRange::new(100.0, 3000.0, 50.0))]
+    pub cursor_speed: f32,
+    /// Multiplier for rumble requests, 0 disables vibration
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.1))]
+    pub rumble_strength: f32,
+}
+
+impl Default for ControllerSettings {
+    fn default() -> Self {
+        Self {
+            left_stick: StickSettings::default(),
+            right_stick: StickSettings::default(),
+            left_trigger_threshold: 0.5,
+            right_trigger_threshold: 0.5,
+            cursor_speed: 900.0,
+            rumble_strength: 1.0,
+        }
+    }
+}
+
+/// Dead zones and response curve for a single analog stick
+#[derive(Reflect, Clone)]
+pub struct StickSettings {
+    /// Deflection below which the stick reads as centred, less than `outer_dead_zone`
+    #[reflect(@SettingRange::new(0.0, 0.9, 0.01))]
+    pub inner_dead_zone: f32,
+    /// Deflection above which the stick reads as fully pushed
+    #[reflect(@SettingRange::new(0.1, 1.0, 0.01))]
+    pub outer_dead_zone: f32,
+    pub response_curve: ResponseCurve,
+    /// Exponent used by [`ResponseCurve::Custom`]
+    #[reflect(@SettingRange::new(0.2, 5.0, 0.1))]
+    pub custom_exponent: f32,
+}
+
+impl Default for StickSettings {
+    fn default() -> Self {
+        Self {
+            inner_dead_zone: 0.1,
+            outer_dead_zone: 0.95,
+            response_curve: ResponseCurve::Linear,
+            custom_exponent: 1.5,
+        }
+    }
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Debug)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    Quadratic,
+    /// Deflection raised to `custom_exponent`
+    Custom,
+}
+
+#[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct InterfaceSettings {
+    /// Language of the menu text
+    pub language: Language,
+    #[reflect(@SettingRange::new(0.5, 2.0, 0.1))]
     pub ui_scale: f32,
-    pub colorblind_mode: u8,
+    pub colorblind_mode: ColorblindMode,
+    /// How strongly colours are corrected and team colours remapped for `colorblind_mode`
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.1))]
+    pub colorblind_strength: f32,
+    /// Text size relative to the UI scale
+    #[reflect(@SettingRange::new(0.8, 2.0, 0.1))]
+    pub text_scale: f32,
+    /// Plain, strongly contrasting menu colours
+    pub high_contrast: bool,
+    /// Turn off menu transitions, screen shake and other motion effects
+    pub reduce_motion: bool,
+    /// Show text in a typeface that is easier to read with dyslexia
+    pub dyslexia_font: bool,
+    pub cursor_size: CursorSize,
+    pub hud: HudSettings,
+}
+
+impl Default for InterfaceSettings {
+    fn default() -> Self {
+        Self {
+            language: Language::English,
+            ui_scale: 1.0,
+            colorblind_mode: ColorblindMode::Off,
+            colorblind_strength: 1.0,
+            text_scale: 1.0,
+            high_contrast: false,
+            reduce_motion: false,
+            dyslexia_font: false,
+            cursor_size: CursorSize::Normal,
+            hud: HudSettings::default(),
+        }
+    }
+}
+
+/// Arrangement of the in-match HUD
+#[derive(Reflect, Clone, PartialEq, Debug)]
+pub struct HudSettings {
+    pub minimap_position: ScreenCorner,
+    /// Minimap size relative to the default
+    #[reflect(@SettingRange::new(0.5, 1.5, 0.1))]
+    pub minimap_size: f32,
+    pub minimap_rotation: MinimapRotation,
+    pub command_card_layout: CommandCardLayout,
+    pub resource_bar_position: ResourceBarPosition,
+    /// Opacity of the chat background
+    #[reflect(@SettingRange::new(0.0, 1.0, 0.1))]
+    pub chat_opacity: f32,
+    /// Seconds before chat messages fade out
+    #[reflect(@SettingRange::new(2.0, 30.0, 1.0))]
+    pub chat_fade_time: f32,
+    /// Seconds the pointer rests on something before its tooltip shows
+    #[reflect(@SettingRange::new(0.0, 2.0, 0.1))]
+    pub tooltip_delay: f32,
+    pub tooltip_detail: TooltipDetail,
+    /// Show frame rate and network latency in a corner of the screen
+    pub performance_overlay: bool,
+}
+
+impl Default for HudSettings {
+    fn default() -> Self {
+        Self {
+            minimap_position: ScreenCorner::BottomLeft,
+            minimap_size: 1.0,
+            minimap_rotation: MinimapRotation::NorthUp,
+            command_card_layout: CommandCardLayout::Grid,
+            resource_bar_position: ResourceBarPosition::TopRight,
+            chat_opacity: 0.6,
+            chat_fade_time: 10.0,
+            tooltip_delay: 0.5,
+            tooltip_detail: TooltipDetail::Detailed,
+            performance_overlay: false,
+        }
+    }
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScreenCorner {
+    #[default]
+    BottomLeft,
+    BottomRight,
+    TopLeft,
+    TopRight,
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MinimapRotation {
+    /// North at the top
+    #[default]
+    NorthUp,
+    /// Turned 45 degrees to match the isometric camera
+    Isometric,
+    /// Turned with the camera
+    FollowCamera,
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommandCardLayout {
+    /// Grid of buttons in the bottom corner opposite the minimap
+    #[default]
+    Grid,
+    /// Single row of buttons along the bottom centre
+    Row,
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResourceBarPosition {
+    TopLeft,
+    TopCenter,
+    #[default]
+    TopRight,
+}
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TooltipDetail {
+    /// Name and hotkey only
+    Basic,
+    /// Also cost, stats and description
+    #[default]
+    Detailed,
 }
 
 #[derive(Component, Reflect, Clone)]
@@ -119,8 +521,8 @@ impl Default for KeybindSettings {
     }
 }
 
-#[derive(Component, Reflect, Default, Clone)]
-#[reflect(Component)]
+#[derive(Component, Resource, Reflect, Default, Clone)]
+#[reflect(Component, Resource)]
 pub struct Settings {
     pub video: VideoSettings,
     pub audio: AudioSettings,
@@ -135,7 +537,7 @@ pub struct SettingsState {
     pub is_visible: bool,
 }
 
-#[derive(Default, Reflect, Clone)]
+#[derive(Default, Reflect, Clone, PartialEq, Eq, Debug)]
 pub enum SettingsTab {
     #[default]
     Video,
@@ -143,33 +545,20 @@ pub enum SettingsTab {
     Gameplay,
     Controls,
     Interface,
+    /// Tab added by a plugin settings section, identified by its label
+    Custom(String),
 }
 
-pub struct SettingsPlugin;
-
-impl Plugin for SettingsPlugin {
-    fn build(&self, app: &mut App) {
-        app
-            .init_resource::<SettingsState>()
-            .register_type::<Settings>()
-            .register_type::<SettingsTab>()
-            .register_type::<VideoSettings>()
-            .register_type::<AudioSettings>()
-            .register_type::<GameplaySettings>()
-            .register_type::<ControlsSettings>()
-            .register_type::<InterfaceSettings>()
-            .register_type::<KeybindSettings>()
-            .add_plugins((
-                VideoPlugin,
-                AudioPlugin,
-                GameplayPlugin,
-                ControlsPlugin,
-                InterfacePlugin,
-            ))
-            .add_systems(Startup, load_settings);
+impl SettingsTab {
+    /// Label shown on the tab button
+    pub fn label(&self) -> &str {
+        match self {
+            SettingsTab::Video => "Video",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Gameplay => "Gameplay",
+            SettingsTab::Controls => "Controls",
+            SettingsTab::Interface => "Interface",
+            SettingsTab::Custom(label) => label,
+        }
     }
 }
-
-fn load_settings() {
-    // TODO: Load from config file
-}
@@ -0,0 +1,287 @@
+//! Subtitles and closed captions
+//!
+//! Game code sends a [`SubtitleLine`] for every spoken line, and a caption for
+//! sound effects a player who can't hear them should know about:
+//!
+//! ```ignore
+//! fn warn_of_attack(mut subtitles: EventWriter<SubtitleLine>) {
+//!     subtitles.send(
+//!         SubtitleLine::new("Our base is under attack!", Duration::from_secs(3)).with_speaker("Adjutant"),
+//!     );
+//!     subtitles.send(SubtitleLine::caption("Explosion", Duration::from_secs(2)));
+//! }
+//! ```
+//!
+//! Lines stay up for their duration of game time and the newest
+//! [`MAX_VISIBLE_LINES`] are shown at the bottom of the screen while
+//! [`GameplaySettings::subtitles`] is on. Captions are shown in square brackets
+//! and only with [`GameplaySettings::closed_captions`]. How lines look follows
+//! [`SubtitleStyle`] and the text size option; speaker colours come from
+//! [`SpeakerColors`].
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::display::UiAccessibility;
+use crate::fonts::FontRole;
+use crate::settings::{GameplaySettings, Settings, SubtitleStyle};
+use crate::SettingsMenuEntity;
+
+/// Lines shown at once; older lines are hidden while newer ones are up
+pub const MAX_VISIBLE_LINES: usize = 3;
+
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubtitleSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl SubtitleSize {
+    /// Font size before the text size option is applied
+    pub fn font_size(self) -> f32 {
+        match self {
+            SubtitleSize::Small => 18.0,
+            SubtitleSize::Medium => 24.0,
+            SubtitleSize::Large => 32.0,
+            SubtitleSize::ExtraLarge => 40.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubtitleKind {
+    Speech,
+    /// A caption describing a sound effect
+    SoundEffect,
+}
+
+/// Line of subtitles for the game to show; the text is shown as given, so
+/// translate it first
+#[derive(Event, Clone, Debug)]
+pub struct SubtitleLine {
+    pub speaker: Option<String>,
+    pub text: String,
+    pub duration: Duration,
+    pub kind: SubtitleKind,
+}
+
+impl SubtitleLine {
+    pub fn new(text: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            speaker: None,
+            text: text.into(),
+            duration,
+            kind: SubtitleKind::Speech,
+        }
+    }
+
+    /// Caption for a sound effect, e.g. "Explosion"
+    pub fn caption(text: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            kind: SubtitleKind::SoundEffect,
+            ..Self::new(text, duration)
+        }
+    }
+
+    pub fn with_speaker(mut self, speaker: impl Into<String>) -> Self {
+        self.speaker = Some(speaker.into());
+        self
+    }
+
+    fn shown_with(&self, gameplay: &GameplaySettings) -> bool {
+        gameplay.subtitles && (self.kind == SubtitleKind::Speech || gameplay.closed_captions)
+    }
+}
+
+/// Lines that are up, oldest first
+#[derive(Resource, Default)]
+pub struct SubtitleQueue {
+    lines: VecDeque<(SubtitleLine, Timer)>,
+}
+
+impl SubtitleQueue {
+    pub fn lines(&self) -> impl Iterator<Item = &SubtitleLine> {
+        self.lines.iter().map(|(line, _)| line)
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+/// Name colours for speakers, used while [`SubtitleStyle::speaker_colors`] is on
+///
+/// Speakers without a colour of their own get one from `palette`, the same one
+/// every time.
+#[derive(Resource, Clone, Debug)]
+pub struct SpeakerColors {
+    pub colors: HashMap<String, Color>,
+    pub palette: Vec<Color>,
+}
+
+impl Default for SpeakerColors {
+    fn default() -> Self {
+        Self {
+            colors: HashMap::new(),
+            palette: vec![
+                Color::srgb(1.0, 0.85, 0.3),
+                Color::srgb(0.45, 0.8, 1.0),
+                Color::srgb(0.6, 1.0, 0.55),
+                Color::srgb(1.0, 0.6, 0.85),
+                Color::srgb(1.0, 0.65, 0.4),
+                Color::srgb(0.75, 0.7, 1.0),
+            ],
+        }
+    }
+}
+
+impl SpeakerColors {
+    pub fn set(&mut self, speaker: impl Into<String>, color: Color) {
+        self.colors.insert(speaker.into(), color);
+    }
+
+    pub fn color(&self, speaker: &str) -> Color {
+        if let Some(color) = self.colors.get(speaker) {
+            return *color;
+        }
+        if self.palette.is_empty() {
+            return SUBTITLE_TEXT_COLOR;
+        }
+        let hash = speaker.bytes().fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+        self.palette[hash % self.palette.len()]
+    }
+}
+
+const SUBTITLE_TEXT_COLOR: Color = Color::WHITE;
+
+/// Bottom of screen node holding the subtitle lines
+#[derive(Component)]
+pub struct SubtitleLayer;
+
+pub struct SubtitlesPlugin;
+
+impl Plugin for SubtitlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SubtitleLine>()
+            .init_resource::<SubtitleQueue>()
+            .init_resource::<SpeakerColors>()
+            .register_type::<SubtitleSize>()
+            .add_systems(Update, (queue_subtitles, expire_subtitles, show_subtitles).chain());
+    }
+}
+
+fn queue_subtitles(mut lines: EventReader<SubtitleLine>, mut queue: ResMut<SubtitleQueue>) {
+    for line in lines.read() {
+        let timer = Timer::new(line.duration, TimerMode::Once);
+        queue.lines.push_back((line.clone(), timer));
+    }
+}
+
+/// Drop lines whose time is up, only flagging the queue as changed when one goes
+fn expire_subtitles(time: Res<Time>, mut queue: ResMut<SubtitleQueue>) {
+    let lines = &mut queue.bypass_change_detection().lines;
+    for (_, timer) in lines.iter_mut() {
+        timer.tick(time.delta());
+    }
+    let before = lines.len();
+    lines.retain(|(_, timer)| !timer.finished());
+    if lines.len() != before {
+        queue.set_changed();
+    }
+}
+
+/// Rebuild the subtitle layer when the lines or how they are shown change
+fn show_subtitles(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    queue: Res<SubtitleQueue>,
+    options: Res<UiAccessibility>,
+    speakers: Res<SpeakerColors>,
+    mut layers: Query<(Entity, Ref<SubtitleLayer>, &mut Visibility)>,
+) {
+    let Ok((layer, marker, mut visibility)) = layers.get_single_mut() else {
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    bottom: Val::Percent(8.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                focus_policy: FocusPolicy::Pass,
+                // Above the HUD, below the settings panel
+                z_index: ZIndex::Global(50),
+                ..default()
+            },
+            Name::new("Subtitles"),
+            SubtitleLayer,
+            SettingsMenuEntity,
+        ));
+        return;
+    };
+
+    let gameplay = &settings.gameplay;
+    visibility.set_if_neq(if gameplay.subtitles { Visibility::Inherited } else { Visibility::Hidden });
+    let restyle = settings.is_changed() || options.is_changed() || speakers.is_changed();
+    if !queue.is_changed() && !restyle && !marker.is_added() {
+        return;
+    }
+
+    let shown: Vec<&SubtitleLine> = queue.lines().filter(|line| line.shown_with(gameplay)).collect();
+    let shown = &shown[shown.len().saturating_sub(MAX_VISIBLE_LINES)..];
+    let mut layer = commands.entity(layer);
+    layer.despawn_descendants();
+    layer.with_children(|parent| {
+        for line in shown {
+            spawn_subtitle_line(parent, line, &gameplay.subtitle_style, &options, &speakers);
+        }
+    });
+}
+
+fn spawn_subtitle_line(
+    parent: &mut ChildBuilder,
+    line: &SubtitleLine,
+    style: &SubtitleStyle,
+    options: &UiAccessibility,
+    speakers: &SpeakerColors,
+) {
+    let font_size = options.font_size(style.size.font_size());
+    let text_style = |color: Color| TextStyle {
+        font_size,
+        color,
+        ..default()
+    };
+
+    let mut sections = Vec::new();
+    if let (true, Some(speaker)) = (style.speaker_names, &line.speaker) {
+        let color = if style.speaker_colors { speakers.color(speaker) } else { SUBTITLE_TEXT_COLOR };
+        sections.push(TextSection::new(format!("{speaker}: "), text_style(color)));
+    }
+    let text = match line.kind {
+        SubtitleKind::Speech => line.text.clone(),
+        SubtitleKind::SoundEffect => format!("[{}]", line.text),
+    };
+    sections.push(TextSection::new(text, text_style(SUBTITLE_TEXT_COLOR)));
+
+    parent.spawn(NodeBundle {
+        style: Style {
+            margin: UiRect::top(Val::Px(4.0)),
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+            max_width: Val::Percent(70.0),
+            ..default()
+        },
+        background_color: Color::BLACK.with_alpha(style.background_opacity).into(),
+        ..default()
+    }).with_children(|parent| {
+        parent.spawn((TextBundle::from_sections(sections), FontRole::Regular));
+    });
+}
@@ -0,0 +1,269 @@
+//! Tabs of the settings menu
+//!
+//! Every tab shown in the menu is described by a [`SettingsTabInfo`] in the
+//! [`SettingsTabs`] resource. The built-in tabs are registered from
+//! [`SettingsMenuConfig::tabs`](crate::SettingsMenuConfig), tabs used by plugin
+//! settings sections are registered automatically, and plugins can add or
+//! restyle tabs with [`RegisterSettingsTab::register_settings_tab`]:
+//!
+//! ```ignore
+//! app.register_settings_tab(
+//!     SettingsTabInfo::new(SettingsTab::Custom("Replays".into()), "Replays")
+//!         .with_icon("icons/replays.png")
+//!         .with_order(250),
+//! );
+//! ```
+//!
+//! Tab bars are built with [`spawn_tab_bar`] and tab content is (re)spawned
+//! into any [`SettingsTabContent`] node whenever the current tab changes.
+
+use bevy::a11y::accesskit::NodeBuilder;
+use bevy::prelude::*;
+
+use crate::fonts::FontRole;
+use crate::localization::{self, LocalizedText};
+use crate::modules::{
+    audio::spawn_audio_settings,
+    controls::spawn_controls_settings,
+    gameplay::spawn_gameplay_settings,
+    interface::spawn_interface_settings,
+    video::spawn_video_settings,
+};
+use sf_ui_common::accessibility::{AccessibilityNode, Role};
+
+use crate::registry::SettingsSections;
+use crate::settings::{SettingsState, SettingsTab};
+use crate::theme::{SettingsTheme, ThemeElement};
+use crate::widgets::spawn_section_controls;
+
+/// Order given to tabs of plugin settings sections that weren't registered
+/// explicitly, placing them after the built-in tabs
+pub const SECTION_TAB_ORDER: i32 = 1000;
+
+/// A tab of the settings menu
+#[derive(Clone, Debug)]
+pub struct SettingsTabInfo {
+    pub tab: SettingsTab,
+    /// Text on the tab button
+    pub label: String,
+    /// Asset path of an image shown before the label
+    pub icon: Option<String>,
+    /// Tabs are shown in ascending order, ties in registration order
+    pub order: i32,
+}
+
+impl SettingsTabInfo {
+    pub fn new(tab: SettingsTab, label: impl Into<String>) -> Self {
+        Self {
+            tab,
+            label: label.into(),
+            icon: None,
+            order: 0,
+        }
+    }
+
+    pub fn with_icon(mut self, path: impl Into<String>) -> Self {
+        self.icon = Some(path.into());
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+/// Tabs shown in the settings menu, kept sorted by [`SettingsTabInfo::order`]
+#[derive(Resource, Default, Clone)]
+pub struct SettingsTabs {
+    tabs: Vec<SettingsTabInfo>,
+}
+
+impl SettingsTabs {
+    pub fn iter(&self) -> impl Iterator<Item = &SettingsTabInfo> {
+        self.tabs.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn get(&self, tab: &SettingsTab) -> Option<&SettingsTabInfo> {
+        self.tabs.iter().find(|info| &info.tab == tab)
+    }
+
+    pub fn contains(&self, tab: &SettingsTab) -> bool {
+        self.get(tab).is_some()
+    }
+
+    /// Add a tab, replacing any existing entry for the same tab
+    pub fn insert(&mut self, info: SettingsTabInfo) {
+        self.tabs.retain(|existing| existing.tab != info.tab);
+        // After every tab with the same order, so ties keep registration order
+        let index = self.tabs.partition_point(|existing| existing.order <= info.order);
+        self.tabs.insert(index, info);
+    }
+}
+
+/// Extension trait for adding tabs to the settings menu on an [`App`]
+pub trait RegisterSettingsTab {
+    /// Add a tab, or replace the label, icon and order of an existing one
+    fn register_settings_tab(&mut self, info: SettingsTabInfo) -> &mut Self;
+}
+
+impl RegisterSettingsTab for App {
+    fn register_settings_tab(&mut self, info: SettingsTabInfo) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(SettingsTabs::default)
+            .insert(info);
+        self
+    }
+}
+
+/// Button selecting a settings tab
+#[derive(Component, Clone)]
+pub struct SettingsTabButton(pub SettingsTab);
+
+/// Node that holds the content of the current settings tab
+#[derive(Component)]
+pub struct SettingsTabContent;
+
+pub struct SettingsTabsPlugin;
+
+impl Plugin for SettingsTabsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsTabs>()
+            .add_systems(Update, (
+                select_settings_tab,
+                (highlight_current_tab, rebuild_tab_content).after(select_settings_tab),
+            ));
+    }
+}
+
+/// Spawn a row of buttons for every registered tab
+///
+/// The button of [`SettingsState::current_tab`] is highlighted once spawned.
+pub fn spawn_tab_bar(parent: &mut ChildBuilder, tabs: &SettingsTabs, asset_server: &AssetServer) {
+    parent.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            ..default()
+        },
+        ThemeElement::TabBar,
+        Name::new("SettingsTabs"),
+        AccessibilityNode::from(NodeBuilder::new(Role::TabList)),
+    )).with_children(|parent| {
+        let tab_width = 100.0 / tabs.len().max(1) as f32;
+        for info in tabs.iter() {
+            parent.spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Percent(tab_width),
+                        height: Val::Percent(100.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                ThemeElement::Tab,
+                SettingsTabButton(info.tab.clone()),
+            )).with_children(|parent| {
+                if let Some(icon) = &info.icon {
+                    parent.spawn((
+                        ImageBundle {
+                            image: UiImage::new(asset_server.load(icon.clone())),
+                            ..default()
+                        },
+                        ThemeElement::TabIcon,
+                    ));
+                }
+                let mut text = parent.spawn((
+                    TextBundle::from_section(info.label.clone(), TextStyle::default()),
+                    ThemeElement::TabLabel,
+                    FontRole::Bold,
+                ));
+                // A label given by the host is shown as it is
+                if info.label == info.tab.label() {
+                    text.insert(LocalizedText::new(localization::tab_id(&info.tab)).with_fallback(info.label.clone()));
+                }
+            });
+        }
+    });
+}
+
+fn select_settings_tab(
+    mut state: ResMut<SettingsState>,
+    query: Query<(&Interaction, &SettingsTabButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction == Interaction::Pressed && state.current_tab != button.0 {
+            state.current_tab = button.0.clone();
+        }
+    }
+}
+
+/// Highlight the current tab's button when it's spawned and after the tab or
+/// the theme changes
+fn highlight_current_tab(
+    state: Res<SettingsState>,
+    theme: Res<SettingsTheme>,
+    mut buttons: Query<(Ref<SettingsTabButton>, &mut BackgroundColor)>,
+) {
+    let refresh = state.is_changed() || theme.is_changed();
+    for (button, mut background) in &mut buttons {
+        if refresh || button.is_added() {
+            *background = theme.tab_background(button.0 == state.current_tab);
+        }
+    }
+}
+
+/// Respawn tab content when the tab changes or a new content node appears
+fn rebuild_tab_content(
+    mut commands: Commands,
+    state: Res<SettingsState>,
+    asset_server: Res<AssetServer>,
+    sections: Option<Res<SettingsSections>>,
+    content: Query<(Entity, Ref<SettingsTabContent>)>,
+) {
+    for (entity, marker) in &content {
+        if !state.is_changed() && !marker.is_added() {
+            continue;
+        }
+
+        commands.entity(entity).despawn_descendants().with_children(|parent| {
+            spawn_tab(parent, &state.current_tab, &asset_server, sections.as_deref());
+        });
+    }
+}
+
+/// Spawn the content of `tab`, followed by any plugin sections placed on it
+pub fn spawn_tab(
+    parent: &mut ChildBuilder,
+    tab: &SettingsTab,
+    asset_server: &Res<AssetServer>,
+    sections: Option<&SettingsSections>,
+) {
+    match tab {
+        SettingsTab::Video => spawn_video_settings(parent, asset_server),
+        SettingsTab::Audio => spawn_audio_settings(parent, asset_server),
+        SettingsTab::Gameplay => spawn_gameplay_settings(parent, asset_server),
+        SettingsTab::Controls => spawn_controls_settings(parent, asset_server),
+        SettingsTab::Interface => spawn_interface_settings(parent, asset_server),
+        SettingsTab::Custom(_) => {}
+    }
+
+    if let Some(sections) = sections {
+        for section in sections.for_tab(tab) {
+            spawn_section_controls(parent, section);
+        }
+    }
+}
@@ -0,0 +1,499 @@
+//! Look of the settings menu
+//!
+//! Colours, spacing, corner radii, font sizes and widget sizes come from a
+//! [`SettingsTheme`] asset, by default [`DEFAULT_THEME_PATH`]. Every node the
+//! menu spawns carries a [`ThemeElement`] and is restyled from the theme when
+//! it appears and whenever the theme changes, so edits to the file show up in
+//! the open menu. Without a theme in the game's assets the one embedded in the
+//! plugin is used.
+//!
+//! Hosts can load another theme at any time, e.g. one per game mode:
+//!
+//! ```ignore
+//! fn use_campaign_theme(mut path: ResMut<SettingsThemePath>) {
+//!     path.0 = "themes/campaign.theme.ron".into();
+//! }
+//! ```
+//!
+//! Only the parts of a node the theme describes are written; layout such as
+//! flex direction and alignment stays as spawned.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use sf_ui_common::colors;
+
+use crate::display::{MenuTextBase, UiAccessibilitySet};
+use crate::ron_asset::{RonAsset, RonAssetPath, RonAssetPlugin};
+
+/// Theme the game ships in its assets
+pub const DEFAULT_THEME_PATH: &str = "themes/settings.theme.ron";
+
+/// Theme used until the game's theme has loaded, or when it has none
+const EMBEDDED_THEME: &str = include_str!("../assets/themes/settings.theme.ron");
+
+/// Colour in a theme file, either `"#rrggbb"`/`"#rrggbbaa"` or the name of a
+/// shared StrategyForge colour such as `"button.normal"`
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        let color = match value.as_str() {
+            "button.normal" => colors::button::NORMAL,
+            "button.hovered" => colors::button::HOVERED,
+            "button.pressed" => colors::button::PRESSED,
+            "focus.highlight" => colors::focus::HIGHLIGHT,
+            "text.normal" => colors::text::NORMAL,
+            hex => Srgba::hex(hex).map_err(|err| format!("{value:?} is not a colour: {err}"))?.into(),
+        };
+        Ok(ThemeColor(color))
+    }
+}
+
+impl From<ThemeColor> for Color {
+    fn from(color: ThemeColor) -> Self {
+        color.0
+    }
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ThemeColors {
+    pub text: ThemeColor,
+    /// Labels, titles and tabs
+    pub bright_text: ThemeColor,
+    /// The undo/redo hint
+    pub muted_text: ThemeColor,
+    pub error: ThemeColor,
+    /// Background of the standalone panel
+    pub panel: ThemeColor,
+    /// Background of the tab content in the host menu
+    pub content: ThemeColor,
+    pub tab_bar: ThemeColor,
+    pub tab: ThemeColor,
+    pub tab_selected: ThemeColor,
+    pub row: ThemeColor,
+    pub button: ThemeColor,
+    pub button_hovered: ThemeColor,
+    pub button_pressed: ThemeColor,
+    /// Buttons with keyboard or gamepad focus
+    pub focused: ThemeColor,
+    pub plot_background: ThemeColor,
+    pub plot_border: ThemeColor,
+    /// Dead zone rings of the stick visualizer
+    pub dead_zone: ThemeColor,
+    pub raw_input: ThemeColor,
+    pub processed_input: ThemeColor,
+    /// Elements of the HUD preview, kept apart from each other in high contrast too
+    pub hud_minimap: ThemeColor,
+    pub hud_command_card: ThemeColor,
+    pub hud_resource_bar: ThemeColor,
+    pub hud_chat: ThemeColor,
+    pub hud_performance_overlay: ThemeColor,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ThemeSpacing {
+    /// Around the menu and the tab content
+    pub panel_padding: f32,
+    /// Below the menu title
+    pub title_margin: f32,
+    /// Above and below each setting row
+    pub row_margin: f32,
+    /// Left and right inside each setting row
+    pub row_padding: f32,
+    /// Above and below section headings and groups of controls
+    pub section_margin: f32,
+    /// Either side of a stepper's value
+    pub value_margin: f32,
+    pub button_margin: f32,
+    pub small_button_margin: f32,
+    /// Above the undo/redo hint
+    pub hint_margin: f32,
+    /// Between a tab's icon and label
+    pub tab_gap: f32,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ThemeRadius {
+    pub panel: f32,
+    pub tab: f32,
+    pub row: f32,
+    pub button: f32,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ThemeFontSizes {
+    /// The menu title
+    pub title: f32,
+    /// Titles of the built-in tabs
+    pub heading: f32,
+    /// Headings of plugin sections
+    pub section: f32,
+    pub tab: f32,
+    /// Setting labels
+    pub label: f32,
+    /// Large buttons such as "Test"
+    pub button: f32,
+    pub value: f32,
+    /// Small buttons, status lines and the undo/redo hint
+    pub small: f32,
+    pub error: f32,
+    pub menu_item: f32,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct WidgetMetrics {
+    pub tab_bar_height: f32,
+    pub tab_icon_size: f32,
+    pub row_height: f32,
+    /// Height of value and step buttons
+    pub control_height: f32,
+    pub value_button_width: f32,
+    pub step_button_width: f32,
+    pub action_button_width: f32,
+    pub action_button_height: f32,
+    pub small_button_width: f32,
+    pub small_button_height: f32,
+    pub menu_item_width: f32,
+    pub menu_item_height: f32,
+}
+
+/// Look of the settings menu, see the [module docs](self)
+///
+/// The resource holds the theme in use; the asset is what theme files load into.
+#[derive(Asset, Resource, TypePath, Deserialize, Clone, PartialEq, Debug)]
+pub struct SettingsTheme {
+    pub colors: ThemeColors,
+    pub spacing: ThemeSpacing,
+    pub radius: ThemeRadius,
+    pub font_sizes: ThemeFontSizes,
+    pub metrics: WidgetMetrics,
+}
+
+impl Default for SettingsTheme {
+    fn default() -> Self {
+        ron::from_str(EMBEDDED_THEME).expect("embedded settings theme is valid")
+    }
+}
+
+/// What a menu node is, which decides the parts of the theme it uses
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThemeElement {
+    /// The standalone panel
+    Panel,
+    /// The menu shown inside the host menu
+    EmbeddedMenu,
+    /// Tab content of the standalone panel
+    Content,
+    /// Tab content inside the host menu
+    EmbeddedContent,
+    Title,
+    TabBar,
+    /// A tab button; its background follows the selection
+    Tab,
+    TabIcon,
+    TabLabel,
+    Row,
+    Label,
+    /// Value and step buttons, and their texts, are dimmed while their setting is locked
+    ValueButton { locked: bool },
+    StepButton { locked: bool },
+    /// Text on value and step buttons
+    ButtonLabel { locked: bool },
+    Value { locked: bool },
+    Error,
+    /// Title of a built-in tab
+    Heading,
+    SectionHeading,
+    /// Container for a group of controls
+    Group,
+    ActionButton,
+    ActionLabel,
+    SmallButton,
+    /// Small button text and status lines
+    SmallText,
+    Hint,
+    MenuItem,
+    MenuItemLabel,
+    StickPlot,
+    DeadZoneRing,
+    StickDot { processed: bool },
+    /// Thumbnail of the HUD arrangement
+    HudPreview,
+    /// An element of the HUD preview; its background follows the settings
+    HudPreviewElement,
+}
+
+/// The parts of a node's look set by the theme, `None` for parts left alone
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ThemedStyle {
+    pub width: Option<Val>,
+    pub height: Option<Val>,
+    pub margin: Option<UiRect>,
+    pub padding: Option<UiRect>,
+    pub column_gap: Option<Val>,
+    pub background: Option<Color>,
+    pub border: Option<Color>,
+    pub radius: Option<f32>,
+    /// Font size and colour of every text section
+    pub text: Option<(f32, Color)>,
+}
+
+impl SettingsTheme {
+    /// How the theme styles `element`
+    pub fn style(&self, element: ThemeElement) -> ThemedStyle {
+        let (colors, spacing, radius, sizes, metrics) =
+            (&self.colors, &self.spacing, &self.radius, &self.font_sizes, &self.metrics);
+        let text = |size: f32, color: ThemeColor| Some((size, color.0));
+        let button = |width: f32, height: f32, margin: UiRect| ThemedStyle {
+            width: Some(Val::Px(width)),
+            height: Some(Val::Px(height)),
+            margin: Some(margin),
+            background: Some(colors.button.0),
+            radius: Some(radius.button),
+            ..default()
+        };
+        // Locked controls blend into their row
+        let control_button = |width: f32, locked: bool| ThemedStyle {
+            background: Some(if locked { colors.row.0 } else { colors.button.0 }),
+            ..button(width, metrics.control_height, UiRect::DEFAULT)
+        };
+        let control_text = |locked: bool| if locked { colors.muted_text } else { colors.text };
+
+        match element {
+            ThemeElement::Panel => ThemedStyle {
+                background: Some(colors.panel.0),
+                radius: Some(radius.panel),
+                ..default()
+            },
+            ThemeElement::EmbeddedMenu | ThemeElement::Content => ThemedStyle {
+                padding: Some(UiRect::all(Val::Px(spacing.panel_padding))),
+                ..default()
+            },
+            ThemeElement::EmbeddedContent => ThemedStyle {
+                padding: Some(UiRect::all(Val::Px(spacing.panel_padding))),
+                background: Some(colors.content.0),
+                ..default()
+            },
+            ThemeElement::Title => ThemedStyle {
+                margin: Some(UiRect::bottom(Val::Px(spacing.title_margin))),
+                text: text(sizes.title, colors.bright_text),
+                ..default()
+            },
+            ThemeElement::TabBar => ThemedStyle {
+                height: Some(Val::Px(metrics.tab_bar_height)),
+                background: Some(colors.tab_bar.0),
+                ..default()
+            },
+            ThemeElement::Tab => ThemedStyle {
+                column_gap: Some(Val::Px(spacing.tab_gap)),
+                radius: Some(radius.tab),
+                ..default()
+            },
+            ThemeElement::TabIcon => ThemedStyle {
+                width: Some(Val::Px(metrics.tab_icon_size)),
+                height: Some(Val::Px(metrics.tab_icon_size)),
+                ..default()
+            },
+            ThemeElement::TabLabel => ThemedStyle {
+                text: text(sizes.tab, colors.bright_text),
+                ..default()
+            },
+            ThemeElement::Row => ThemedStyle {
+                height: Some(Val::Px(metrics.row_height)),
+                margin: Some(UiRect::vertical(Val::Px(spacing.row_margin))),
+                padding: Some(UiRect::horizontal(Val::Px(spacing.row_padding))),
+                background: Some(colors.row.0),
+                radius: Some(radius.row),
+                ..default()
+            },
+            ThemeElement::Label => ThemedStyle {
+                text: text(sizes.label, colors.bright_text),
+                ..default()
+            },
+            ThemeElement::ValueButton { locked } => control_button(metrics.value_button_width, locked),
+            ThemeElement::StepButton { locked } => control_button(metrics.step_button_width, locked),
+            ThemeElement::ButtonLabel { locked } => ThemedStyle {
+                text: text(sizes.label, control_text(locked)),
+                ..default()
+            },
+            ThemeElement::Value { locked } => ThemedStyle {
+                margin: Some(UiRect::horizontal(Val::Px(spacing.value_margin))),
+                text: text(sizes.value, control_text(locked)),
+                ..default()
+            },
+            ThemeElement::Error => ThemedStyle {
+                text: text(sizes.error, colors.error),
+                ..default()
+            },
+            ThemeElement::Heading => ThemedStyle {
+                text: text(sizes.heading, colors.text),
+                ..default()
+            },
+            ThemeElement::SectionHeading => ThemedStyle {
+                margin: Some(UiRect::vertical(Val::Px(spacing.section_margin))),
+                text: text(sizes.section, colors.text),
+                ..default()
+            },
+            ThemeElement::Group => ThemedStyle {
+                margin: Some(UiRect::vertical(Val::Px(spacing.section_margin))),
+                ..default()
+            },
+            ThemeElement::ActionButton => button(
+                metrics.action_button_width,
+                metrics.action_button_height,
+                UiRect::all(Val::Px(spacing.button_margin)),
+            ),
+            ThemeElement::ActionLabel => ThemedStyle {
+                text: text(sizes.button, colors.text),
+                ..default()
+            },
+            ThemeElement::SmallButton => button(
+                metrics.small_button_width,
+                metrics.small_button_height,
+                UiRect::all(Val::Px(spacing.small_button_margin)),
+            ),
+            ThemeElement::SmallText => ThemedStyle {
+                text: text(sizes.small, colors.text),
+                ..default()
+            },
+            ThemeElement::Hint => ThemedStyle {
+                margin: Some(UiRect::top(Val::Px(spacing.hint_margin))),
+                text: text(sizes.small, colors.muted_text),
+                ..default()
+            },
+            ThemeElement::MenuItem => button(
+                metrics.menu_item_width,
+                metrics.menu_item_height,
+                UiRect::all(Val::Px(spacing.small_button_margin)),
+            ),
+            ThemeElement::MenuItemLabel => ThemedStyle {
+                text: text(sizes.menu_item, colors.bright_text),
+                ..default()
+            },
+            ThemeElement::StickPlot | ThemeElement::HudPreview => ThemedStyle {
+                margin: Some(UiRect::all(Val::Px(spacing.button_margin))),
+                background: Some(colors.plot_background.0),
+                border: Some(colors.plot_border.0),
+                ..default()
+            },
+            ThemeElement::DeadZoneRing => ThemedStyle {
+                border: Some(colors.dead_zone.0),
+                ..default()
+            },
+            ThemeElement::StickDot { processed } => ThemedStyle {
+                background: Some(if processed { colors.processed_input.0 } else { colors.raw_input.0 }),
+                ..default()
+            },
+            ThemeElement::HudPreviewElement => ThemedStyle {
+                border: Some(colors.plot_border.0),
+                ..default()
+            },
+        }
+    }
+
+    /// Background of a button for its interaction state
+    pub fn button_background(&self, interaction: Interaction) -> BackgroundColor {
+        match interaction {
+            Interaction::Pressed => self.colors.button_pressed.into(),
+            Interaction::Hovered => self.colors.button_hovered.into(),
+            Interaction::None => self.colors.button.into(),
+        }
+    }
+
+    pub fn tab_background(&self, selected: bool) -> BackgroundColor {
+        if selected {
+            self.colors.tab_selected.into()
+        } else {
+            self.colors.tab.into()
+        }
+    }
+}
+
+impl RonAsset for SettingsTheme {
+    const EXTENSION: &'static str = "theme.ron";
+    const DEFAULT_PATH: &'static str = DEFAULT_THEME_PATH;
+    const DESCRIPTION: &'static str = "settings theme";
+}
+
+/// Theme the menu loads, [`DEFAULT_THEME_PATH`] unless the host sets another
+pub type SettingsThemePath = RonAssetPath<SettingsTheme>;
+
+pub struct SettingsThemePlugin;
+
+impl Plugin for SettingsThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<SettingsTheme>::default())
+            .add_systems(PostUpdate, apply_settings_theme.before(UiAccessibilitySet));
+    }
+}
+
+/// Restyle themed nodes when they appear and whenever the theme changes
+fn apply_settings_theme(
+    theme: Res<SettingsTheme>,
+    mut nodes: Query<(Ref<ThemeElement>, ThemedNode)>,
+) {
+    for (element, mut node) in &mut nodes {
+        if !theme.is_changed() && !element.is_changed() {
+            continue;
+        }
+        node.apply(&theme.style(*element));
+    }
+}
+
+/// The parts of a node the theme writes
+#[derive(bevy::ecs::query::QueryData)]
+#[query_data(mutable)]
+struct ThemedNode {
+    style: &'static mut Style,
+    text: Option<&'static mut Text>,
+    background: Option<&'static mut BackgroundColor>,
+    border: Option<&'static mut BorderColor>,
+    radius: Option<&'static mut BorderRadius>,
+    /// Accessibility restyling starts from the themed text
+    text_base: Option<&'static mut MenuTextBase>,
+}
+
+impl ThemedNodeItem<'_> {
+    fn apply(&mut self, themed: &ThemedStyle) {
+        let style = &mut *self.style;
+        if let Some(width) = themed.width {
+            style.width = width;
+        }
+        if let Some(height) = themed.height {
+            style.height = height;
+        }
+        if let Some(margin) = themed.margin {
+            style.margin = margin;
+        }
+        if let Some(padding) = themed.padding {
+            style.padding = padding;
+        }
+        if let Some(gap) = themed.column_gap {
+            style.column_gap = gap;
+        }
+        if let (Some(color), Some(background)) = (themed.background, self.background.as_mut()) {
+            background.0 = color;
+        }
+        if let (Some(color), Some(border)) = (themed.border, self.border.as_mut()) {
+            border.0 = color;
+        }
+        if let (Some(radius), Some(border_radius)) = (themed.radius, self.radius.as_mut()) {
+            **border_radius = BorderRadius::all(Val::Px(radius));
+        }
+        if let Some((size, color)) = themed.text {
+            if let Some(text) = self.text.as_mut() {
+                for section in &mut text.sections {
+                    section.style.font_size = size;
+                    section.style.color = color;
+                }
+            }
+            if let Some(base) = self.text_base.as_mut() {
+                base.set_themed(size, color);
+            }
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Tutorial hints
+//!
+//! Game code registers each hint once under an id and asks for it when the
+//! player reaches the point it explains:
+//!
+//! ```ignore
+//! app.register_tutorial_hint(
+//!     "build-barracks",
+//!     TutorialHint::new("Barracks", "Build a barracks to train infantry."),
+//! );
+//!
+//! fn on_first_worker(mut hints: EventWriter<ShowTutorialHint>) {
+//!     hints.send(ShowTutorialHint::new("build-barracks"));
+//! }
+//! ```
+//!
+//! A hint is shown only while [`GameplaySettings::show_tutorials`] is on and only
+//! once: its id is added to [`SeenTutorialHints`], a hidden settings section
+//! saved with the settings but kept out of change events and undo history.
+//! Hints asked for while another one is up wait their turn.
+//!
+//! [`GameplaySettings::show_tutorials`]: crate::settings::GameplaySettings::show_tutorials
+
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::display::UiAccessibility;
+use crate::fonts::FontRole;
+use crate::localization::{keys, LocalizedText};
+use crate::registry::RegisterSettingsSection;
+use crate::settings::Settings;
+use crate::theme::SettingsTheme;
+use crate::SettingsMenuEntity;
+
+/// Hint text to show the player; shown as given, so translate it first
+#[derive(Clone, Debug)]
+pub struct TutorialHint {
+    pub title: String,
+    pub text: String,
+}
+
+impl TutorialHint {
+    pub fn new(title: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            text: text.into(),
+        }
+    }
+}
+
+/// Ask for the registered hint with this id to be shown
+#[derive(Event, Clone, Debug)]
+pub struct ShowTutorialHint(pub String);
+
+impl ShowTutorialHint {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+/// Registered hints and the ones waiting to be shown
+#[derive(Resource, Default)]
+pub struct TutorialHints {
+    hints: HashMap<String, TutorialHint>,
+    pending: VecDeque<String>,
+    current: Option<String>,
+}
+
+impl TutorialHints {
+    pub fn register(&mut self, id: impl Into<String>, hint: TutorialHint) {
+        self.hints.insert(id.into(), hint);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&TutorialHint> {
+        self.hints.get(id)
+    }
+
+    /// Id of the hint on screen
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Take the hint on screen down, showing the next one that is waiting
+    pub fn dismiss(&mut self) {
+        self.current = None;
+    }
+
+    fn is_queued(&self, id: &str) -> bool {
+        self.current.as_deref() == Some(id) || self.pending.iter().any(|pending| pending == id)
+    }
+}
+
+/// Ids of the tutorial hints already shown, so each is only shown once
+#[derive(Resource, Reflect, Default, Clone, Debug)]
+pub struct SeenTutorialHints {
+    pub ids: Vec<String>,
+}
+
+impl SeenTutorialHints {
+    /// Whether the hint with this id has been shown before
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.iter().any(|seen| seen == id)
+    }
+}
+
+/// Extension trait for registering tutorial hints on an [`App`]
+pub trait RegisterTutorialHint {
+    fn register_tutorial_hint(&mut self, id: impl Into<String>, hint: TutorialHint) -> &mut Self;
+}
+
+impl RegisterTutorialHint for App {
+    fn register_tutorial_hint(&mut self, id: impl Into<String>, hint: TutorialHint) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(TutorialHints::default)
+            .register(id, hint);
+        self
+    }
+}
+
+/// Card showing the current hint
+#[derive(Component)]
+pub struct TutorialHintCard;
+
+/// Button on the hint card that dismisses it
+#[derive(Component)]
+pub struct DismissTutorialHintButton;
+
+pub struct TutorialHintsPlugin;
+
+impl Plugin for TutorialHintsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShowTutorialHint>()
+            .init_resource::<TutorialHints>()
+            .register_hidden_settings_section::<SeenTutorialHints>("tutorial_hints")
+            .add_systems(Update, (
+                queue_tutorial_hints,
+                handle_dismiss_button,
+                advance_tutorial_hints,
+                show_tutorial_hint,
+            ).chain());
+    }
+}
+
+fn queue_tutorial_hints(
+    settings: Res<Settings>,
+    seen: Res<SeenTutorialHints>,
+    mut requests: EventReader<ShowTutorialHint>,
+    mut hints: ResMut<TutorialHints>,
+) {
+    for ShowTutorialHint(id) in requests.read() {
+        if !settings.gameplay.show_tutorials || seen.contains(id) || hints.is_queued(id) {
+            continue;
+        }
+        if hints.get(id).is_none() {
+            warn!("Tutorial hint {:?} was never registered", id);
+            continue;
+        }
+        hints.pending.push_back(id.clone());
+    }
+}
+
+fn handle_dismiss_button(
+    mut hints: ResMut<TutorialHints>,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<DismissTutorialHintButton>)>,
+) {
+    if buttons.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        hints.dismiss();
+    }
+}
+
+/// Put up the next waiting hint and record it as seen, or clear every hint
+/// once tutorials are turned off
+fn advance_tutorial_hints(
+    settings: Res<Settings>,
+    mut seen: ResMut<SeenTutorialHints>,
+    mut hints: ResMut<TutorialHints>,
+) {
+    if !settings.gameplay.show_tutorials {
+        if hints.current.is_some() || !hints.pending.is_empty() {
+            hints.current = None;
+            hints.pending.clear();
+        }
+        return;
+    }
+    if hints.current.is_some() {
+        return;
+    }
+
+    while let Some(id) = hints.pending.pop_front() {
+        // Reset seen hints can be pressed while one is waiting, so check again
+        if seen.contains(&id) {
+            continue;
+        }
+        seen.ids.push(id.clone());
+        hints.current = Some(id);
+        break;
+    }
+}
+
+/// Rebuild the hint card when the hint or how it looks changes
+fn show_tutorial_hint(
+    mut commands: Commands,
+    hints: Res<TutorialHints>,
+    theme: Res<SettingsTheme>,
+    options: Res<UiAccessibility>,
+    cards: Query<Entity, With<TutorialHintCard>>,
+) {
+    if !hints.is_changed() && !theme.is_changed() && !options.is_changed() {
+        return;
+    }
+    for card in &cards {
+        commands.entity(card).despawn_recursive();
+    }
+    let Some(hint) = hints.current().and_then(|id| hints.get(id)) else {
+        return;
+    };
+
+    let colors = &theme.colors;
+    let sizes = &theme.font_sizes;
+    let text_style = |font_size: f32, color: Color| TextStyle {
+        font_size: options.font_size(font_size),
+        color: options.text_color(color),
+        ..default()
+    };
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(theme.spacing.panel_padding),
+                right: Val::Px(theme.spacing.panel_padding),
+                max_width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(theme.spacing.row_padding / 2.0)),
+                ..default()
+            },
+            background_color: options.background(colors.panel.0).into(),
+            border_radius: BorderRadius::all(Val::Px(theme.radius.panel)),
+            // Above the subtitles, below the settings panel
+            z_index: ZIndex::Global(60),
+            ..default()
+        },
+        Name::new("TutorialHint"),
+        TutorialHintCard,
+        SettingsMenuEntity,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(hint.title.clone(), text_style(sizes.label, colors.bright_text.0)),
+            FontRole::Bold,
+        ));
+        parent.spawn((
+            TextBundle::from_section(hint.text.clone(), text_style(sizes.small, colors.text.0)).with_style(Style {
+                margin: UiRect::vertical(Val::Px(theme.spacing.hint_margin)),
+                ..default()
+            }),
+            FontRole::Regular,
+        ));
+        parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    align_self: AlignSelf::FlexEnd,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Px(theme.metrics.small_button_width),
+                    height: Val::Px(theme.metrics.small_button_height),
+                    ..default()
+                },
+                background_color: theme.button_background(Interaction::None),
+                border_radius: BorderRadius::all(Val::Px(theme.radius.button)),
+                ..default()
+            },
+            DismissTutorialHintButton,
+        )).with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("Got it", text_style(sizes.small, colors.text.0)),
+                LocalizedText::new(keys::TUTORIAL_DISMISS).with_fallback("Got it"),
+                FontRole::Bold,
+            ));
+        });
+    });
+}
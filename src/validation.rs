@@ -0,0 +1,405 @@
+//! Constraints on settings values
+//!
+//! Numeric fields are limited by their [`SettingRange`] reflect attribute, the
+//! same one that configures their stepper, so plugin settings sections are
+//! validated without extra code. The built-in [`Settings`] also have a few
+//! rules that span fields or aren't ranges:
+//!
+//! - the resolution is one of [`SUPPORTED_RESOLUTIONS`]
+//! - the camera's minimum zoom is at most its maximum zoom
+//! - each stick's inner dead zone is below its outer dead zone
+//!
+//! Values read from the settings file are repaired on load and the fixes are
+//! listed in [`SettingsRepairReport`]. Values edited in game are not changed,
+//! [`SettingsValidation`] lists what is currently wrong and the menu shows
+//! the message next to the offending control. The zoom limits are the
+//! exception: editing one past the other moves the other along, since camera
+//! code clamps to them every frame.
+
+use bevy::prelude::*;
+use bevy::reflect::{ReflectRef, TypeInfo, Typed, VariantInfo};
+
+use crate::changes::SettingsCommitSet;
+use crate::localization::{keys, Localization, LocalizedText, MessageArg};
+use crate::registry::{self, SettingsSections};
+use crate::settings::{CameraSettings, Settings, StickSettings};
+use crate::widgets::SettingRange;
+
+/// Resolutions the video settings accept
+pub const SUPPORTED_RESOLUTIONS: &[(u32, u32)] = &[
+    (1280, 720),
+    (1366, 768),
+    (1600, 900),
+    (1920, 1080),
+    (1920, 1200),
+    (2560, 1080),
+    (2560, 1440),
+    (3440, 1440),
+    (3840, 2160),
+];
+
+/// A setting that breaks its constraints
+pub struct SettingIssue {
+    /// Setting path, e.g. `"audio.music_volume"` or `"minimap.zoom"`
+    pub path: String,
+    /// What is wrong, e.g. "1.50 is above the maximum 1.00"; displays in English
+    pub message: LocalizedText,
+    /// Closest valid value
+    pub repaired: Box<dyn Reflect>,
+}
+
+/// Settings currently breaking their constraints, updated whenever settings change
+#[derive(Resource, Default)]
+pub struct SettingsValidation {
+    issues: Vec<SettingIssue>,
+}
+
+impl SettingsValidation {
+    pub fn iter(&self) -> impl Iterator<Item = &SettingIssue> {
+        self.issues.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issue with the setting at `path`
+    pub fn get(&self, path: &str) -> Option<&SettingIssue> {
+        self.issues.iter().find(|issue| issue.path == path)
+    }
+}
+
+/// Invalid values the settings file contained and what they were repaired to
+#[derive(Resource, Default)]
+pub struct SettingsRepairReport {
+    pub repairs: Vec<SettingIssue>,
+}
+
+/// Text next to a control showing what is wrong with the setting at `path`
+#[derive(Component, Clone, Debug)]
+pub struct SettingErrorText {
+    pub path: String,
+}
+
+pub struct SettingsValidationPlugin;
+
+impl Plugin for SettingsValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsValidation>()
+            .init_resource::<SettingsRepairReport>()
+            .add_systems(Update, (
+                validate_changed_settings,
+                update_setting_errors.after(validate_changed_settings),
+            ))
+            .add_systems(PostUpdate, keep_zoom_order.before(SettingsCommitSet));
+    }
+}
+
+/// Check `value` against its constraints
+///
+/// `prefix` is the setting path of `value` itself: empty for [`Settings`], the
+/// section id for plugin sections.
+pub fn validate_value(prefix: &str, value: &dyn Reflect) -> Vec<SettingIssue> {
+    let mut issues = Vec::new();
+    check_fields(prefix, value, &mut issues);
+    if let Some(settings) = value.downcast_ref::<Settings>() {
+        check_settings(settings, &mut issues);
+    }
+    issues
+}
+
+/// Validate [`Settings`] and every registered section
+pub fn validate_settings(world: &World) -> Vec<SettingIssue> {
+    let mut issues = validate_value("", world.resource::<Settings>());
+    if let Some(sections) = world.get_resource::<SettingsSections>() {
+        for section in sections.iter() {
+            if let Some(value) = section.reflect(world) {
+                issues.extend(validate_value(section.id, value));
+            }
+        }
+    }
+    issues
+}
+
+/// Repair [`Settings`] and every registered section, returning what was fixed
+pub fn repair_settings(world: &mut World) -> Vec<SettingIssue> {
+    let mut repairs = Vec::new();
+    // A range repair can break a rule between fields, whose repairs stay in range
+    for _ in 0..2 {
+        let issues = validate_settings(world);
+        if issues.is_empty() {
+            break;
+        }
+        for issue in issues {
+            if registry::edit_setting(world, &issue.path, |field| field.apply(issue.repaired.as_ref())) {
+                repairs.push(issue);
+            }
+        }
+    }
+    repairs
+}
+
+fn validate_changed_settings(world: &mut World) {
+    let sections = world.get_resource::<SettingsSections>().cloned().unwrap_or_default();
+    let changed = world.is_resource_changed::<Settings>()
+        || sections.iter().any(|section| section.is_changed(world));
+    if !changed {
+        return;
+    }
+
+    let issues = validate_settings(world);
+    let mut validation = world.resource_mut::<SettingsValidation>();
+    // Skip the change tick when nothing was or is wrong so error texts aren't touched
+    if !(issues.is_empty() && validation.is_empty()) {
+        validation.issues = issues;
+    }
+}
+
+fn update_setting_errors(
+    validation: Res<SettingsValidation>,
+    localization: Res<Localization>,
+    mut errors: Query<(Ref<SettingErrorText>, &mut Text)>,
+) {
+    for (error, mut text) in &mut errors {
+        if !validation.is_changed() && !localization.is_changed() && !error.is_added() {
+            continue;
+        }
+        let message = validation
+            .get(&error.path)
+            .map(|issue| localization.text(&issue.message))
+            .unwrap_or_default();
+        if text.sections[0].value != message {
+            text.sections[0].value = message;
+        }
+    }
+}
+
+/// Keep the minimum zoom at most the maximum zoom, moving the limit that wasn't
+/// edited so both land in the same commit and undo together
+fn keep_zoom_order(mut settings: ResMut<Settings>, mut last_min: Local<Option<f32>>) {
+    if !settings.is_changed() {
+        return;
+    }
+    let camera = &settings.bypass_change_detection().controls.camera;
+    let (min, max) = (camera.min_zoom, camera.max_zoom);
+    let min_edited = last_min.is_some_and(|last| last != min);
+    *last_min = Some(min);
+    if min <= max {
+        return;
+    }
+
+    let camera = &mut settings.controls.camera;
+    if min_edited {
+        camera.max_zoom = min;
+    } else {
+        camera.min_zoom = max;
+        *last_min = Some(max);
+    }
+}
+
+/// Check the numeric fields of structs and struct variants against their [`SettingRange`]
+fn check_fields(prefix: &str, value: &dyn Reflect, issues: &mut Vec<SettingIssue>) {
+    match (value.reflect_ref(), value.get_represented_type_info()) {
+        (ReflectRef::Struct(value), Some(TypeInfo::Struct(info))) => {
+            for (index, field) in value.iter_fields().enumerate() {
+                let Some(name) = value.name_at(index) else {
+                    continue;
+                };
+                let range = info.field(name).and_then(|field| field.get_attribute::<SettingRange>());
+                check_field(&join_path(prefix, name), field, range, issues);
+            }
+        }
+        (ReflectRef::Enum(value), Some(TypeInfo::Enum(info))) => {
+            let Some(VariantInfo::Struct(variant)) = info.variant(value.variant_name()) else {
+                return;
+            };
+            for (index, field) in value.iter_fields().enumerate() {
+                let Some(name) = value.name_at(index) else {
+                    continue;
+                };
+                let range = variant.field(name).and_then(|field| field.get_attribute::<SettingRange>());
+                check_field(&join_path(prefix, name), field.value(), range, issues);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_field(path: &str, value: &dyn Reflect, range: Option<&SettingRange>, issues: &mut Vec<SettingIssue>) {
+    let Some(range) = range else {
+        check_fields(path, value, issues);
+        return;
+    };
+
+    let repaired: Option<(LocalizedText, Box<dyn Reflect>)> = if let Some(value) = value.downcast_ref::<f32>() {
+        check_number(*value, range, false).map(|(message, repaired)| (message, Box::new(repaired) as Box<dyn Reflect>))
+    } else if let Some(value) = value.downcast_ref::<u32>() {
+        check_number(*value as f32, range, true)
+            .map(|(message, repaired)| (message, Box::new(repaired.round() as u32) as Box<dyn Reflect>))
+    } else if let Some(Some(value)) = value.downcast_ref::<Option<u32>>() {
+        // `None` means no limit, only set values are checked
+        check_number(*value as f32, range, true)
+            .map(|(message, repaired)| (message, Box::new(Some(repaired.round() as u32)) as Box<dyn Reflect>))
+    } else {
+        None
+    };
+
+    if let Some((message, repaired)) = repaired {
+        issues.push(SettingIssue {
+            path: path.to_string(),
+            message,
+            repaired,
+        });
+    }
+}
+
+/// The problem with `value` and the value to repair it to, if it is out of range
+fn check_number(value: f32, range: &SettingRange, integer: bool) -> Option<(LocalizedText, f32)> {
+    let show = |value: f32| MessageArg::Number {
+        value: value.into(),
+        decimals: if integer { 0 } else { range.decimals() },
+    };
+    if value.is_nan() {
+        Some((LocalizedText::new(keys::VALIDATION_NOT_A_NUMBER), range.min))
+    } else if value < range.min {
+        let message = LocalizedText::new(keys::VALIDATION_BELOW_MINIMUM)
+            .with_arg("value", show(value))
+            .with_arg("min", show(range.min));
+        Some((message, range.min))
+    } else if value > range.max {
+        let message = LocalizedText::new(keys::VALIDATION_ABOVE_MAXIMUM)
+            .with_arg("value", show(value))
+            .with_arg("max", show(range.max));
+        Some((message, range.max))
+    } else {
+        None
+    }
+}
+
+/// Rules between fields of the built-in settings
+fn check_settings(settings: &Settings, issues: &mut Vec<SettingIssue>) {
+    let (width, height) = settings.video.resolution;
+    if !SUPPORTED_RESOLUTIONS.contains(&(width, height)) {
+        let fallback = crate::settings::VideoSettings::default().resolution;
+        issues.push(SettingIssue {
+            path: "video.resolution".to_string(),
+            message: LocalizedText::new(keys::VALIDATION_UNSUPPORTED_RESOLUTION)
+                .with_arg("width", width)
+                .with_arg("height", height),
+            repaired: Box::new(fallback),
+        });
+    }
+
+    let camera = &settings.controls.camera;
+    if camera.min_zoom > camera.max_zoom {
+        issues.push(SettingIssue {
+            path: "controls.camera.max_zoom".to_string(),
+            message: LocalizedText::new(keys::VALIDATION_ZOOM_ORDER).with_arg("min", decimal::<CameraSettings>("min_zoom", camera.min_zoom)),
+            repaired: Box::new(camera.min_zoom),
+        });
+    }
+
+    let controller = &settings.controls.controller;
+    for (name, stick) in [("left_stick", &controller.left_stick), ("right_stick", &controller.right_stick)] {
+        if stick.inner_dead_zone >= stick.outer_dead_zone {
+            issues.push(SettingIssue {
+                path: format!("controls.controller.{name}.inner_dead_zone"),
+                message: LocalizedText::new(keys::VALIDATION_DEAD_ZONE_ORDER)
+                    .with_arg("outer", decimal::<StickSettings>("outer_dead_zone", stick.outer_dead_zone)),
+                repaired: Box::new((stick.outer_dead_zone - 0.05).max(0.0)),
+            });
+        }
+    }
+}
+
+/// `value` of `field` of `T`, with as many decimals as the menu shows for it
+fn decimal<T: Typed>(field: &str, value: f32) -> MessageArg {
+    let decimals = SettingRange::of::<T>(field).map_or(2, |range| range.decimals());
+    MessageArg::Number { value: value.into(), decimals }
+}
+
+fn join_path(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range() -> SettingRange {
+        SettingRange::new(0.5, 2.0, 0.1)
+    }
+
+    #[test]
+    fn numbers_in_range_pass() {
+        assert!(check_number(0.5, &range(), false).is_none());
+        assert!(check_number(1.25, &range(), false).is_none());
+        assert!(check_number(2.0, &range(), false).is_none());
+    }
+
+    #[test]
+    fn numbers_out_of_range_repair_to_the_nearest_limit() {
+        let (message, repaired) = check_number(0.1, &range(), false).unwrap();
+        assert_eq!(message.id, keys::VALIDATION_BELOW_MINIMUM);
+        assert_eq!(repaired, 0.5);
+
+        let (message, repaired) = check_number(7.0, &range(), true).unwrap();
+        assert_eq!(message.id, keys::VALIDATION_ABOVE_MAXIMUM);
+        assert_eq!(repaired, 2.0);
+
+        let (message, repaired) = check_number(f32::NAN, &range(), false).unwrap();
+        assert_eq!(message.id, keys::VALIDATION_NOT_A_NUMBER);
+        assert_eq!(repaired, 0.5);
+    }
+
+    #[test]
+    fn default_settings_are_valid() {
+        let issues = validate_value("", &Settings::default());
+        assert!(issues.is_empty(), "{:?}", issues.iter().map(|issue| &issue.path).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn repairs_out_of_range_fields_and_broken_rules() {
+        let mut settings = Settings::default();
+        settings.audio.music_volume = -1.0;
+        settings.video.ui_scale = f32::NAN;
+        settings.gameplay.autosave.interval_minutes = Some(500);
+        settings.gameplay.autosave.slots = 0;
+        settings.controls.camera.min_zoom = 4.0;
+        settings.controls.camera.max_zoom = 2.0;
+        let mut world = World::new();
+        world.insert_resource(settings);
+
+        let mut repaired: Vec<String> = repair_settings(&mut world).into_iter().map(|issue| issue.path).collect();
+        repaired.sort();
+        assert_eq!(
+            repaired,
+            [
+                "audio.music_volume",
+                "controls.camera.max_zoom",
+                "gameplay.autosave.interval_minutes",
+                "gameplay.autosave.slots",
+                "video.ui_scale",
+            ],
+        );
+
+        let settings = world.resource::<Settings>();
+        assert_eq!(settings.audio.music_volume, 0.0);
+        assert_eq!(settings.video.ui_scale, 0.5);
+        assert_eq!(settings.gameplay.autosave.interval_minutes, Some(60));
+        assert_eq!(settings.gameplay.autosave.slots, 1);
+        assert_eq!(settings.controls.camera.max_zoom, 4.0);
+        assert!(validate_settings(&world).is_empty());
+    }
+
+    #[test]
+    fn unset_optional_numbers_are_not_checked() {
+        let mut settings = Settings::default();
+        settings.gameplay.autosave.interval_minutes = None;
+        assert!(validate_value("", &settings).is_empty());
+    }
+}
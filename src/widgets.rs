@@ -0,0 +1,548 @@
+//! Reflection driven settings widgets
+//!
+//! Each control is bound to a reflection path inside [`Settings`](crate::settings::Settings)
+//! or a registered settings section (for example `"controls.camera.edge_scroll"` or
+//! `"minimap.zoom"`), so new fields only need a row spawned in their tab instead of
+//! bespoke interaction systems.
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, ReflectRef, TypeInfo, Typed, VariantInfo};
+
+use crate::accessibility::{AccessibleControl, ControlRole};
+use crate::fonts::FontRole;
+use crate::localization::{self, LocalizedText};
+use crate::registry::{self, SettingsSectionInfo};
+use crate::display::UiAccessibilitySet;
+use crate::theme::{SettingsTheme, ThemeElement};
+use crate::validation::SettingErrorText;
+
+/// How a setting is edited
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlKind {
+    /// `bool` field flipped by a single button
+    Toggle,
+    /// Numeric field changed in fixed steps between `min` and `max`
+    Stepper { min: f32, max: f32, step: f32 },
+    /// Unit-variant enum field that advances to the next variant on click
+    Cycle,
+}
+
+/// Button editing the setting at `path`
+#[derive(Component, Clone, Debug)]
+pub struct SettingButton {
+    pub path: String,
+    pub action: SettingAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SettingAction {
+    Toggle,
+    Step { delta: f32, min: f32, max: f32 },
+    Cycle,
+}
+
+/// Text displaying the current value of the setting at `path`
+#[derive(Component, Clone, Debug)]
+pub struct SettingValueText {
+    pub path: String,
+}
+
+/// Range and step of a numeric settings field
+///
+/// Plugin settings sections attach it to fields as a reflect attribute,
+/// e.g. `#[reflect(@SettingRange::new(0.0, 1.0, 0.05))]`, to configure the
+/// generated stepper. Values outside the range are reported by
+/// [`validation`](crate::validation) and repaired when loaded.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct SettingRange {
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl SettingRange {
+    pub const fn new(min: f32, max: f32, step: f32) -> Self {
+        Self { min, max, step }
+    }
+
+    /// Decimals needed to show multiples of the step, e.g. 2 for 0.05
+    pub fn decimals(&self) -> usize {
+        (0..4)
+            .find(|decimals| {
+                let scaled = self.step * 10f32.powi(*decimals as i32);
+                (scaled - scaled.round()).abs() < 1e-3
+            })
+            .unwrap_or(4)
+    }
+
+    /// Range attached to `field` of the struct `T`
+    pub fn of<T: Typed>(field: &str) -> Option<Self> {
+        let TypeInfo::Struct(info) = T::type_info() else {
+            return None;
+        };
+        info.field(field)?.get_attribute::<SettingRange>().copied()
+    }
+}
+
+impl Default for SettingRange {
+    fn default() -> Self {
+        Self::new(0.0, 1.0, 0.05)
+    }
+}
+
+impl From<SettingRange> for ControlKind {
+    fn from(range: SettingRange) -> Self {
+        ControlKind::Stepper { min: range.min, max: range.max, step: range.step }
+    }
+}
+
+/// Settings the menu can't edit right now, e.g. while a match is running
+///
+/// Locking a path also locks the fields inside it.
+#[derive(Resource, Default, Debug)]
+pub struct LockedSettings {
+    paths: Vec<String>,
+}
+
+impl LockedSettings {
+    pub fn lock(&mut self, path: impl Into<String>) {
+        let path = path.into();
+        if !self.paths.contains(&path) {
+            self.paths.push(path);
+        }
+    }
+
+    pub fn unlock(&mut self, path: &str) {
+        self.paths.retain(|locked| locked != path);
+    }
+
+    pub fn is_locked(&self, path: &str) -> bool {
+        self.paths.iter().any(|locked| {
+            path.strip_prefix(locked.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+}
+
+pub struct SettingsWidgetsPlugin;
+
+impl Plugin for SettingsWidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SettingRange>()
+            .init_resource::<LockedSettings>()
+            .add_systems(Update, (
+                handle_setting_buttons,
+                update_setting_value_text.after(handle_setting_buttons),
+                show_locked_controls,
+            ))
+            .add_systems(PostUpdate, keep_locked_buttons_dim.before(UiAccessibilitySet));
+    }
+}
+
+/// Apply button presses to the bound settings fields, ignoring locked ones
+fn handle_setting_buttons(
+    world: &mut World,
+    buttons: &mut QueryState<(&Interaction, &SettingButton), Changed<Interaction>>,
+) {
+    let pressed: Vec<SettingButton> = buttons
+        .iter(world)
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .filter(|(_, button)| !registry::is_locked(world, &button.path))
+        .map(|(_, button)| button.clone())
+        .collect();
+
+    for button in pressed {
+        let found = registry::edit_setting(world, &button.path, |field| match button.action {
+            SettingAction::Toggle => {
+                if let Some(value) = field.downcast_mut::<bool>() {
+                    *value = !*value;
+                }
+            }
+            SettingAction::Step { delta, min, max } => step_value(field, delta, min, max),
+            SettingAction::Cycle => cycle_variant(field),
+        });
+        if !found {
+            warn!("Settings widget bound to unknown path {:?}", button.path);
+        }
+    }
+}
+
+/// Dim the buttons and values of locked settings, and undim them once unlocked
+fn show_locked_controls(
+    locks: Res<LockedSettings>,
+    buttons: Query<(Entity, &SettingButton, Option<&Children>)>,
+    values: Query<(Entity, &SettingValueText)>,
+    added_buttons: Query<(), Added<SettingButton>>,
+    added_values: Query<(), Added<SettingValueText>>,
+    mut elements: Query<&mut ThemeElement>,
+) {
+    if !locks.is_changed() && added_buttons.is_empty() && added_values.is_empty() {
+        return;
+    }
+
+    let mut set_locked = |entity: Entity, locked: bool| {
+        let Ok(mut element) = elements.get_mut(entity) else {
+            return;
+        };
+        let updated = match *element {
+            ThemeElement::ValueButton { .. } => ThemeElement::ValueButton { locked },
+            ThemeElement::StepButton { .. } => ThemeElement::StepButton { locked },
+            ThemeElement::ButtonLabel { .. } => ThemeElement::ButtonLabel { locked },
+            ThemeElement::Value { .. } => ThemeElement::Value { locked },
+            other => other,
+        };
+        element.set_if_neq(updated);
+    };
+
+    for (entity, button, children) in &buttons {
+        let locked = locks.is_locked(&button.path);
+        set_locked(entity, locked);
+        for &child in children.into_iter().flatten() {
+            set_locked(child, locked);
+        }
+    }
+    for (entity, value) in &values {
+        set_locked(entity, locks.is_locked(&value.path));
+    }
+}
+
+/// Undo the hover and press highlights the tabs give locked buttons
+fn keep_locked_buttons_dim(
+    theme: Res<SettingsTheme>,
+    mut buttons: Query<(&ThemeElement, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (element, mut background) in &mut buttons {
+        if !matches!(element, ThemeElement::ValueButton { locked: true } | ThemeElement::StepButton { locked: true }) {
+            continue;
+        }
+        if let Some(color) = theme.style(*element).background {
+            background.set_if_neq(BackgroundColor(color));
+        }
+    }
+}
+
+/// Keep value labels in sync with the settings they display
+fn update_setting_value_text(
+    world: &mut World,
+    labels: &mut QueryState<(Entity, &SettingValueText)>,
+) {
+    let updates: Vec<(Entity, String)> = labels
+        .iter(world)
+        .filter_map(|(entity, label)| {
+            registry::read_setting(world, &label.path, |value| localization::format_setting(world, &label.path, value))
+                .map(|value| (entity, value))
+        })
+        .collect();
+
+    for (entity, value) in updates {
+        let Some(mut text) = world.get_mut::<Text>(entity) else {
+            continue;
+        };
+        // Only touch the text when the value changed to avoid relayouts
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+fn step_value(field: &mut dyn Reflect, delta: f32, min: f32, max: f32) {
+    if let Some(value) = field.downcast_mut::<f32>() {
+        // Round to the step grid so repeated presses don't accumulate float error
+        *value = ((*value + delta) / delta.abs()).round() * delta.abs();
+        *value = value.clamp(min, max);
+    } else if let Some(value) = field.downcast_mut::<u32>() {
+        *value = (*value as f32 + delta).clamp(min, max).round() as u32;
+    } else if let Some(value) = field.downcast_mut::<Option<u32>>() {
+        // Stepping down from the minimum unsets the value and stepping up starts at the minimum
+        *value = match *value {
+            None if delta > 0.0 => Some(min.round() as u32),
+            None => None,
+            Some(current) if current as f32 + delta < min => None,
+            Some(current) => Some((current as f32 + delta).clamp(min, max).round() as u32),
+        };
+    }
+}
+
+/// Advance a unit-variant enum to its next variant, wrapping around
+fn cycle_variant(field: &mut dyn Reflect) {
+    let Some(TypeInfo::Enum(info)) = field.get_represented_type_info() else {
+        return;
+    };
+    let ReflectRef::Enum(current) = field.reflect_ref() else {
+        return;
+    };
+
+    // Only unit variants can be built without data, skip over the others
+    let count = info.variant_len();
+    let next = (1..=count)
+        .map(|offset| (current.variant_index() + offset) % count)
+        .filter_map(|index| info.variant_at(index))
+        .find(|variant| matches!(variant, VariantInfo::Unit(_)));
+    if let Some(variant) = next {
+        field.apply(&DynamicEnum::new(variant.name(), DynamicVariant::Unit));
+    }
+}
+
+/// Display string for a settings value, in English
+///
+/// The menu shows values through [`localization::format_setting`] instead.
+pub fn format_value(value: &dyn Reflect) -> String {
+    if let Some(value) = value.downcast_ref::<bool>() {
+        return if *value { "On" } else { "Off" }.to_string();
+    }
+    if let Some(value) = value.downcast_ref::<f32>() {
+        return format!("{value:.2}");
+    }
+    if let Some(value) = value.downcast_ref::<u32>() {
+        return value.to_string();
+    }
+    if let ReflectRef::Enum(value) = value.reflect_ref() {
+        return value.variant_name().to_string();
+    }
+    format!("{value:?}")
+}
+
+/// Helper function to spawn a settings row with a label and content
+pub fn spawn_setting_row<'a, F>(parent: &'a mut ChildBuilder, label: &str, content_builder: F) -> EntityCommands<'a>
+where
+    F: FnOnce(&mut ChildBuilder),
+{
+    spawn_row(parent, label, (), content_builder)
+}
+
+/// Settings row whose label text also gets `label_extras`
+fn spawn_row<'a, F>(parent: &'a mut ChildBuilder, label: &str, label_extras: impl Bundle, content_builder: F) -> EntityCommands<'a>
+where
+    F: FnOnce(&mut ChildBuilder),
+{
+    let mut row = parent.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(80.0),
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+        ThemeElement::Row,
+    ));
+    row.with_children(|parent| {
+        // Setting label
+        parent.spawn((
+            TextBundle::from_section(label, TextStyle::default()),
+            ThemeElement::Label,
+            FontRole::Regular,
+            label_extras,
+        ));
+
+        // Setting control - built by the provided function
+        content_builder(parent);
+    });
+    row
+}
+
+/// Spawn a labelled row whose control edits the setting at `path`
+///
+/// `label` is shown when the current language has no message for the setting.
+pub fn spawn_setting_control(parent: &mut ChildBuilder, label: &str, path: &str, kind: ControlKind) {
+    spawn_row(parent, label, LocalizedText::setting(path, label), |parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        }).with_children(|parent| match kind {
+            ControlKind::Toggle => {
+                spawn_value_button(parent, path, SettingAction::Toggle, AccessibleControl::new(path, label, ControlRole::Toggle));
+            }
+            ControlKind::Cycle => {
+                spawn_value_button(parent, path, SettingAction::Cycle, AccessibleControl::new(path, label, ControlRole::Selector));
+            }
+            ControlKind::Stepper { min, max, step } => {
+                spawn_step_button(parent, "-", path, SettingAction::Step { delta: -step, min, max })
+                    .insert(AccessibleControl::new(path, label, ControlRole::Decrease));
+                spawn_value_text(parent, path)
+                    .insert(AccessibleControl::new(path, label, ControlRole::Slider(SettingRange::new(min, max, step))));
+                spawn_step_button(parent, "+", path, SettingAction::Step { delta: step, min, max })
+                    .insert(AccessibleControl::new(path, label, ControlRole::Increase));
+            }
+        });
+
+        parent.spawn((
+            TextBundle::from_section("", TextStyle::default()),
+            ThemeElement::Error,
+            SettingErrorText {
+                path: path.to_string(),
+            },
+            FontRole::Regular,
+        ));
+    }).insert(AccessibleControl::new(path, label, ControlRole::Row));
+}
+
+/// Button that shows the current value and edits it when pressed
+fn spawn_value_button(parent: &mut ChildBuilder, path: &str, action: SettingAction, accessible: AccessibleControl) {
+    parent.spawn((
+        ButtonBundle {
+            style: control_button_style(),
+            ..default()
+        },
+        ThemeElement::ValueButton { locked: false },
+        SettingButton {
+            path: path.to_string(),
+            action,
+        },
+        accessible,
+    )).with_children(|parent| {
+        spawn_value_text(parent, path);
+    });
+}
+
+fn spawn_step_button<'a>(parent: &'a mut ChildBuilder, label: &str, path: &str, action: SettingAction) -> EntityCommands<'a> {
+    let mut button = parent.spawn((
+        ButtonBundle {
+            style: control_button_style(),
+            ..default()
+        },
+        ThemeElement::StepButton { locked: false },
+        SettingButton {
+            path: path.to_string(),
+            action,
+        },
+    ));
+    button.with_children(|parent| {
+        parent.spawn((
+            TextBundle::from_section(label, TextStyle::default()),
+            ThemeElement::ButtonLabel { locked: false },
+            FontRole::Bold,
+        ));
+    });
+    button
+}
+
+fn spawn_value_text<'a>(parent: &'a mut ChildBuilder, path: &str) -> EntityCommands<'a> {
+    parent.spawn((
+        TextBundle::from_section("", TextStyle::default()),
+        ThemeElement::Value { locked: false },
+        SettingValueText {
+            path: path.to_string(),
+        },
+        FontRole::Regular,
+    ))
+}
+
+fn control_button_style() -> Style {
+    Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+/// Spawn a heading and one control per field of a plugin settings section
+pub fn spawn_section_controls(parent: &mut ChildBuilder, section: &SettingsSectionInfo) {
+    let TypeInfo::Struct(info) = section.type_info else {
+        warn!("Settings section {:?} is not a struct and can't be shown", section.id);
+        return;
+    };
+
+    parent.spawn((
+        TextBundle::from_section(section.label, TextStyle::default()),
+        ThemeElement::SectionHeading,
+        LocalizedText::new(localization::section_id(section.id)).with_fallback(section.label),
+        FontRole::Bold,
+    ));
+
+    // Field kinds are read from a default instance since `TypeInfo` doesn't
+    // describe the field types themselves
+    let defaults = section.default_value();
+    let ReflectRef::Struct(defaults) = defaults.reflect_ref() else {
+        return;
+    };
+
+    for (index, value) in defaults.iter_fields().enumerate() {
+        let Some(name) = defaults.name_at(index) else {
+            continue;
+        };
+
+        let kind = if value.is::<bool>() {
+            ControlKind::Toggle
+        } else if value.is::<f32>() || value.is::<u32>() {
+            info.field(name)
+                .and_then(|field| field.get_attribute::<SettingRange>())
+                .copied()
+                .unwrap_or_default()
+                .into()
+        } else if let ReflectRef::Enum(_) = value.reflect_ref() {
+            ControlKind::Cycle
+        } else {
+            // Nested structs and collections have no generic control
+            continue;
+        };
+
+        let path = format!("{}.{}", section.id, name);
+        spawn_setting_control(parent, &field_label(name), &path, kind);
+    }
+}
+
+/// Turn a field name like `show_pings` into a label like `Show pings`
+pub fn field_label(name: &str) -> String {
+    let mut label = name.replace('_', " ");
+    if let Some(first) = label.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step_optional(value: Option<u32>, delta: f32) -> Option<u32> {
+        let mut value = value;
+        step_value(&mut value, delta, 5.0, 60.0);
+        value
+    }
+
+    #[test]
+    fn optional_steps_within_range() {
+        assert_eq!(step_optional(Some(10), 5.0), Some(15));
+        assert_eq!(step_optional(Some(10), -5.0), Some(5));
+        assert_eq!(step_optional(Some(60), 5.0), Some(60));
+    }
+
+    #[test]
+    fn optional_unsets_below_minimum_and_restarts_at_it() {
+        assert_eq!(step_optional(Some(5), -5.0), None);
+        assert_eq!(step_optional(None, -5.0), None);
+        assert_eq!(step_optional(None, 5.0), Some(5));
+    }
+
+    #[test]
+    fn locks_cover_the_path_and_fields_inside_it() {
+        let mut locks = LockedSettings::default();
+        locks.lock("gameplay.difficulty");
+
+        assert!(locks.is_locked("gameplay.difficulty"));
+        assert!(locks.is_locked("gameplay.difficulty.level"));
+        assert!(!locks.is_locked("gameplay"));
+        assert!(!locks.is_locked("gameplay.difficulty_bonus"));
+        assert!(!locks.is_locked("gameplay.custom_difficulty"));
+    }
+
+    #[test]
+    fn unlock_removes_only_that_lock() {
+        let mut locks = LockedSettings::default();
+        locks.lock("audio");
+        locks.lock("audio");
+        locks.lock("minimap");
+        locks.unlock("audio");
+
+        assert!(!locks.is_locked("audio.music_volume"));
+        assert!(locks.is_locked("minimap.zoom"));
+    }
+}
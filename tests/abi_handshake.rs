@@ -0,0 +1,64 @@
+//! Loads the built plugin library the way the game does and checks the
+//! metadata a host queries before calling the constructor.
+
+#![cfg(target_os = "linux")]
+
+use std::ffi::{c_char, CStr};
+use std::path::Path;
+
+use libloading::{Library, Symbol};
+
+/// Set by build.rs to the library built alongside the tests
+fn plugin_library_path() -> &'static Path {
+    Path::new(env!("SF_PLUGIN_LIBRARY"))
+}
+
+unsafe fn query_string(library: &Library, symbol: &[u8]) -> String {
+    let function: Symbol<extern "C" fn() -> *const c_char> = library
+        .get(symbol)
+        .unwrap_or_else(|err| panic!("missing export {}: {}", String::from_utf8_lossy(symbol), err));
+    CStr::from_ptr(function()).to_str().unwrap().to_string()
+}
+
+#[test]
+fn metadata_matches_the_build() {
+    let path = plugin_library_path();
+    let library = unsafe { Library::new(path) }
+        .unwrap_or_else(|err| panic!("failed to load {:?}: {}", path, err));
+
+    unsafe {
+        let abi_version: Symbol<extern "C" fn() -> u32> =
+            library.get(b"settings_plugin_abi_version").unwrap();
+        assert_eq!(abi_version(), sf_settings_menu::SETTINGS_PLUGIN_ABI_VERSION);
+
+        assert_eq!(query_string(&library, b"settings_plugin_bevy_version"), sf_settings_menu::BEVY_VERSION);
+        assert_eq!(
+            query_string(&library, b"settings_plugin_template_version"),
+            sf_settings_menu::PLUGIN_TEMPLATE_VERSION
+        );
+        assert_eq!(query_string(&library, b"settings_plugin_rustc_version"), sf_settings_menu::RUSTC_VERSION);
+        assert_eq!(query_string(&library, b"settings_plugin_name"), "SettingsMenuPlugin");
+        assert!(!query_string(&library, b"settings_plugin_version").is_empty());
+    }
+}
+
+#[test]
+fn handle_round_trips_through_the_library() {
+    let path = plugin_library_path();
+    let library = unsafe { Library::new(path) }
+        .unwrap_or_else(|err| panic!("failed to load {:?}: {}", path, err));
+
+    unsafe {
+        let create: Symbol<extern "C" fn() -> *mut std::ffi::c_void> =
+            library.get(b"create_settings_plugin").unwrap();
+        let destroy: Symbol<unsafe extern "C" fn(*mut std::ffi::c_void)> =
+            library.get(b"destroy_settings_plugin").unwrap();
+
+        let handle = create();
+        assert!(!handle.is_null());
+        destroy(handle);
+
+        // Null is accepted so hosts can free unconditionally
+        destroy(std::ptr::null_mut());
+    }
+}
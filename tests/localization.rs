@@ -0,0 +1,80 @@
+//! Checks the shipped message files against the strings the menu shows.
+
+use bevy::prelude::*;
+use sf_settings_menu::localization::{self, Language, Localization, LocalizationPlugin, LocalizedText};
+use sf_settings_menu::{Settings, SettingRange};
+
+#[test]
+fn shipped_locales_parse() {
+    let localization = Localization::shipped();
+    assert!(localization.errors().is_empty(), "{:#?}", localization.errors());
+}
+
+/// `language: id` for every id some shipped language doesn't define
+fn missing_messages(ids: &[String]) -> Vec<String> {
+    let localization = Localization::shipped();
+    let mut missing = Vec::new();
+    for language in Language::ALL {
+        for id in ids {
+            if !localization.has_message(language, id) {
+                missing.push(format!("{}: {}", language.id(), id));
+            }
+        }
+    }
+    missing
+}
+
+#[test]
+fn every_locale_has_every_label() {
+    let missing = missing_messages(&localization::required_messages());
+    assert!(missing.is_empty(), "missing messages:\n{}", missing.join("\n"));
+}
+
+#[test]
+fn every_locale_has_every_english_message() {
+    let english: Vec<String> = Language::English.message_ids().into_iter().map(String::from).collect();
+    assert!(english.len() > 100, "only found {} English messages", english.len());
+    let missing = missing_messages(&english);
+    assert!(missing.is_empty(), "missing messages:\n{}", missing.join("\n"));
+}
+
+#[test]
+fn values_follow_the_language() {
+    let mut localization = Localization::shipped();
+    let volume = SettingRange::new(0.0, 1.0, 0.05);
+    let margin = SettingRange::new(1.0, 100.0, 1.0);
+
+    assert_eq!(localization.format_value("audio.music_volume", &0.3f32, Some(volume)), "0.30");
+    assert_eq!(localization.format_value("video.fps_limit", &Some(60u32), None), "60 FPS");
+    assert_eq!(localization.format_value("video.fps_limit", &None::<u32>, None), "Unlimited");
+    assert_eq!(localization.format_value("controls.camera.edge_scroll_margin", &1.0f32, Some(margin)), "1 pixel");
+    assert_eq!(localization.format_value("controls.camera.edge_scroll_margin", &12.0f32, Some(margin)), "12 pixels");
+    assert_eq!(localization.format_value("interface.high_contrast", &true, None), "On");
+
+    localization.set_language(Language::German);
+    assert_eq!(localization.format_value("audio.music_volume", &0.3f32, Some(volume)), "0,30");
+    assert_eq!(localization.format_value("video.fps_limit", &Some(60u32), None), "60 FPS");
+    assert_eq!(localization.format_value("interface.language", &Language::French, None), "Français");
+    assert_eq!(localization.setting_label("audio.music_volume", "Music volume"), "Musiklautstärke");
+    // Settings nobody translated keep the label given in code
+    assert_eq!(localization.setting_label("minimap.zoom", "Zoom"), "Zoom");
+}
+
+#[test]
+fn open_menu_is_relabelled_when_the_language_changes() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<Settings>()
+        .add_plugins(LocalizationPlugin);
+    let label = app
+        .world_mut()
+        .spawn((Text::from_section("", default()), LocalizedText::setting("audio.music_volume", "Music volume")))
+        .id();
+
+    app.update();
+    assert_eq!(app.world().get::<Text>(label).unwrap().sections[0].value, "Music volume");
+
+    app.world_mut().resource_mut::<Settings>().interface.language = Language::Spanish;
+    app.update();
+    assert_eq!(app.world().get::<Text>(label).unwrap().sections[0].value, "Volumen de la música");
+}